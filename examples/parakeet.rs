@@ -24,7 +24,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Loading model: {:?}", model_path);
 
     let load_start = Instant::now();
-    let mut model = ParakeetModel::load(&model_path, &Quantization::Int8)?;
+    let model = ParakeetModel::load(&model_path, &Quantization::Int8)?;
     let load_duration = load_start.elapsed();
     println!("Model loaded in {:.2?}", load_duration);
 
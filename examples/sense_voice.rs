@@ -52,7 +52,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     );
 
     let load_start = Instant::now();
-    let mut model = SenseVoiceModel::load(&model_path, &quantization)?;
+    let model = SenseVoiceModel::load(&model_path, &quantization)?;
     let load_duration = load_start.elapsed();
     println!("Model loaded in {:.2?}", load_duration);
 
@@ -0,0 +1,261 @@
+//! Async wrapper for local models.
+//!
+//! Requires the `async` feature. [`AsyncSpeechModel`] owns a
+//! `Box<dyn SpeechModel>` and runs it on a dedicated inference thread, so
+//! async services can `await` transcriptions without blocking the runtime
+//! or wrapping the model in `spawn_blocking` and a `Mutex`.
+//!
+//! Requests are processed one at a time in arrival order. At most
+//! `queue_capacity` requests wait in the queue; further callers wait in
+//! [`transcribe()`](AsyncSpeechModel::transcribe) until there is room.
+//!
+//! Uses only `tokio::sync`, so it works under any executor.
+//!
+//! ```ignore
+//! use std::sync::Arc;
+//! use transcribe_rs::AsyncSpeechModel;
+//!
+//! let model = Arc::new(AsyncSpeechModel::new(Box::new(engine))?);
+//! let result = model.transcribe(samples, TranscribeOptions::default()).await?;
+//! ```
+
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::thread;
+
+use tokio::sync::{mpsc, oneshot};
+
+use crate::transcriber::Transcriber;
+use crate::{
+    ModelCapabilities, SpeechModel, TranscribeError, TranscribeOptions, TranscriptionResult,
+};
+
+/// Default number of requests that may wait for the inference thread.
+const DEFAULT_QUEUE_CAPACITY: usize = 16;
+
+enum Input {
+    Samples(Vec<f32>),
+    File(PathBuf),
+    Chunked(Vec<f32>, Box<dyn Transcriber>),
+}
+
+struct Job {
+    input: Input,
+    options: TranscribeOptions,
+    reply: oneshot::Sender<Result<TranscriptionResult, TranscribeError>>,
+}
+
+/// A [`SpeechModel`] running on its own thread behind an async API.
+///
+/// Cheap to share: all methods take `&self`, so wrap it in an `Arc` and call
+/// it from any number of tasks. Dropping the wrapper lets the thread finish
+/// queued requests and then drop the model.
+pub struct AsyncSpeechModel {
+    queue: mpsc::Sender<Job>,
+    capabilities: ModelCapabilities,
+}
+
+impl AsyncSpeechModel {
+    /// Start the inference thread with the default queue capacity.
+    pub fn new(model: Box<dyn SpeechModel>) -> Result<Self, TranscribeError> {
+        Self::with_queue_capacity(model, DEFAULT_QUEUE_CAPACITY)
+    }
+
+    /// Start the inference thread, allowing up to `capacity` queued requests
+    /// (at least 1).
+    pub fn with_queue_capacity(
+        model: Box<dyn SpeechModel>,
+        capacity: usize,
+    ) -> Result<Self, TranscribeError> {
+        let capabilities = model.capabilities();
+        let (queue, jobs) = mpsc::channel(capacity.max(1));
+        thread::Builder::new()
+            .name(format!("transcribe-rs-{}", capabilities.engine_id))
+            .spawn(move || run_worker(model, jobs))?;
+        Ok(Self {
+            queue,
+            capabilities,
+        })
+    }
+
+    /// Capabilities of the wrapped model.
+    pub fn capabilities(&self) -> &ModelCapabilities {
+        &self.capabilities
+    }
+
+    /// Transcribe 16 kHz mono samples with [`SpeechModel::transcribe`].
+    ///
+    /// Waits for room in the queue, then for the result. If the returned
+    /// future is dropped before the request starts, the request is skipped;
+    /// use [`TranscribeOptions::cancel`] to stop one that is running.
+    pub async fn transcribe(
+        &self,
+        samples: Vec<f32>,
+        options: TranscribeOptions,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        self.submit(Input::Samples(samples), options).await
+    }
+
+    /// Transcribe a WAV file with [`SpeechModel::transcribe_file`]. The file
+    /// is read on the inference thread.
+    pub async fn transcribe_file(
+        &self,
+        wav_path: impl AsRef<Path>,
+        options: TranscribeOptions,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        self.submit(Input::File(wav_path.as_ref().to_path_buf()), options)
+            .await
+    }
+
+    /// Transcribe 16 kHz mono samples with `transcriber` (e.g.
+    /// [`VadChunked`](crate::transcriber::VadChunked) for long audio). The
+    /// transcriber's own options apply; the transcriber is dropped
+    /// afterwards.
+    pub async fn transcribe_chunked(
+        &self,
+        samples: Vec<f32>,
+        transcriber: Box<dyn Transcriber>,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        self.submit(
+            Input::Chunked(samples, transcriber),
+            TranscribeOptions::default(),
+        )
+        .await
+    }
+
+    /// Number of requests that can be queued right now without waiting.
+    pub fn available_capacity(&self) -> usize {
+        self.queue.capacity()
+    }
+
+    async fn submit(
+        &self,
+        input: Input,
+        options: TranscribeOptions,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        let (reply, result) = oneshot::channel();
+        let job = Job {
+            input,
+            options,
+            reply,
+        };
+        self.queue.send(job).await.map_err(|_| worker_stopped())?;
+        result.await.map_err(|_| worker_stopped())?
+    }
+}
+
+fn worker_stopped() -> TranscribeError {
+    TranscribeError::Inference("inference thread stopped".to_string())
+}
+
+fn run_worker(mut model: Box<dyn SpeechModel>, mut jobs: mpsc::Receiver<Job>) {
+    while let Some(mut job) = jobs.blocking_recv() {
+        if job.reply.is_closed() {
+            log::debug!("skipping abandoned request");
+            continue;
+        }
+        // A panicking engine fails its request, not every later one.
+        let result = panic::catch_unwind(AssertUnwindSafe(|| match &mut job.input {
+            Input::Samples(samples) => model.transcribe(samples, &job.options),
+            Input::File(path) => model.transcribe_file(path, &job.options),
+            Input::Chunked(samples, transcriber) => transcriber.transcribe(model.as_mut(), samples),
+        }))
+        .unwrap_or_else(|_| {
+            log::error!("{} panicked during inference", model.capabilities().name);
+            Err(TranscribeError::Inference("model panicked".to_string()))
+        });
+        let _ = job.reply.send(result);
+    }
+    log::debug!("inference thread exiting");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcriber::test_helpers::MockModel;
+    use std::future::Future;
+    use std::sync::Arc;
+    use std::task::{Context, Poll, Wake, Waker};
+
+    /// Minimal executor: poll on this thread, park until woken.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        struct Unpark(thread::Thread);
+        impl Wake for Unpark {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
+        }
+        let waker = Waker::from(Arc::new(Unpark(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        let mut future = std::pin::pin!(future);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    /// Model that panics on empty input.
+    struct PanicOnEmpty;
+
+    impl SpeechModel for PanicOnEmpty {
+        fn capabilities(&self) -> ModelCapabilities {
+            MockModel.capabilities()
+        }
+
+        fn transcribe_raw(
+            &mut self,
+            samples: &[f32],
+            options: &TranscribeOptions,
+        ) -> Result<TranscriptionResult, TranscribeError> {
+            assert!(!samples.is_empty());
+            MockModel.transcribe_raw(samples, options)
+        }
+    }
+
+    #[test]
+    fn async_transcribe_runs_on_worker() {
+        let model =
+            Arc::new(AsyncSpeechModel::with_queue_capacity(Box::new(MockModel), 2).unwrap());
+        let handles: Vec<_> = (1..=4)
+            .map(|i| {
+                let model = Arc::clone(&model);
+                thread::spawn(move || {
+                    block_on(model.transcribe(vec![0.0; i * 100], TranscribeOptions::default()))
+                })
+            })
+            .collect();
+        let mut texts: Vec<String> = handles
+            .into_iter()
+            .map(|h| h.join().unwrap().unwrap().text)
+            .collect();
+        texts.sort();
+        assert_eq!(texts, ["chunk_100", "chunk_200", "chunk_300", "chunk_400"]);
+    }
+
+    #[test]
+    fn async_transcribe_chunked_uses_transcriber() {
+        use crate::transcriber::{EnergyAdaptiveChunked, EnergyAdaptiveConfig};
+
+        let model = AsyncSpeechModel::new(Box::new(MockModel)).unwrap();
+        let config = EnergyAdaptiveConfig {
+            target_chunk_secs: 1.0,
+            search_window_secs: 0.0,
+            ..Default::default()
+        };
+        let transcriber = EnergyAdaptiveChunked::new(config, TranscribeOptions::default());
+        let result =
+            block_on(model.transcribe_chunked(vec![0.0; 40000], Box::new(transcriber))).unwrap();
+        assert_eq!(result.text, "chunk_16000 chunk_16000 chunk_8000");
+    }
+
+    #[test]
+    fn async_model_survives_panics() {
+        let model = AsyncSpeechModel::new(Box::new(PanicOnEmpty)).unwrap();
+        let options = TranscribeOptions::default();
+        assert!(block_on(model.transcribe(Vec::new(), options.clone())).is_err());
+        let result = block_on(model.transcribe(vec![0.0; 10], options)).unwrap();
+        assert_eq!(result.text, "chunk_10");
+    }
+}
@@ -49,9 +49,21 @@ pub const SAMPLES_PER_MS: usize = 16;
 /// - Channels: 1 (mono)
 /// - Format: PCM integer samples
 pub fn read_wav_samples(wav_path: &Path) -> Result<Vec<f32>, TranscribeError> {
-    let mut reader = hound::WavReader::open(wav_path).map_err(|e| {
+    let reader = hound::WavReader::open(wav_path).map_err(|e| {
         TranscribeError::Audio(format!("failed to open {}: {}", wav_path.display(), e))
     })?;
+    read_samples(reader)
+}
+
+/// Decode an in-memory WAV file. Same format requirements as
+/// [`read_wav_samples`].
+pub fn decode_wav(bytes: &[u8]) -> Result<Vec<f32>, TranscribeError> {
+    read_samples(hound::WavReader::new(std::io::Cursor::new(bytes))?)
+}
+
+fn read_samples<R: std::io::Read>(
+    mut reader: hound::WavReader<R>,
+) -> Result<Vec<f32>, TranscribeError> {
     let spec = reader.spec();
 
     let expected_spec = hound::WavSpec {
@@ -107,3 +119,38 @@ pub fn prepend_silence(samples: &[f32], silence_ms: u32) -> Vec<f32> {
     padded.extend_from_slice(samples);
     padded
 }
+
+/// Encode samples as an in-memory WAV file (16 kHz, 16-bit PCM, mono), the
+/// format [`read_wav_samples`] expects. Samples are clamped to [-1.0, 1.0].
+pub fn encode_wav(samples: &[f32]) -> Result<Vec<u8>, TranscribeError> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate: 16000,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut bytes = Vec::with_capacity(44 + samples.len() * 2);
+    let mut writer = hound::WavWriter::new(std::io::Cursor::new(&mut bytes), spec)?;
+    for &sample in samples {
+        writer.write_sample((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)?;
+    }
+    writer.finalize()?;
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_wav_round_trips() {
+        let samples = [0.0, 0.5, -0.5, 1.0, -2.0];
+        let decoded = decode_wav(&encode_wav(&samples).unwrap()).unwrap();
+        assert_eq!(decoded.len(), samples.len());
+        let expected = [0.0, 0.5, -0.5, 1.0, -1.0];
+        for (d, e) in decoded.iter().zip(expected) {
+            assert!((d - e).abs() < 1e-4, "{d} != {e}");
+        }
+        assert!(decode_wav(b"not a wav").is_err());
+    }
+}
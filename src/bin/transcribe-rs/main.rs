@@ -0,0 +1,500 @@
+//! `transcribe-rs` command-line tool.
+//!
+//! Loads any engine compiled into the crate through [`ModelSpec`] and
+//! transcribes WAV files, glob patterns or raw PCM from stdin, optionally
+//! split with [`VadChunked`] or [`EnergyAdaptiveChunked`].
+//!
+//! ```text
+//! transcribe-rs -m models/parakeet-v3 -q int8 --chunking vad -f srt -o subs/ 'audio/*.wav'
+//! ffmpeg -i talk.mp3 -f s16le -ac 1 -ar 16000 - | transcribe-rs -m models/sense-voice -
+//! transcribe-rs -m models/parakeet-v3 --serve 127.0.0.1:8000   # `server` feature
+//! ```
+
+mod output;
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Instant;
+
+use clap::{ArgAction, Parser, ValueEnum};
+use transcribe_rs::transcriber::{
+    EnergyAdaptiveChunked, EnergyAdaptiveConfig, Transcriber, VadChunked, VadChunkedConfig,
+};
+use transcribe_rs::vad::{EnergyVad, SmoothedVad, Vad};
+use transcribe_rs::{
+    audio, set_ort_accelerator, set_whisper_accelerator, ModelSpec, OrtAccelerator,
+    TimestampGranularity, TranscribeError, TranscribeOptions, TranscriptionResult,
+    WhisperAccelerator,
+};
+
+use output::Format;
+
+const SAMPLE_RATE: f32 = 16000.0;
+
+/// Transcribe audio with any engine built into transcribe-rs.
+#[derive(Debug, Clone, Parser)]
+#[command(name = "transcribe-rs", version)]
+struct Args {
+    /// WAV files (16 kHz, mono, 16-bit) or glob patterns; `-` reads raw
+    /// 16 kHz mono PCM from stdin
+    #[cfg_attr(not(feature = "server"), arg(required = true))]
+    #[cfg_attr(feature = "server", arg(required_unless_present = "serve"))]
+    inputs: Vec<String>,
+
+    /// Model directory, or the model file for whisper.cpp
+    #[arg(short, long)]
+    model: PathBuf,
+    /// Engine id (parakeet, canary, cohere, gigaam, sense_voice, moonshine,
+    /// moonshine_streaming, whisper_cpp, whisperfile); detected from the
+    /// model when omitted
+    #[arg(short, long)]
+    engine: Option<String>,
+    /// ONNX quantization: fp32, fp16, int8, int4 or auto
+    #[arg(short, long)]
+    quantization: Option<String>,
+    /// Fail when the requested quantized files are missing instead of
+    /// loading FP32
+    #[arg(long)]
+    strict_quantization: bool,
+    /// Model variant, e.g. the Moonshine size
+    #[arg(long)]
+    variant: Option<String>,
+    /// Whisperfile executable
+    #[arg(long)]
+    whisperfile: Option<PathBuf>,
+    /// Intra-op threads for ONNX engines
+    #[arg(long)]
+    threads: Option<usize>,
+    /// Accelerator: auto, cpu, cuda, tensorrt, directml, rocm, coreml,
+    /// webgpu or xnnpack. whisper.cpp uses the GPU for anything but cpu
+    #[arg(short, long)]
+    accelerator: Option<OrtAccelerator>,
+
+    /// Language hint, e.g. en or de
+    #[arg(short, long)]
+    language: Option<String>,
+    /// Translate to English (engines that support it)
+    #[arg(long)]
+    translate: bool,
+    /// Segment granularity (Parakeet; other engines return their own
+    /// segments)
+    #[arg(long, value_enum)]
+    timestamps: Option<Granularity>,
+
+    /// How to split long audio before transcribing
+    #[arg(long, value_enum, default_value_t = Chunking::None)]
+    chunking: Chunking,
+    /// Silero VAD model for `--chunking vad` (an energy VAD otherwise)
+    #[arg(long)]
+    vad_model: Option<PathBuf>,
+    /// Longest chunk in seconds (`vad`), or target chunk length (`energy`)
+    #[arg(long, default_value_t = 30.0)]
+    max_chunk_secs: f32,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = Format::Txt)]
+    format: Format,
+    /// Write `<input name>.<format>` files to this directory instead of
+    /// printing to stdout (inputs whose names clash are rejected)
+    #[arg(short, long)]
+    output_dir: Option<PathBuf>,
+    /// Sample format of stdin PCM
+    #[arg(long, value_enum, default_value_t = PcmFormat::S16le)]
+    stdin_format: PcmFormat,
+
+    /// Serve the OpenAI audio API on this address instead of transcribing
+    /// inputs, e.g. 127.0.0.1:8000
+    #[cfg(feature = "server")]
+    #[arg(long, value_name = "ADDR", conflicts_with = "inputs")]
+    serve: Option<String>,
+    /// Model name reported by `/v1/models`; defaults to the model
+    /// directory name
+    #[cfg(feature = "server")]
+    #[arg(long, requires = "serve")]
+    model_name: Option<String>,
+
+    /// More logging (-v info, -vv debug); RUST_LOG overrides
+    #[arg(short, long, action = ArgAction::Count)]
+    verbose: u8,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Granularity {
+    Token,
+    Word,
+    Segment,
+}
+
+impl From<Granularity> for TimestampGranularity {
+    fn from(g: Granularity) -> Self {
+        match g {
+            Granularity::Token => TimestampGranularity::Token,
+            Granularity::Word => TimestampGranularity::Word,
+            Granularity::Segment => TimestampGranularity::Segment,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Chunking {
+    /// Whole input in one call
+    None,
+    /// Split on silence found by a VAD
+    Vad,
+    /// Fixed-length chunks split at low-energy frames
+    Energy,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum PcmFormat {
+    /// Signed 16-bit little-endian
+    S16le,
+    /// 32-bit float little-endian
+    F32le,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Input {
+    Stdin,
+    File(PathBuf),
+}
+
+impl Input {
+    fn name(&self) -> String {
+        match self {
+            Input::Stdin => "-".to_string(),
+            Input::File(path) => path.display().to_string(),
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    let level = match args.verbose {
+        0 => log::LevelFilter::Warn,
+        1 => log::LevelFilter::Info,
+        _ => log::LevelFilter::Debug,
+    };
+    env_logger::Builder::new()
+        .filter_level(level)
+        .parse_default_env()
+        .init();
+
+    match run(&args) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Transcribe every input; `Ok(false)` if some of them failed.
+fn run(args: &Args) -> Result<bool, TranscribeError> {
+    if let Some(accel) = args.accelerator {
+        set_ort_accelerator(accel);
+        set_whisper_accelerator(match accel {
+            OrtAccelerator::Auto => WhisperAccelerator::Auto,
+            OrtAccelerator::CpuOnly => WhisperAccelerator::CpuOnly,
+            _ => WhisperAccelerator::Gpu,
+        });
+    }
+
+    let spec = ModelSpec {
+        engine: args.engine.clone(),
+        path: args.model.clone(),
+        quantization: args.quantization.clone(),
+        strict_quantization: args.strict_quantization,
+        variant: args.variant.clone(),
+        binary: args.whisperfile.clone(),
+        num_threads: args.threads,
+    };
+    let load_start = Instant::now();
+    let mut model = spec.load()?;
+    log::info!(
+        "Loaded {} in {:.2?}",
+        model.capabilities().name,
+        load_start.elapsed()
+    );
+
+    #[cfg(feature = "server")]
+    if let Some(addr) = &args.serve {
+        serve(args, model, addr)?;
+        return Ok(true);
+    }
+
+    let inputs = expand_inputs(&args.inputs)?;
+    if let Some(dir) = &args.output_dir {
+        check_output_paths(dir, &inputs, args.format)?;
+        fs::create_dir_all(dir)?;
+    }
+
+    let options = TranscribeOptions {
+        language: args.language.clone(),
+        translate: args.translate,
+        timestamp_granularity: args.timestamps.map(Into::into),
+        ..Default::default()
+    };
+
+    let mut ok = true;
+    for input in &inputs {
+        let result = read_input(input, args.stdin_format).and_then(|samples| {
+            let start = Instant::now();
+            // A fresh transcriber per input, so nothing a failed input left
+            // buffered can leak into the next one.
+            let result = match transcriber(args, &options)?.as_mut() {
+                Some(t) => t.transcribe(model.as_mut(), &samples),
+                None => model.transcribe(&samples, &options),
+            }?;
+            let duration = samples.len() as f32 / SAMPLE_RATE;
+            log::info!(
+                "Transcribed {} ({:.1}s of audio) in {:.2?}",
+                input.name(),
+                duration,
+                start.elapsed()
+            );
+            write_output(args, input, &result, duration, inputs.len() > 1)
+        });
+        if let Err(e) = result {
+            eprintln!("error: {}: {e}", input.name());
+            ok = false;
+        }
+    }
+    Ok(ok)
+}
+
+/// Serve `model` until the process is killed. Long uploads are split with
+/// `--chunking`, or the energy chunker when it is `none`.
+#[cfg(feature = "server")]
+fn serve(
+    args: &Args,
+    model: Box<dyn transcribe_rs::SpeechModel>,
+    addr: &str,
+) -> Result<(), TranscribeError> {
+    use transcribe_rs::server::{ServerConfig, TranscriptionServer};
+
+    let name = args.model_name.clone().unwrap_or_else(|| {
+        args.model
+            .file_stem()
+            .map_or_else(|| "default".into(), |s| s.to_string_lossy().into_owned())
+    });
+    let runtime = tokio::runtime::Runtime::new()?;
+    let model = transcribe_rs::AsyncSpeechModel::new(model)?;
+    let chunking = args.clone();
+    let server = TranscriptionServer::new(name.clone(), model)
+        .with_config(ServerConfig {
+            chunk_threshold_secs: args.max_chunk_secs,
+            ..Default::default()
+        })
+        .with_transcriber(move |options| match transcriber(&chunking, options)? {
+            Some(t) => Ok(t),
+            None => {
+                let config = EnergyAdaptiveConfig {
+                    target_chunk_secs: chunking.max_chunk_secs,
+                    ..Default::default()
+                };
+                Ok(Box::new(EnergyAdaptiveChunked::new(
+                    config,
+                    options.clone(),
+                )))
+            }
+        });
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind(addr).await?;
+        eprintln!("Serving {name} on http://{}/v1", listener.local_addr()?);
+        server.serve(listener).await
+    })?;
+    Ok(())
+}
+
+/// Resolve `-`, file names and glob patterns, in order.
+fn expand_inputs(patterns: &[String]) -> Result<Vec<Input>, TranscribeError> {
+    let mut inputs = Vec::new();
+    for pattern in patterns {
+        if pattern == "-" {
+            inputs.push(Input::Stdin);
+            continue;
+        }
+        // Literal paths win, so file names with `[` or `*` still work.
+        if Path::new(pattern).exists() {
+            inputs.push(Input::File(pattern.into()));
+            continue;
+        }
+        let matches = glob::glob(pattern)
+            .map_err(|e| TranscribeError::Config(format!("bad pattern {pattern:?}: {e}")))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| TranscribeError::Io(e.into_error()))?;
+        if matches.is_empty() {
+            return Err(TranscribeError::Config(format!(
+                "no files match {pattern:?}"
+            )));
+        }
+        inputs.extend(matches.into_iter().map(Input::File));
+    }
+    Ok(inputs)
+}
+
+fn read_input(input: &Input, format: PcmFormat) -> Result<Vec<f32>, TranscribeError> {
+    match input {
+        Input::File(path) => audio::read_wav_samples(path),
+        Input::Stdin => {
+            let mut bytes = Vec::new();
+            io::stdin().lock().read_to_end(&mut bytes)?;
+            Ok(decode_pcm(&bytes, format))
+        }
+    }
+}
+
+/// Raw little-endian PCM to samples; a trailing partial sample is dropped.
+fn decode_pcm(bytes: &[u8], format: PcmFormat) -> Vec<f32> {
+    match format {
+        PcmFormat::S16le => bytes
+            .chunks_exact(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / i16::MAX as f32)
+            .collect(),
+        PcmFormat::F32le => bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect(),
+    }
+}
+
+fn transcriber(
+    args: &Args,
+    options: &TranscribeOptions,
+) -> Result<Option<Box<dyn Transcriber>>, TranscribeError> {
+    let transcriber: Box<dyn Transcriber> = match args.chunking {
+        Chunking::None => return Ok(None),
+        Chunking::Vad => {
+            let config = VadChunkedConfig {
+                max_chunk_secs: args.max_chunk_secs,
+                ..Default::default()
+            };
+            Box::new(VadChunked::new(vad(args)?, config, options.clone()))
+        }
+        Chunking::Energy => {
+            let config = EnergyAdaptiveConfig {
+                target_chunk_secs: args.max_chunk_secs,
+                ..Default::default()
+            };
+            Box::new(EnergyAdaptiveChunked::new(config, options.clone()))
+        }
+    };
+    Ok(Some(transcriber))
+}
+
+fn vad(args: &Args) -> Result<Box<dyn Vad>, TranscribeError> {
+    let inner: Box<dyn Vad> = match &args.vad_model {
+        #[cfg(feature = "vad-silero")]
+        Some(path) => Box::new(transcribe_rs::vad::SileroVad::new(path, 0.3)?),
+        #[cfg(not(feature = "vad-silero"))]
+        Some(_) => {
+            return Err(TranscribeError::Config(
+                "--vad-model requires the `vad-silero` feature".to_string(),
+            ))
+        }
+        None => Box::new(EnergyVad::new(480, 0.01)),
+    };
+    Ok(Box::new(SmoothedVad::new(inner, 15, 15, 2)))
+}
+
+/// `DIR/{input file stem}.{format}`, or `DIR/stdin.{format}`.
+fn output_path(dir: &Path, input: &Input, format: Format) -> PathBuf {
+    let stem = match input {
+        Input::Stdin => "stdin".into(),
+        Input::File(path) => path
+            .file_stem()
+            .map_or_else(|| "output".into(), |s| s.to_string_lossy()),
+    };
+    dir.join(format!("{stem}.{}", format.extension()))
+}
+
+/// Fail before transcribing anything if two inputs would write the same
+/// output file (`a/x.wav` and `b/x.wav`, or `x.wav` and `x.flac`).
+fn check_output_paths(dir: &Path, inputs: &[Input], format: Format) -> Result<(), TranscribeError> {
+    let mut seen = HashMap::new();
+    for input in inputs {
+        if let Some(other) = seen.insert(output_path(dir, input, format), input) {
+            return Err(TranscribeError::Config(format!(
+                "{} and {} would both be written to {}; transcribe them in separate runs",
+                other.name(),
+                input.name(),
+                output_path(dir, input, format).display()
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn write_output(
+    args: &Args,
+    input: &Input,
+    result: &TranscriptionResult,
+    duration_secs: f32,
+    label: bool,
+) -> Result<(), TranscribeError> {
+    let text = output::render(args.format, result, &input.name(), duration_secs);
+    match &args.output_dir {
+        Some(dir) => {
+            let path = output_path(dir, input, args.format);
+            fs::write(&path, text)?;
+            log::info!("Wrote {}", path.display());
+        }
+        None => {
+            let mut stdout = io::stdout().lock();
+            if label && args.format == Format::Txt {
+                write!(stdout, "{}: ", input.name())?;
+            }
+            stdout.write_all(text.as_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expands_files_globs_and_stdin() {
+        let dir = std::env::temp_dir().join(format!("transcribe-rs-cli-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for name in ["b.wav", "a.wav", "notes.txt"] {
+            fs::write(dir.join(name), b"").unwrap();
+        }
+        let glob = dir.join("*.wav").display().to_string();
+
+        assert_eq!(
+            expand_inputs(&["-".into(), glob.clone()]).unwrap(),
+            [
+                Input::Stdin,
+                Input::File(dir.join("a.wav")),
+                Input::File(dir.join("b.wav"))
+            ]
+        );
+        assert!(expand_inputs(&[dir.join("*.mp3").display().to_string()]).is_err());
+        fs::remove_dir_all(dir).unwrap();
+
+        let out = Path::new("out");
+        let distinct = [
+            Input::Stdin,
+            Input::File("a/x.wav".into()),
+            Input::File("y.wav".into()),
+        ];
+        assert!(check_output_paths(out, &distinct, Format::Txt).is_ok());
+        for clash in [["a/x.wav", "b/x.wav"], ["x.wav", "x.flac"]] {
+            let inputs = clash.map(|p| Input::File(p.into()));
+            assert!(check_output_paths(out, &inputs, Format::Srt).is_err());
+        }
+
+        assert_eq!(
+            decode_pcm(&[0x00, 0x40, 0xff, 0x7f, 0x01], PcmFormat::S16le),
+            [16384.0 / 32767.0, 1.0]
+        );
+    }
+}
@@ -0,0 +1,81 @@
+//! Transcript formats.
+
+use clap::ValueEnum;
+use serde_json::json;
+use transcribe_rs::subtitle::{to_srt, to_vtt};
+use transcribe_rs::TranscriptionResult;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Format {
+    /// Plain text
+    Txt,
+    /// One JSON object per input with text and segments
+    Json,
+    /// SubRip subtitles
+    Srt,
+    /// WebVTT subtitles
+    Vtt,
+}
+
+impl Format {
+    pub fn extension(self) -> &'static str {
+        match self {
+            Format::Txt => "txt",
+            Format::Json => "json",
+            Format::Srt => "srt",
+            Format::Vtt => "vtt",
+        }
+    }
+}
+
+/// Render `result` for the input named `source`.
+pub fn render(
+    format: Format,
+    result: &TranscriptionResult,
+    source: &str,
+    duration_secs: f32,
+) -> String {
+    match format {
+        Format::Txt => format!("{}\n", result.text.trim()),
+        Format::Json => {
+            let segments = result.segments.as_ref().map(|segs| {
+                segs.iter()
+                    .map(|s| json!({ "start": s.start, "end": s.end, "text": s.text }))
+                    .collect::<Vec<_>>()
+            });
+            let value = json!({
+                "file": source,
+                "duration": duration_secs,
+                "text": result.text.trim(),
+                "segments": segments,
+            });
+            format!("{value}\n")
+        }
+        Format::Srt => to_srt(result, duration_secs),
+        Format::Vtt => to_vtt(result, duration_secs),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use transcribe_rs::TranscriptionSegment;
+
+    #[test]
+    fn renders_json_line() {
+        let result = TranscriptionResult {
+            text: " hello world".into(),
+            segments: Some(vec![TranscriptionSegment {
+                start: 0.5,
+                end: 1.25,
+                text: " hello world".into(),
+            }]),
+        };
+        let line = render(Format::Json, &result, "a.wav", 4.0);
+        assert!(line.ends_with('\n') && !line.trim_end().contains('\n'));
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["file"], "a.wav");
+        assert_eq!(value["text"], "hello world");
+        assert_eq!(value["segments"][0]["end"], 1.25);
+    }
+}
@@ -0,0 +1,111 @@
+//! Cooperative cancellation.
+//!
+//! A [`CancellationToken`] is a cheap, cloneable flag. Pass a clone in
+//! [`TranscribeOptions::cancel`](crate::TranscribeOptions::cancel) (or an
+//! engine's params) and call [`cancel()`](CancellationToken::cancel) from
+//! any thread. Engines check it between decoder steps, chunked transcribers
+//! between chunks, and whisper.cpp through its abort callback; the running
+//! call then returns [`TranscribeError::Cancelled`].
+//!
+//! ```ignore
+//! use transcribe_rs::{CancellationToken, TranscribeOptions};
+//!
+//! let cancel = CancellationToken::new();
+//! let options = TranscribeOptions { cancel: Some(cancel.clone()), ..Default::default() };
+//! let worker = std::thread::spawn(move || chunker.transcribe_file(&mut model, &path));
+//!
+//! // later, e.g. when the user aborts the upload:
+//! cancel.cancel();
+//! assert!(matches!(worker.join().unwrap(), Err(TranscribeError::Cancelled)));
+//! ```
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::TranscribeError;
+
+/// Shared flag that requests cancellation of a running transcription.
+///
+/// Clones share the same flag. Cancellation is permanent; use a new token
+/// for the next job.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request cancellation. Running calls stop at their next check.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Return [`TranscribeError::Cancelled`] if cancellation was requested.
+    pub fn check(&self) -> Result<(), TranscribeError> {
+        if self.is_cancelled() {
+            Err(TranscribeError::Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// [`CancellationToken::check`] for an optional token.
+pub(crate) fn check(token: Option<&CancellationToken>) -> Result<(), TranscribeError> {
+    token.map_or(Ok(()), CancellationToken::check)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcriber::test_helpers::{make_silence, make_speech, MockModel};
+    use crate::transcriber::{Transcriber, VadChunked, VadChunkedConfig};
+    use crate::vad::EnergyVad;
+    use crate::{SpeechModel, TranscribeOptions};
+
+    #[test]
+    fn cancelled_model_call_fails() {
+        let cancel = CancellationToken::new();
+        let options = TranscribeOptions {
+            cancel: Some(cancel.clone()),
+            ..Default::default()
+        };
+        assert!(MockModel.transcribe(&[0.0; 1600], &options).is_ok());
+
+        cancel.cancel();
+        assert!(cancel.clone().is_cancelled());
+        assert!(matches!(
+            MockModel.transcribe(&[0.0; 1600], &options),
+            Err(TranscribeError::Cancelled)
+        ));
+    }
+
+    #[test]
+    fn cancel_stops_transcriber_between_chunks() {
+        let cancel = CancellationToken::new();
+        let options = TranscribeOptions {
+            cancel: Some(cancel.clone()),
+            ..Default::default()
+        };
+        let config = VadChunkedConfig {
+            min_chunk_secs: 0.0,
+            ..Default::default()
+        };
+        let mut t = VadChunked::new(Box::new(EnergyVad::new(480, 0.01)), config, options);
+
+        let mut region = make_speech(480, 10);
+        region.extend(make_silence(480, 5));
+        assert_eq!(t.feed(&mut MockModel, &region).unwrap().len(), 1);
+
+        cancel.cancel();
+        assert!(matches!(
+            t.feed(&mut MockModel, &region),
+            Err(TranscribeError::Cancelled)
+        ));
+    }
+}
@@ -6,4 +6,4 @@ pub mod tokens;
 pub use ctc::{ctc_greedy_decode, CtcDecoderResult};
 pub use greedy::GreedyDecoder;
 pub use sentencepiece::{parse_byte_token, sentencepiece_to_text};
-pub use tokens::{load_vocab, SymbolTable};
+pub use tokens::{load_vocab, parse_vocab, SymbolTable};
@@ -8,7 +8,13 @@ use std::path::Path;
 /// Replaces `▁` (U+2581) with space in token strings.
 pub fn load_vocab(path: &Path) -> Result<(Vec<String>, Option<i32>), std::io::Error> {
     let content = fs::read_to_string(path)?;
+    let (vocab, blank_idx) = parse_vocab(&content);
+    log::info!("Loaded {} vocab tokens from {:?}", vocab.len(), path);
+    Ok((vocab, blank_idx))
+}
 
+/// Parse the contents of a vocabulary file; see [`load_vocab`].
+pub fn parse_vocab(content: &str) -> (Vec<String>, Option<i32>) {
     let mut max_id = 0;
     let mut tokens_with_ids: Vec<(String, usize)> = Vec::new();
     let mut blank_idx: Option<i32> = None;
@@ -31,9 +37,7 @@ pub fn load_vocab(path: &Path) -> Result<(Vec<String>, Option<i32>), std::io::Er
     for (token, id) in tokens_with_ids {
         vocab[id] = token.replace('\u{2581}', " ");
     }
-
-    log::info!("Loaded {} vocab tokens from {:?}", vocab.len(), path);
-    Ok((vocab, blank_idx))
+    (vocab, blank_idx)
 }
 
 /// Symbol table mapping token IDs to strings.
@@ -48,7 +52,13 @@ pub struct SymbolTable {
 impl SymbolTable {
     /// Load a symbol table from a file where each line is `symbol id`.
     pub fn load(path: &Path) -> Result<Self, std::io::Error> {
-        let contents = fs::read_to_string(path)?;
+        let table = Self::parse(&fs::read_to_string(path)?);
+        log::info!("Loaded {} tokens from {:?}", table.id_to_sym.len(), path);
+        Ok(table)
+    }
+
+    /// Parse the contents of a symbol table file; see [`load`](Self::load).
+    pub fn parse(contents: &str) -> Self {
         let mut id_to_sym = HashMap::new();
 
         for line in contents.lines() {
@@ -65,8 +75,7 @@ impl SymbolTable {
             }
         }
 
-        log::info!("Loaded {} tokens from {:?}", id_to_sym.len(), path);
-        Ok(Self { id_to_sym })
+        Self { id_to_sym }
     }
 
     /// Decode all symbols from base64 (for FunASR Nano models).
@@ -0,0 +1,227 @@
+//! Agglomerative clustering of speaker embeddings.
+
+use crate::TranscribeError;
+
+/// When to stop merging clusters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Clustering {
+    /// Merge until the closest clusters are further apart than this cosine
+    /// distance (0.0 = identical direction, 2.0 = opposite).
+    Threshold(f32),
+    /// Merge until exactly this many clusters remain (known speaker count).
+    NumSpeakers(usize),
+}
+
+impl Default for Clustering {
+    fn default() -> Self {
+        Clustering::Threshold(0.6)
+    }
+}
+
+/// Cluster embeddings with average-linkage agglomerative clustering over
+/// cosine distance.
+///
+/// Returns one label per embedding. Labels are numbered in order of first
+/// appearance, so the first embedding is always speaker 0.
+///
+/// Uses the nearest-neighbour chain algorithm: O(n²) time and memory in the
+/// number of embeddings.
+///
+/// Fails with [`TranscribeError::Inference`] if an embedding contains NaN
+/// or infinite values.
+pub fn agglomerative(
+    embeddings: &[Vec<f32>],
+    clustering: Clustering,
+) -> Result<Vec<usize>, TranscribeError> {
+    let n = embeddings.len();
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+    if let Some(i) = embeddings
+        .iter()
+        .position(|e| e.iter().any(|x| !x.is_finite()))
+    {
+        return Err(TranscribeError::Inference(format!(
+            "speaker embedding {i} contains non-finite values"
+        )));
+    }
+
+    let normalized: Vec<Vec<f32>> = embeddings.iter().map(|e| l2_normalize(e)).collect();
+    let mut dist = vec![0.0f32; n * n];
+    for i in 0..n {
+        for j in i + 1..n {
+            let d = 1.0 - dot(&normalized[i], &normalized[j]);
+            dist[i * n + j] = d;
+            dist[j * n + i] = d;
+        }
+    }
+
+    let mut merges = nn_chain(&mut dist, n)?;
+    merges.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+    let num_merges = match clustering {
+        Clustering::Threshold(t) => merges.iter().take_while(|m| m.2 <= t).count(),
+        Clustering::NumSpeakers(k) => n - k.clamp(1, n),
+    };
+
+    let mut parent: Vec<usize> = (0..n).collect();
+    for &(a, b, _) in &merges[..num_merges] {
+        let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+        parent[rb] = ra;
+    }
+
+    let mut labels = Vec::with_capacity(n);
+    let mut roots: Vec<usize> = Vec::new();
+    for i in 0..n {
+        let root = find(&mut parent, i);
+        let label = match roots.iter().position(|&r| r == root) {
+            Some(label) => label,
+            None => {
+                roots.push(root);
+                roots.len() - 1
+            }
+        };
+        labels.push(label);
+    }
+    Ok(labels)
+}
+
+/// Build the full dendrogram with the nearest-neighbour chain algorithm.
+///
+/// Returns merges as `(slot_a, slot_b, distance)`, where each slot is a
+/// member of the cluster it stands for. Merges come out of order; average
+/// linkage is reducible, so sorting them by distance yields the same
+/// hierarchy as greedy agglomeration.
+fn nn_chain(dist: &mut [f32], n: usize) -> Result<Vec<(usize, usize, f32)>, TranscribeError> {
+    let mut active = vec![true; n];
+    let mut size = vec![1usize; n];
+    let mut chain: Vec<usize> = Vec::new();
+    let mut merges = Vec::with_capacity(n.saturating_sub(1));
+
+    for _ in 1..n {
+        if chain.is_empty() {
+            chain.push(active.iter().position(|&a| a).unwrap_or(0));
+        }
+
+        // Grow the chain until its last two entries are mutual nearest
+        // neighbours.
+        let (a, b, d) = loop {
+            let a = chain[chain.len() - 1];
+            let prev = chain.len().checked_sub(2).map(|i| chain[i]);
+            let mut best = prev;
+            let mut best_d = prev.map_or(f32::INFINITY, |p| dist[a * n + p]);
+            for c in 0..n {
+                if active[c] && c != a && dist[a * n + c] < best_d {
+                    best = Some(c);
+                    best_d = dist[a * n + c];
+                }
+            }
+            let Some(b) = best else {
+                return Err(TranscribeError::Inference(
+                    "clustering found no finite distance between clusters".into(),
+                ));
+            };
+            if Some(b) == prev {
+                chain.truncate(chain.len() - 2);
+                break (a, b, best_d);
+            }
+            chain.push(b);
+        };
+
+        // Merge b into a (Lance-Williams update for average linkage).
+        let (sa, sb) = (size[a] as f32, size[b] as f32);
+        for c in 0..n {
+            if active[c] && c != a && c != b {
+                let d = (sa * dist[a * n + c] + sb * dist[b * n + c]) / (sa + sb);
+                dist[a * n + c] = d;
+                dist[c * n + a] = d;
+            }
+        }
+        active[b] = false;
+        size[a] += size[b];
+        merges.push((a, b, d));
+    }
+    Ok(merges)
+}
+
+fn find(parent: &mut [usize], mut i: usize) -> usize {
+    while parent[i] != i {
+        parent[i] = parent[parent[i]];
+        i = parent[i];
+    }
+    i
+}
+
+fn dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b).map(|(x, y)| x * y).sum()
+}
+
+fn l2_normalize(v: &[f32]) -> Vec<f32> {
+    let norm = dot(v, v).sqrt();
+    if norm == 0.0 {
+        return v.to_vec();
+    }
+    v.iter().map(|x| x / norm).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points() -> Vec<Vec<f32>> {
+        vec![
+            vec![1.0, 0.0, 0.0],
+            vec![0.0, 1.0, 0.0],
+            vec![0.95, 0.05, 0.0],
+            vec![0.0, 0.0, 1.0],
+            vec![0.05, 0.9, 0.1],
+            vec![0.9, 0.1, 0.05],
+        ]
+    }
+
+    fn cluster(embeddings: &[Vec<f32>], clustering: Clustering) -> Vec<usize> {
+        agglomerative(embeddings, clustering).unwrap()
+    }
+
+    #[test]
+    fn threshold_separates_directions() {
+        let labels = cluster(&points(), Clustering::Threshold(0.3));
+        assert_eq!(labels, vec![0, 1, 0, 2, 1, 0]);
+    }
+
+    #[test]
+    fn known_speaker_count() {
+        assert_eq!(
+            cluster(&points(), Clustering::NumSpeakers(3)),
+            vec![0, 1, 0, 2, 1, 0]
+        );
+        let two = cluster(&points(), Clustering::NumSpeakers(2));
+        assert_eq!(two.iter().max(), Some(&1));
+        assert_eq!(cluster(&points(), Clustering::NumSpeakers(1)), vec![0; 6]);
+        // More speakers than embeddings: every embedding is its own speaker
+        assert_eq!(
+            cluster(&points()[..2], Clustering::NumSpeakers(5)),
+            vec![0, 1]
+        );
+    }
+
+    #[test]
+    fn scale_invariant_and_empty() {
+        let labels = cluster(
+            &[vec![2.0, 0.0], vec![0.1, 0.0], vec![0.0, 3.0]],
+            Clustering::Threshold(0.1),
+        );
+        assert_eq!(labels, vec![0, 0, 1]);
+        assert!(cluster(&[], Clustering::default()).is_empty());
+    }
+
+    #[test]
+    fn rejects_non_finite_embeddings() {
+        for bad in [f32::NAN, f32::INFINITY] {
+            let mut embeddings = points();
+            embeddings[2][1] = bad;
+            let err = agglomerative(&embeddings, Clustering::NumSpeakers(2)).unwrap_err();
+            assert!(matches!(err, TranscribeError::Inference(_)));
+        }
+    }
+}
@@ -0,0 +1,312 @@
+//! Speaker diarization ("who spoke when").
+//!
+//! A [`Diarizer`] finds speech with a [`Vad`], cuts it into short windows,
+//! embeds each window with a [`SpeakerEmbedder`] and clusters the embeddings
+//! into speakers. The resulting [`SpeakerTurn`]s can then be attached to a
+//! transcription with [`assign_speakers`].
+//!
+//! Built-in embedders:
+//!
+//! - [`OnnxSpeakerEmbedder`] — WeSpeaker / ECAPA-style ONNX models on 80-dim
+//!   FBANK features (requires `onnx` feature)
+//!
+//! # Example
+//!
+//! ```ignore
+//! use transcribe_rs::diarize::{assign_speakers, Diarizer, DiarizeConfig, OnnxSpeakerEmbedder};
+//!
+//! let embedder = OnnxSpeakerEmbedder::load(Path::new("models/wespeaker_en_voxceleb_resnet34.onnx"))?;
+//! let mut diarizer = Diarizer::new(Box::new(embedder), Box::new(vad), DiarizeConfig::default());
+//!
+//! let turns = diarizer.diarize(&samples)?;
+//! let result = model.transcribe(&samples, &options)?;
+//! for seg in assign_speakers(result.segments.as_deref().unwrap_or_default(), &turns) {
+//!     println!("[speaker {:?}] {}", seg.speaker, seg.segment.text);
+//! }
+//! ```
+
+mod cluster;
+#[cfg(feature = "onnx")]
+mod onnx_embedder;
+
+pub use cluster::{agglomerative, Clustering};
+#[cfg(feature = "onnx")]
+pub use onnx_embedder::OnnxSpeakerEmbedder;
+
+use std::ops::Range;
+
+use crate::transcriber::SAMPLE_RATE;
+use crate::vad::{self, SegmentConfig, Vad};
+use crate::{TranscribeError, TranscriptionSegment};
+
+/// Computes a fixed-size speaker embedding for a span of audio.
+///
+/// Embeddings of the same speaker should point in similar directions;
+/// clustering uses cosine distance, so magnitudes are ignored.
+pub trait SpeakerEmbedder: Send {
+    /// Embed 16 kHz mono audio.
+    fn embed(&mut self, samples: &[f32]) -> Result<Vec<f32>, TranscribeError>;
+}
+
+/// Configuration for [`Diarizer`].
+#[derive(Debug, Clone)]
+pub struct DiarizeConfig {
+    /// How many speakers to produce (threshold or known count).
+    pub clustering: Clustering,
+    /// Maximum duration of one embedding window in seconds. Longer speech
+    /// regions are cut into windows of this length, so speaker changes
+    /// inside a region can be detected at this resolution.
+    pub window_secs: f32,
+    /// Minimum duration of an embedding window in seconds. Shorter speech
+    /// regions are skipped (too little audio for a reliable embedding), and
+    /// shorter trailing windows are merged into the previous window.
+    pub min_window_secs: f32,
+    /// Adjacent turns of the same speaker separated by at most this many
+    /// seconds of silence are merged into one turn.
+    pub merge_gap_secs: f32,
+}
+
+impl Default for DiarizeConfig {
+    fn default() -> Self {
+        Self {
+            clustering: Clustering::default(),
+            window_secs: 1.5,
+            min_window_secs: 0.5,
+            merge_gap_secs: 0.5,
+        }
+    }
+}
+
+/// A contiguous span of audio attributed to one speaker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeakerTurn {
+    /// Start time in seconds.
+    pub start: f32,
+    /// End time in seconds.
+    pub end: f32,
+    /// Speaker index, numbered in order of first appearance.
+    pub speaker: usize,
+}
+
+/// A transcription segment with the speaker who said it.
+#[derive(Debug, Clone)]
+pub struct LabeledSegment {
+    pub segment: TranscriptionSegment,
+    /// Speaker with the largest overlap, or `None` if the segment does not
+    /// overlap any turn.
+    pub speaker: Option<usize>,
+}
+
+/// Offline speaker diarization.
+pub struct Diarizer {
+    embedder: Box<dyn SpeakerEmbedder>,
+    vad: Box<dyn Vad>,
+    config: DiarizeConfig,
+}
+
+impl Diarizer {
+    pub fn new(
+        embedder: Box<dyn SpeakerEmbedder>,
+        vad: Box<dyn Vad>,
+        config: DiarizeConfig,
+    ) -> Self {
+        Self {
+            embedder,
+            vad,
+            config,
+        }
+    }
+
+    /// Diarize 16 kHz mono audio. Returns speaker turns in time order.
+    pub fn diarize(&mut self, samples: &[f32]) -> Result<Vec<SpeakerTurn>, TranscribeError> {
+        let windows = self.windows(samples)?;
+        log::info!("diarize: {} embedding windows", windows.len());
+
+        let mut embeddings = Vec::with_capacity(windows.len());
+        for &(start, end) in &windows {
+            embeddings.push(self.embedder.embed(&samples[start..end])?);
+        }
+        let labels = agglomerative(&embeddings, self.config.clustering)?;
+
+        let mut turns: Vec<SpeakerTurn> = Vec::new();
+        for (&(start, end), speaker) in windows.iter().zip(labels) {
+            let (start, end) = (start as f32 / SAMPLE_RATE, end as f32 / SAMPLE_RATE);
+            match turns.last_mut() {
+                Some(last)
+                    if last.speaker == speaker
+                        && start - last.end <= self.config.merge_gap_secs =>
+                {
+                    last.end = end;
+                }
+                _ => turns.push(SpeakerTurn {
+                    start,
+                    end,
+                    speaker,
+                }),
+            }
+        }
+        Ok(turns)
+    }
+
+    /// Speech regions cut into embedding windows, as sample ranges.
+    fn windows(&mut self, samples: &[f32]) -> Result<Vec<(usize, usize)>, TranscribeError> {
+        let max_len = ((self.config.window_secs * SAMPLE_RATE) as usize).max(1);
+        let min_len = (self.config.min_window_secs * SAMPLE_RATE) as usize;
+
+        let regions = vad::segment(
+            samples,
+            self.vad.as_mut(),
+            &SegmentConfig {
+                min_speech_secs: self.config.min_window_secs,
+                speech_pad_secs: 0.0,
+                ..Default::default()
+            },
+        )?;
+
+        let mut windows = Vec::new();
+        for region in regions {
+            let Range { start, end } = region.sample_range();
+            let end = end.min(samples.len());
+            let mut pos = start;
+            while pos < end {
+                let mut window_end = (pos + max_len).min(end);
+                if end - window_end < min_len {
+                    window_end = end;
+                }
+                windows.push((pos, window_end));
+                pos = window_end;
+            }
+        }
+        Ok(windows)
+    }
+}
+
+/// Label each segment with the speaker whose turns overlap it the most.
+pub fn assign_speakers(
+    segments: &[TranscriptionSegment],
+    turns: &[SpeakerTurn],
+) -> Vec<LabeledSegment> {
+    segments
+        .iter()
+        .map(|seg| {
+            let mut overlap_by_speaker: Vec<f32> = Vec::new();
+            for turn in turns {
+                let overlap = seg.end.min(turn.end) - seg.start.max(turn.start);
+                if overlap > 0.0 {
+                    if overlap_by_speaker.len() <= turn.speaker {
+                        overlap_by_speaker.resize(turn.speaker + 1, 0.0);
+                    }
+                    overlap_by_speaker[turn.speaker] += overlap;
+                }
+            }
+            let speaker = overlap_by_speaker
+                .iter()
+                .enumerate()
+                .filter(|(_, &o)| o > 0.0)
+                .max_by(|a, b| a.1.total_cmp(b.1))
+                .map(|(speaker, _)| speaker);
+            LabeledSegment {
+                segment: seg.clone(),
+                speaker,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vad::EnergyVad;
+
+    /// Embeds by loudness: quiet and loud speech are different "speakers".
+    struct LoudnessEmbedder;
+
+    impl SpeakerEmbedder for LoudnessEmbedder {
+        fn embed(&mut self, samples: &[f32]) -> Result<Vec<f32>, TranscribeError> {
+            let mean = samples.iter().sum::<f32>() / samples.len() as f32;
+            Ok(if mean > 0.5 {
+                vec![1.0, 0.0]
+            } else {
+                vec![0.0, 1.0]
+            })
+        }
+    }
+
+    fn diarizer(clustering: Clustering) -> Diarizer {
+        Diarizer::new(
+            Box::new(LoudnessEmbedder),
+            Box::new(EnergyVad::new(480, 0.05)),
+            DiarizeConfig {
+                clustering,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn secs(value: f32, secs: f32) -> Vec<f32> {
+        vec![value; (secs * SAMPLE_RATE) as usize]
+    }
+
+    #[test]
+    fn diarize_two_speakers() {
+        // A (loud) 3s, silence 1s, B (quiet) 2s, A 1s
+        let mut audio = secs(0.9, 3.0);
+        audio.extend(secs(0.0, 1.0));
+        audio.extend(secs(0.2, 2.0));
+        audio.extend(secs(0.9, 1.0));
+
+        let turns = diarizer(Clustering::Threshold(0.5))
+            .diarize(&audio)
+            .unwrap();
+        let speakers: Vec<usize> = turns.iter().map(|t| t.speaker).collect();
+        assert_eq!(speakers, vec![0, 1, 0]);
+        assert!((turns[0].end - 3.0).abs() < 0.05);
+        assert!((turns[1].start - 4.0).abs() < 0.05);
+        assert!((turns[2].end - 7.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn diarize_known_count_merges_speakers() {
+        let mut audio = secs(0.9, 2.0);
+        audio.extend(secs(0.2, 2.0));
+        let turns = diarizer(Clustering::NumSpeakers(1))
+            .diarize(&audio)
+            .unwrap();
+        assert_eq!(turns.len(), 1);
+        assert_eq!(turns[0].speaker, 0);
+    }
+
+    #[test]
+    fn diarize_skips_short_regions() {
+        let mut audio = secs(0.9, 0.2);
+        audio.extend(secs(0.0, 1.0));
+        assert!(diarizer(Clustering::default())
+            .diarize(&audio)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn assign_by_largest_overlap() {
+        let turns = vec![
+            SpeakerTurn {
+                start: 0.0,
+                end: 2.0,
+                speaker: 0,
+            },
+            SpeakerTurn {
+                start: 2.0,
+                end: 5.0,
+                speaker: 1,
+            },
+        ];
+        let seg = |start, end| TranscriptionSegment {
+            start,
+            end,
+            text: String::new(),
+        };
+        let labeled = assign_speakers(&[seg(0.5, 1.5), seg(1.5, 3.0), seg(6.0, 7.0)], &turns);
+        let speakers: Vec<Option<usize>> = labeled.iter().map(|l| l.speaker).collect();
+        assert_eq!(speakers, vec![Some(0), Some(1), None]);
+    }
+}
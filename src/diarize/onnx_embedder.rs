@@ -0,0 +1,96 @@
+use std::path::Path;
+
+use ndarray::Axis;
+use ort::inputs;
+use ort::session::Session;
+use ort::value::TensorRef;
+
+use crate::features::{compute_mel, MelConfig, WindowType};
+use crate::onnx::session::{self, SessionConfig};
+use crate::TranscribeError;
+
+use super::SpeakerEmbedder;
+
+/// Speaker embedding model in ONNX format (WeSpeaker, 3D-Speaker, ECAPA
+/// exports with the same interface).
+///
+/// Expects a single input of shape `[batch, frames, 80]` holding Kaldi-style
+/// FBANK features (25ms window, 10ms hop, 16-bit sample scale, mean
+/// normalized over time), and a first output of shape `[batch, dim]`.
+pub struct OnnxSpeakerEmbedder {
+    session: Session,
+    input_name: String,
+    mel_config: MelConfig,
+}
+
+impl OnnxSpeakerEmbedder {
+    /// Load an embedding model from an `.onnx` file.
+    pub fn load(model_path: &Path) -> Result<Self, TranscribeError> {
+        Self::load_with_config(model_path, &SessionConfig::default())
+    }
+
+    /// Load with explicit ONNX session settings (execution providers,
+    /// threads, ...).
+    pub fn load_with_config(
+        model_path: &Path,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        if !model_path.exists() {
+            return Err(TranscribeError::ModelNotFound(model_path.to_path_buf()));
+        }
+
+        let session = session::create_session_with_config(model_path, config)?;
+        let input_name = session
+            .inputs()
+            .first()
+            .map(|i| i.name().to_string())
+            .ok_or_else(|| TranscribeError::Config("embedding model has no inputs".into()))?;
+        log::debug!("speaker embedding model input: {}", input_name);
+
+        Ok(Self {
+            session,
+            input_name,
+            mel_config: MelConfig {
+                sample_rate: 16000,
+                num_mels: 80,
+                n_fft: 400,
+                hop_length: 160,
+                window: WindowType::Hamming,
+                f_min: 20.0,
+                f_max: None,
+                pre_emphasis: Some(0.97),
+                snip_edges: true,
+                normalize_samples: false,
+            },
+        })
+    }
+}
+
+impl SpeakerEmbedder for OnnxSpeakerEmbedder {
+    fn embed(&mut self, samples: &[f32]) -> Result<Vec<f32>, TranscribeError> {
+        let mut features = compute_mel(samples, &self.mel_config);
+        if features.nrows() == 0 {
+            return Err(TranscribeError::Audio(format!(
+                "too little audio for a speaker embedding ({} samples)",
+                samples.len()
+            )));
+        }
+
+        // Cepstral mean normalization over time
+        if let Some(mean) = features.mean_axis(Axis(0)) {
+            features -= &mean;
+        }
+
+        let (frames, dims) = features.dim();
+        let input = features
+            .into_shape_with_order((1, frames, dims))?
+            .into_dyn();
+        let t_input = TensorRef::from_array_view(input.view())?;
+
+        let outputs = self
+            .session
+            .run(inputs![self.input_name.as_str() => t_input])?;
+        let embedding = outputs[0].try_extract_array::<f32>()?;
+        Ok(embedding.iter().copied().collect())
+    }
+}
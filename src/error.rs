@@ -15,6 +15,11 @@ pub enum TranscribeError {
     #[error("config error: {0}")]
     Config(String),
 
+    /// The call was stopped through a
+    /// [`CancellationToken`](crate::CancellationToken).
+    #[error("transcription cancelled")]
+    Cancelled,
+
     #[error(transparent)]
     Io(#[from] std::io::Error),
 
@@ -10,6 +10,23 @@
 //! - **Remote**: OpenAI API (requires `openai` feature)
 //! - **Timestamped Results**: Detailed timing information for transcribed segments
 //! - **Unified API**: `SpeechModel` trait for all local engines
+//! - **Streaming**: [`StreamingSpeechModel`] for live partial/stable hypotheses,
+//!   native for Moonshine Streaming and emulated for any other engine via
+//!   [`streaming::EmulatedStreaming`]
+//! - **Model Registry**: load any engine by `engine_id` from a serde
+//!   [`ModelSpec`], or detect it from the model directory, via the
+//!   [`registry`] module
+//! - **Model Downloads**: manifests with SHA-256 hashes, resumable downloads
+//!   and a model cache via the `models` module (requires `models` feature)
+//! - **Async**: [`AsyncSpeechModel`] runs any local engine on its own thread
+//!   behind an `async fn` API (requires `async` feature)
+//! - **Speaker Diarization**: speaker turns from embedding clustering via the
+//!   [`diarize`] module (ONNX embedding models require `onnx` feature)
+//! - **Metrics**: per-call stage timings, token counts and real-time factor
+//!   through a [`metrics::MetricsSink`] callback
+//! - **Subtitles**: SRT and WebVTT output via the [`subtitle`] module
+//! - **Server**: OpenAI-compatible `/v1/audio/transcriptions` endpoint for
+//!   any local model via the `server` module (requires `server` feature)
 //! - **Hardware Acceleration**: GPU support for ORT engines (`ort-cuda`, `ort-rocm`,
 //!   `ort-directml`, `ort-coreml`, `ort-webgpu`) and whisper.cpp (Metal/Vulkan)
 //!   via the [`accel`] module
@@ -27,12 +44,15 @@
 //! These traits are intentionally separate because the execution model differs:
 //! local models are synchronous and take audio samples directly, while remote
 //! services are async and may only accept file uploads.
+//! To choose a backend at runtime, `remote::RemoteSpeechModel` adapts any remote
+//! engine to [`SpeechModel`], and with the `async` feature [`AsyncSpeechModel`]
+//! implements [`RemoteTranscriptionEngine`] for any local model.
 //!
 //! ## Quick Start
 //!
 //! ```toml
 //! [dependencies]
-//! transcribe-rs = { version = "0.3", features = ["onnx"] }
+//! transcribe-rs = { version = "0.4", features = ["onnx"] }
 //! ```
 //!
 //! ```ignore
@@ -88,12 +108,16 @@
 
 pub mod accel;
 pub mod audio;
+pub mod cancel;
 pub mod error;
+pub mod metrics;
+pub mod subtitle;
 pub use accel::{
     get_ort_accelerator, get_whisper_accelerator, get_whisper_gpu_device, set_ort_accelerator,
     set_whisper_accelerator, set_whisper_gpu_device, OrtAccelerator, WhisperAccelerator,
     GPU_DEVICE_AUTO,
 };
+pub use cancel::CancellationToken;
 pub use error::TranscribeError;
 
 #[cfg(feature = "audio-features")]
@@ -103,14 +127,31 @@ pub mod features;
 #[cfg(feature = "onnx")]
 pub mod onnx;
 
+pub mod diarize;
+pub mod registry;
+pub mod streaming;
 pub mod transcriber;
 pub mod vad;
 
+pub use registry::{ModelRegistry, ModelSpec};
+pub use streaming::StreamingSpeechModel;
+
 #[cfg(feature = "whisper-cpp")]
 pub mod whisper_cpp;
 #[cfg(feature = "whisperfile")]
 pub mod whisperfile;
 
+#[cfg(feature = "models")]
+pub mod models;
+
+#[cfg(feature = "async")]
+pub mod async_model;
+#[cfg(feature = "async")]
+pub use async_model::AsyncSpeechModel;
+
+#[cfg(feature = "server")]
+pub mod server;
+
 #[cfg(feature = "openai")]
 pub mod remote;
 #[cfg(feature = "openai")]
@@ -131,12 +172,24 @@ pub struct ModelCapabilities {
     pub languages: &'static [&'static str],
     /// Whether the model can produce word/segment timestamps.
     pub supports_timestamps: bool,
+    /// Whether the model returns one segment per word when asked for
+    /// [`TimestampGranularity::Word`].
+    pub supports_word_timestamps: bool,
     /// Whether the model can translate to English.
     pub supports_translation: bool,
     /// Whether the model supports streaming inference.
     pub supports_streaming: bool,
 }
 
+/// Granularity of the segments in a [`TranscriptionResult`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum TimestampGranularity {
+    #[default]
+    Token,
+    Word,
+    Segment,
+}
+
 /// Options for transcription.
 #[derive(Debug, Clone, Default)]
 pub struct TranscribeOptions {
@@ -154,6 +207,16 @@ pub struct TranscribeOptions {
     /// Set to `Some(0)` to explicitly disable.
     /// When `None`, each engine applies its own default (typically 0 ms).
     pub trailing_silence_ms: Option<u32>,
+    /// Token to abort the call from another thread. Checked between decoder
+    /// steps and between chunks; a cancelled call returns
+    /// [`TranscribeError::Cancelled`].
+    pub cancel: Option<CancellationToken>,
+    /// Receives stage timings of the call; see [`metrics`]. Honoured by the
+    /// ONNX engines.
+    pub metrics: Option<metrics::MetricsSink>,
+    /// Granularity of the returned segments. Honoured by Parakeet; other
+    /// engines return the segments they produce natively.
+    pub timestamp_granularity: Option<TimestampGranularity>,
 }
 
 /// Unified interface for speech-to-text models.
@@ -203,6 +266,8 @@ pub trait SpeechModel: Send {
         samples: &[f32],
         options: &TranscribeOptions,
     ) -> Result<TranscriptionResult, TranscribeError> {
+        cancel::check(options.cancel.as_ref())?;
+
         let lead_ms = options
             .leading_silence_ms
             .unwrap_or_else(|| self.default_leading_silence_ms());
@@ -234,6 +299,18 @@ pub trait SpeechModel: Send {
         Ok(result)
     }
 
+    /// Run one second of silence through the model.
+    ///
+    /// The first inference on a fresh session is much slower than later ones
+    /// (allocator growth, kernel selection, GPU graph capture). Call this
+    /// after loading to pay that cost up front rather than on the first
+    /// request.
+    fn warm_up(&mut self) -> Result<(), TranscribeError> {
+        let silence = vec![0.0; self.capabilities().sample_rate as usize];
+        self.transcribe_raw(&silence, &TranscribeOptions::default())?;
+        Ok(())
+    }
+
     /// Transcribe a WAV file (16 kHz, 16-bit, mono).
     fn transcribe_file(
         &mut self,
@@ -285,3 +362,39 @@ pub struct TranscriptionSegment {
     /// The transcribed text for this segment
     pub text: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcriber::test_helpers::MockModel;
+
+    /// Records the lengths of the audio passed to [`MockModel`].
+    struct Recording {
+        calls: Vec<usize>,
+    }
+
+    impl SpeechModel for Recording {
+        fn capabilities(&self) -> ModelCapabilities {
+            MockModel.capabilities()
+        }
+
+        fn transcribe_raw(
+            &mut self,
+            samples: &[f32],
+            options: &TranscribeOptions,
+        ) -> Result<TranscriptionResult, TranscribeError> {
+            assert!(samples.iter().all(|&s| s == 0.0));
+            self.calls.push(samples.len());
+            MockModel.transcribe_raw(samples, options)
+        }
+    }
+
+    #[test]
+    fn warm_up_runs_one_second_of_silence() {
+        assert!(MockModel.warm_up().is_ok());
+
+        let mut model = Recording { calls: Vec::new() };
+        model.warm_up().unwrap();
+        assert_eq!(model.calls, vec![16000]);
+    }
+}
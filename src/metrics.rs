@@ -0,0 +1,209 @@
+//! Per-call timing metrics.
+//!
+//! A [`MetricsSink`] is a cheap, cloneable callback. Pass a clone in
+//! [`TranscribeOptions::metrics`](crate::TranscribeOptions::metrics) (or an
+//! engine's params) and the ONNX engines time each stage of the call —
+//! feature extraction, preprocessor, encoder, every decoder step — and hand
+//! the sink an [`InferenceMetrics`] when the call succeeds. Without a sink
+//! nothing is recorded.
+//!
+//! ```ignore
+//! use transcribe_rs::metrics::MetricsSink;
+//! use transcribe_rs::TranscribeOptions;
+//!
+//! let options = TranscribeOptions {
+//!     metrics: Some(MetricsSink::new(|m| {
+//!         log::info!("{}: RTF {:.3}, {} tokens", m.engine, m.real_time_factor(), m.tokens);
+//!         for stage in &m.stages {
+//!             log::info!("  {}: {:?} over {} runs", stage.name, stage.duration, stage.runs);
+//!         }
+//!     })),
+//!     ..Default::default()
+//! };
+//! model.transcribe(&samples, &options)?;
+//! ```
+//!
+//! For operator-level detail, enable ONNX Runtime's profiler with
+//! `SessionConfig::with_profiling` (requires the `onnx` feature).
+
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Wall time spent in one stage of a call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StageMetrics {
+    /// `"features"`, `"preprocessor"`, `"encoder"`, `"decoder"`, ...
+    pub name: &'static str,
+    /// Total time over all runs of this stage.
+    pub duration: Duration,
+    /// How many times the stage ran (decoder steps for `"decoder"`).
+    pub runs: usize,
+}
+
+/// Timing of one transcription call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InferenceMetrics {
+    /// Engine that ran the call (`ModelCapabilities::engine_id`).
+    pub engine: &'static str,
+    /// Duration of the audio the model saw, including silence padding.
+    pub audio_duration: Duration,
+    /// Wall time of the whole call.
+    pub total: Duration,
+    /// Stages in the order they first ran.
+    pub stages: Vec<StageMetrics>,
+    /// Tokens emitted by the decoder.
+    pub tokens: usize,
+}
+
+impl InferenceMetrics {
+    /// Processing time divided by audio duration; below 1.0 is faster than
+    /// real time.
+    pub fn real_time_factor(&self) -> f32 {
+        if self.audio_duration.is_zero() {
+            return 0.0;
+        }
+        self.total.as_secs_f32() / self.audio_duration.as_secs_f32()
+    }
+
+    pub fn stage(&self, name: &str) -> Option<&StageMetrics> {
+        self.stages.iter().find(|s| s.name == name)
+    }
+}
+
+/// Callback that receives the [`InferenceMetrics`] of each call.
+///
+/// Clones share the same callback, which may run on whichever thread made
+/// the call.
+#[derive(Clone)]
+pub struct MetricsSink(
+    #[cfg_attr(not(feature = "onnx"), allow(dead_code))]
+    Arc<dyn Fn(&InferenceMetrics) + Send + Sync>,
+);
+
+impl MetricsSink {
+    pub fn new(callback: impl Fn(&InferenceMetrics) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(callback))
+    }
+}
+
+impl fmt::Debug for MetricsSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("MetricsSink")
+    }
+}
+
+/// Collects the metrics of one call; a no-op without a sink.
+// Only the ONNX engines record stages so far.
+#[cfg_attr(not(feature = "onnx"), allow(dead_code))]
+pub(crate) struct Recorder<'a> {
+    sink: Option<&'a MetricsSink>,
+    start: Instant,
+    metrics: InferenceMetrics,
+}
+
+#[cfg_attr(not(feature = "onnx"), allow(dead_code))]
+impl<'a> Recorder<'a> {
+    pub(crate) fn new(
+        sink: Option<&'a MetricsSink>,
+        engine: &'static str,
+        num_samples: usize,
+        sample_rate: u32,
+    ) -> Self {
+        Self {
+            sink,
+            start: Instant::now(),
+            metrics: InferenceMetrics {
+                engine,
+                audio_duration: Duration::from_secs_f64(num_samples as f64 / sample_rate as f64),
+                total: Duration::ZERO,
+                stages: Vec::new(),
+                tokens: 0,
+            },
+        }
+    }
+
+    /// Run `f` and add its wall time to `stage`.
+    pub(crate) fn time<T>(&mut self, stage: &'static str, f: impl FnOnce() -> T) -> T {
+        if self.sink.is_none() {
+            return f();
+        }
+        let start = Instant::now();
+        let out = f();
+        self.add(stage, start.elapsed());
+        out
+    }
+
+    fn add(&mut self, stage: &'static str, elapsed: Duration) {
+        match self.metrics.stages.iter_mut().find(|s| s.name == stage) {
+            Some(s) => {
+                s.duration += elapsed;
+                s.runs += 1;
+            }
+            None => self.metrics.stages.push(StageMetrics {
+                name: stage,
+                duration: elapsed,
+                runs: 1,
+            }),
+        }
+    }
+
+    pub(crate) fn add_tokens(&mut self, tokens: usize) {
+        self.metrics.tokens += tokens;
+    }
+
+    /// Hand the metrics to the sink.
+    pub(crate) fn finish(mut self) {
+        if let Some(sink) = self.sink {
+            self.metrics.total = self.start.elapsed();
+            (sink.0)(&self.metrics);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn recorder_accumulates_stages_and_reports_once() {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let sink = {
+            let seen = seen.clone();
+            MetricsSink::new(move |m| seen.lock().unwrap().push(m.clone()))
+        };
+
+        let mut rec = Recorder::new(Some(&sink), "mock", 32000, 16000);
+        assert_eq!(rec.time("encoder", || 7), 7);
+        for _ in 0..3 {
+            rec.time("decoder", || ());
+        }
+        rec.add_tokens(3);
+        rec.finish();
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 1);
+        let m = &seen[0];
+        assert_eq!(m.engine, "mock");
+        assert_eq!(m.audio_duration, Duration::from_secs(2));
+        assert_eq!(
+            m.stages
+                .iter()
+                .map(|s| (s.name, s.runs))
+                .collect::<Vec<_>>(),
+            [("encoder", 1), ("decoder", 3)]
+        );
+        assert_eq!(m.stage("decoder").unwrap().runs, 3);
+        assert_eq!(m.tokens, 3);
+        assert!(m.real_time_factor() < 1.0);
+    }
+
+    #[test]
+    fn recorder_without_sink_records_nothing() {
+        let mut rec = Recorder::new(None, "mock", 16000, 16000);
+        rec.time("encoder", || ());
+        assert!(rec.metrics.stages.is_empty());
+        rec.finish();
+    }
+}
@@ -0,0 +1,574 @@
+//! Model manifests, integrity checks and downloads.
+//!
+//! Requires the `models` feature. A [`Manifest`] lists [`ModelBundle`]s:
+//! the engine, and each file's URL, size and SHA-256, optionally tagged
+//! with the quantization it belongs to. [`ModelCache`] fetches a bundle into
+//! a cache directory:
+//!
+//! - interrupted downloads resume from the `.part` file with an HTTP range
+//!   request;
+//! - every file is checked against its SHA-256 before it is installed;
+//! - files are installed by renaming, so a model directory never contains
+//!   partially written files under their final names.
+//!
+//! ```ignore
+//! use transcribe_rs::models::{Manifest, ModelCache};
+//!
+//! let manifest = Manifest::load("models.json")?;
+//! let bundle = manifest.get("parakeet-tdt-0.6b-v3").unwrap();
+//! let mut cache = ModelCache::new(ModelCache::default_dir())
+//!     .with_progress(|p| eprintln!("{}: {}/{}", p.file, p.downloaded, p.total));
+//! let dir = cache.fetch(bundle, Some("int8"))?;
+//! let mut model = bundle.spec(&dir, Some("int8")).load()?;
+//! ```
+//!
+//! The crate ships no manifest of its own; apps keep theirs next to their
+//! config so hashes are pinned to the files they tested.
+
+use std::collections::BTreeSet;
+use std::env;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use ureq::Agent;
+
+use crate::{ModelSpec, TranscribeError};
+
+/// One file of a [`ModelBundle`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelFile {
+    /// Path relative to the model directory (e.g. `vocab.txt`,
+    /// `onnx/encoder_model.onnx`).
+    pub name: String,
+    pub url: String,
+    /// Size in bytes.
+    pub size: u64,
+    /// Lowercase hex SHA-256 of the file.
+    pub sha256: String,
+    /// Quantization this file belongs to ("fp32", "int8", ...). `None` for
+    /// files every variant needs (vocabularies, preprocessors).
+    #[serde(default)]
+    pub quantization: Option<String>,
+}
+
+/// A downloadable model: everything one engine needs to load it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModelBundle {
+    /// Unique id, also the directory name in the cache.
+    pub id: String,
+    /// `engine_id` to load it with (see [`crate::registry`]).
+    pub engine: String,
+    /// Engine-specific variant, passed on as [`ModelSpec::variant`].
+    #[serde(default)]
+    pub variant: Option<String>,
+    pub files: Vec<ModelFile>,
+}
+
+impl ModelBundle {
+    /// Quantizations the bundle offers, sorted.
+    pub fn quantizations(&self) -> Vec<&str> {
+        let set: BTreeSet<&str> = self
+            .files
+            .iter()
+            .filter_map(|f| f.quantization.as_deref())
+            .collect();
+        set.into_iter().collect()
+    }
+
+    /// Files needed for `quantization` (`None` means "fp32"): the shared
+    /// files plus those tagged with it.
+    pub fn files_for<'a>(
+        &'a self,
+        quantization: Option<&'a str>,
+    ) -> impl Iterator<Item = &'a ModelFile> + 'a {
+        let wanted = quantization.unwrap_or("fp32");
+        self.files.iter().filter(move |f| {
+            f.quantization
+                .as_deref()
+                .is_none_or(|q| q.eq_ignore_ascii_case(wanted))
+        })
+    }
+
+    /// Check that `dir` holds every file for `quantization` with the
+    /// expected size. Cheap enough to run before every load.
+    pub fn validate(&self, dir: &Path, quantization: Option<&str>) -> Result<(), TranscribeError> {
+        self.check_known(quantization)?;
+        for file in self.files_for(quantization) {
+            let path = dir.join(checked_name(&file.name)?);
+            let size = match fs::metadata(&path) {
+                Ok(meta) => meta.len(),
+                Err(_) => return Err(TranscribeError::ModelNotFound(path)),
+            };
+            if size != file.size {
+                return Err(invalid_data(format!(
+                    "{}: expected {} bytes, found {}",
+                    path.display(),
+                    file.size,
+                    size
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// [`validate`](Self::validate), then check every file's SHA-256.
+    pub fn verify(&self, dir: &Path, quantization: Option<&str>) -> Result<(), TranscribeError> {
+        self.validate(dir, quantization)?;
+        for file in self.files_for(quantization) {
+            check_sha256(&dir.join(&file.name), &file.sha256)?;
+        }
+        Ok(())
+    }
+
+    /// A [`ModelSpec`] for loading this bundle from `dir`.
+    pub fn spec(&self, dir: &Path, quantization: Option<&str>) -> ModelSpec {
+        ModelSpec {
+            engine: Some(self.engine.clone()),
+            path: dir.to_path_buf(),
+            quantization: quantization.map(str::to_string),
+            variant: self.variant.clone(),
+            ..Default::default()
+        }
+    }
+
+    fn check_known(&self, quantization: Option<&str>) -> Result<(), TranscribeError> {
+        let available = self.quantizations();
+        match quantization {
+            Some(q) if !available.iter().any(|a| a.eq_ignore_ascii_case(q)) => {
+                Err(TranscribeError::Config(format!(
+                    "{} has no {q} variant (available: {})",
+                    self.id,
+                    available.join(", ")
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A list of model bundles, usually read from JSON:
+///
+/// ```json
+/// { "bundles": [ { "id": "sense-voice-int8", "engine": "sense_voice", "files": [
+///     { "name": "model.int8.onnx", "url": "https://...", "size": 239233841,
+///       "sha256": "...", "quantization": "int8" },
+///     { "name": "tokens.txt", "url": "https://...", "size": 315823, "sha256": "..." } ] } ] }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    pub bundles: Vec<ModelBundle>,
+}
+
+impl Manifest {
+    pub fn from_json(json: &str) -> Result<Self, TranscribeError> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, TranscribeError> {
+        Self::from_json(&fs::read_to_string(path)?)
+    }
+
+    pub fn get(&self, id: &str) -> Option<&ModelBundle> {
+        self.bundles.iter().find(|b| b.id == id)
+    }
+}
+
+/// Download progress for one file.
+#[derive(Debug, Clone)]
+pub struct DownloadProgress<'a> {
+    pub bundle: &'a str,
+    pub file: &'a str,
+    /// Bytes on disk so far, including any resumed part.
+    pub downloaded: u64,
+    pub total: u64,
+}
+
+type ProgressFn = Box<dyn FnMut(&DownloadProgress) + Send>;
+
+/// Downloads bundles into `root/<bundle id>/`.
+pub struct ModelCache {
+    root: PathBuf,
+    agent: Agent,
+    progress: Option<ProgressFn>,
+}
+
+impl ModelCache {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .build()
+            .into();
+        Self {
+            root: root.into(),
+            agent,
+            progress: None,
+        }
+    }
+
+    /// `$TRANSCRIBE_RS_CACHE`, else `transcribe-rs` under the platform
+    /// cache directory (`$XDG_CACHE_HOME`, `%LOCALAPPDATA%` or
+    /// `~/.cache`).
+    pub fn default_dir() -> PathBuf {
+        if let Some(dir) = env::var_os("TRANSCRIBE_RS_CACHE") {
+            return dir.into();
+        }
+        env::var_os("XDG_CACHE_HOME")
+            .or_else(|| env::var_os("LOCALAPPDATA"))
+            .map(PathBuf::from)
+            .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".cache")))
+            .unwrap_or_else(env::temp_dir)
+            .join("transcribe-rs")
+    }
+
+    /// Call `progress` as data arrives.
+    pub fn with_progress(
+        mut self,
+        progress: impl FnMut(&DownloadProgress) + Send + 'static,
+    ) -> Self {
+        self.progress = Some(Box::new(progress));
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Where `bundle` is (or would be) installed.
+    pub fn bundle_dir(&self, bundle: &ModelBundle) -> Result<PathBuf, TranscribeError> {
+        Ok(self.root.join(checked_name(&bundle.id)?))
+    }
+
+    /// Whether `bundle` is fully installed for `quantization` (sizes only;
+    /// use [`ModelBundle::verify`] to re-hash).
+    pub fn is_installed(&self, bundle: &ModelBundle, quantization: Option<&str>) -> bool {
+        self.bundle_dir(bundle)
+            .is_ok_and(|dir| bundle.validate(&dir, quantization).is_ok())
+    }
+
+    /// Download whatever `bundle` is missing for `quantization` and return
+    /// its directory. Files already installed with the right size are
+    /// skipped.
+    pub fn fetch(
+        &mut self,
+        bundle: &ModelBundle,
+        quantization: Option<&str>,
+    ) -> Result<PathBuf, TranscribeError> {
+        bundle.check_known(quantization)?;
+        let dir = self.bundle_dir(bundle)?;
+        for file in bundle.files_for(quantization) {
+            let dest = dir.join(checked_name(&file.name)?);
+            if fs::metadata(&dest).is_ok_and(|m| m.len() == file.size) {
+                continue;
+            }
+            self.download(&bundle.id, file, &dest)?;
+        }
+        bundle.validate(&dir, quantization)?;
+        Ok(dir)
+    }
+
+    /// Download `file` to `dest.part`, resuming if it exists, verify it and
+    /// rename it to `dest`.
+    fn download(
+        &mut self,
+        bundle: &str,
+        file: &ModelFile,
+        dest: &Path,
+    ) -> Result<(), TranscribeError> {
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let part = part_path(dest);
+        let mut downloaded = fs::metadata(&part).map_or(0, |m| m.len());
+        if downloaded > file.size {
+            fs::remove_file(&part)?;
+            downloaded = 0;
+        }
+
+        if downloaded < file.size {
+            let mut request = self.agent.get(&file.url);
+            if downloaded > 0 {
+                log::info!("Resuming {} at byte {}", file.name, downloaded);
+                request = request.header("Range", format!("bytes={downloaded}-"));
+            } else {
+                log::info!("Downloading {} ({} bytes)", file.name, file.size);
+            }
+            let response = request
+                .call()
+                .map_err(|e| io::Error::other(format!("GET {}: {e}", file.url)))?;
+
+            let mut out = match response.status().as_u16() {
+                206 => OpenOptions::new().append(true).open(&part)?,
+                200 => {
+                    // The server ignored the range; start over.
+                    downloaded = 0;
+                    File::create(&part)?
+                }
+                status => {
+                    return Err(io::Error::other(format!("GET {}: HTTP {status}", file.url)).into())
+                }
+            };
+
+            let mut body = response.into_body().into_reader();
+            let mut buf = vec![0u8; 64 * 1024];
+            loop {
+                let n = body.read(&mut buf)?;
+                if n == 0 {
+                    break;
+                }
+                out.write_all(&buf[..n])?;
+                downloaded += n as u64;
+                if let Some(progress) = &mut self.progress {
+                    progress(&DownloadProgress {
+                        bundle,
+                        file: &file.name,
+                        downloaded,
+                        total: file.size,
+                    });
+                }
+            }
+            out.sync_all()?;
+        }
+
+        if downloaded < file.size {
+            // Keep the part so the next fetch resumes.
+            return Err(invalid_data(format!(
+                "{}: download ended after {downloaded} of {} bytes",
+                file.name, file.size
+            )));
+        }
+        if let Err(e) = check_sha256(&part, &file.sha256) {
+            fs::remove_file(&part)?;
+            return Err(e);
+        }
+        fs::rename(&part, dest)?;
+        Ok(())
+    }
+}
+
+/// Lowercase hex SHA-256 of the file at `path`.
+pub fn sha256_file(path: &Path) -> Result<String, TranscribeError> {
+    let mut hasher = Sha256::new();
+    io::copy(&mut File::open(path)?, &mut hasher)?;
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect())
+}
+
+fn check_sha256(path: &Path, expected: &str) -> Result<(), TranscribeError> {
+    let actual = sha256_file(path)?;
+    if actual.eq_ignore_ascii_case(expected) {
+        Ok(())
+    } else {
+        Err(invalid_data(format!(
+            "{}: SHA-256 mismatch (expected {expected}, got {actual})",
+            path.display()
+        )))
+    }
+}
+
+fn invalid_data(message: String) -> TranscribeError {
+    io::Error::new(io::ErrorKind::InvalidData, message).into()
+}
+
+/// Reject manifest names that would escape the cache directory.
+fn checked_name(name: &str) -> Result<&Path, TranscribeError> {
+    let path = Path::new(name);
+    if name.is_empty() || !path.components().all(|c| matches!(c, Component::Normal(_))) {
+        return Err(TranscribeError::Config(format!(
+            "invalid path in manifest: {name:?}"
+        )));
+    }
+    Ok(path)
+}
+
+fn part_path(dest: &Path) -> PathBuf {
+    let mut name = dest.as_os_str().to_owned();
+    name.push(".part");
+    name.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::BufRead;
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    /// Serves `content` at any path, honoring `Range: bytes=N-`. Records the
+    /// range start of each request (`None` for full requests).
+    fn serve(content: &'static [u8]) -> (String, Arc<Mutex<Vec<Option<u64>>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let log = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut reader = io::BufReader::new(stream.try_clone().unwrap());
+                let mut range = None;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("range: bytes=") {
+                        range = value.trim().trim_end_matches('-').parse::<u64>().ok();
+                    }
+                }
+                log.lock().unwrap().push(range);
+                let start = range.unwrap_or(0) as usize;
+                let status = if range.is_some() {
+                    "206 Partial Content"
+                } else {
+                    "200 OK"
+                };
+                let body = &content[start..];
+                write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    body.len()
+                )
+                .unwrap();
+                stream.write_all(body).unwrap();
+            }
+        });
+        (url, requests)
+    }
+
+    fn create_temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("models_test_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn sha256_of(data: &[u8]) -> String {
+        Sha256::digest(data)
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect()
+    }
+
+    fn file(url: &str, name: &str, content: &[u8], quantization: Option<&str>) -> ModelFile {
+        ModelFile {
+            name: name.to_string(),
+            url: format!("{url}/{name}"),
+            size: content.len() as u64,
+            sha256: sha256_of(content),
+            quantization: quantization.map(str::to_string),
+        }
+    }
+
+    const WEIGHTS: &[u8] = b"pretend these are int8 encoder weights";
+
+    #[test]
+    fn fetch_verifies_and_installs() {
+        assert_eq!(
+            sha256_of(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        let (url, requests) = serve(WEIGHTS);
+        let root = create_temp_dir("fetch");
+        let json = serde_json::to_string(&Manifest {
+            bundles: vec![ModelBundle {
+                id: "test-model".to_string(),
+                engine: "sense_voice".to_string(),
+                variant: None,
+                files: vec![
+                    file(&url, "model.int8.onnx", WEIGHTS, Some("int8")),
+                    file(&url, "onnx/shared.bin", WEIGHTS, None),
+                    file(&url, "model.onnx", b"fp32 weights", Some("fp32")),
+                ],
+            }],
+        })
+        .unwrap();
+        let manifest = Manifest::from_json(&json).unwrap();
+        let bundle = manifest.get("test-model").unwrap();
+        assert_eq!(bundle.quantizations(), ["fp32", "int8"]);
+
+        let mut cache = ModelCache::new(&root);
+        assert!(!cache.is_installed(bundle, Some("int8")));
+        assert!(cache.fetch(bundle, Some("int4")).is_err());
+        let dir = cache.fetch(bundle, Some("int8")).unwrap();
+
+        assert_eq!(fs::read(dir.join("model.int8.onnx")).unwrap(), WEIGHTS);
+        assert!(dir.join("onnx/shared.bin").exists());
+        assert!(!dir.join("model.onnx").exists());
+        assert!(cache.is_installed(bundle, Some("int8")));
+        bundle.verify(&dir, Some("int8")).unwrap();
+        assert_eq!(
+            bundle.spec(&dir, Some("int8")).engine.as_deref(),
+            Some("sense_voice")
+        );
+
+        // Already installed: no more requests.
+        cache.fetch(bundle, Some("int8")).unwrap();
+        assert_eq!(requests.lock().unwrap().len(), 2);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn fetch_resumes_partial_download() {
+        let (url, requests) = serve(WEIGHTS);
+        let root = create_temp_dir("resume");
+        let bundle = ModelBundle {
+            id: "resume".to_string(),
+            engine: "gigaam".to_string(),
+            variant: None,
+            files: vec![file(&url, "model.onnx", WEIGHTS, None)],
+        };
+        fs::create_dir_all(root.join("resume")).unwrap();
+        fs::write(root.join("resume/model.onnx.part"), &WEIGHTS[..10]).unwrap();
+
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&progress);
+        let mut cache =
+            ModelCache::new(&root).with_progress(move |p| seen.lock().unwrap().push(p.downloaded));
+        let dir = cache.fetch(&bundle, None).unwrap();
+
+        assert_eq!(*requests.lock().unwrap(), [Some(10)]);
+        assert_eq!(fs::read(dir.join("model.onnx")).unwrap(), WEIGHTS);
+        assert!(!dir.join("model.onnx.part").exists());
+        assert_eq!(
+            progress.lock().unwrap().last(),
+            Some(&(WEIGHTS.len() as u64))
+        );
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn checksum_mismatch_is_not_installed() {
+        let (url, _) = serve(WEIGHTS);
+        let root = create_temp_dir("mismatch");
+        let mut bad = file(&url, "model.onnx", WEIGHTS, None);
+        bad.sha256 = sha256_of(b"something else");
+        let bundle = ModelBundle {
+            id: "mismatch".to_string(),
+            engine: "gigaam".to_string(),
+            variant: None,
+            files: vec![bad],
+        };
+
+        let err = ModelCache::new(&root).fetch(&bundle, None).unwrap_err();
+        assert!(err.to_string().contains("SHA-256 mismatch"), "{err}");
+        assert!(!root.join("mismatch/model.onnx").exists());
+        assert!(!root.join("mismatch/model.onnx.part").exists());
+
+        let escaping = ModelBundle {
+            id: "../outside".to_string(),
+            ..bundle
+        };
+        assert!(matches!(
+            ModelCache::new(&root).fetch(&escaping, None),
+            Err(TranscribeError::Config(_))
+        ));
+        fs::remove_dir_all(root).unwrap();
+    }
+}
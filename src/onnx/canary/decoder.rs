@@ -5,17 +5,22 @@ use ort::value::{DynValue, Tensor};
 
 use super::vocab::Vocab;
 use crate::decode::GreedyDecoder;
-use crate::TranscribeError;
+use crate::metrics::Recorder;
+use crate::onnx::session::SharedSession;
+use crate::{cancel, CancellationToken, TranscribeError};
 
+#[allow(clippy::too_many_arguments)]
 pub fn decode_autoregressive(
-    decoder: &mut Session,
+    decoder: &SharedSession,
     encoder_embeddings: &DynValue,
     encoder_mask: &DynValue,
     prompt_tokens: Vec<i64>,
     vocab: &Vocab,
     max_sequence_length: usize,
+    cancel: Option<&CancellationToken>,
+    rec: &mut Recorder,
 ) -> Result<String, TranscribeError> {
-    let (num_layers, hidden_dim) = extract_decoder_mems_shape(decoder)?;
+    let (num_layers, hidden_dim) = extract_decoder_mems_shape(&decoder.lock())?;
 
     log::debug!(
         "Decoder cache dimensions: num_layers={}, hidden_dim={}",
@@ -29,6 +34,7 @@ pub fn decode_autoregressive(
     let eos_id = vocab.eos_token_id();
     let mut greedy = GreedyDecoder::new(eos_id);
     let mut all_tokens = prompt_tokens;
+    let prompt_len = all_tokens.len();
 
     // Limit decode steps so total tokens (prompt + generated) stays within
     // the model's position embedding table (typically 1024).
@@ -41,6 +47,8 @@ pub fn decode_autoregressive(
     );
 
     for step in 0..max_steps {
+        cancel::check(cancel)?;
+
         let input_ids_tensor = if step == 0 {
             let len = all_tokens.len();
             let shape = vec![1i64, len as i64];
@@ -52,12 +60,16 @@ pub fn decode_autoregressive(
             Tensor::from_array((vec![1i64, 1i64], vec![last].into_boxed_slice()))?
         };
 
-        let mut outputs = decoder.run(ort::inputs![
-            "input_ids" => input_ids_tensor,
-            "encoder_embeddings" => encoder_embeddings,
-            "encoder_mask" => encoder_mask,
-            "decoder_mems" => decoder_mems
-        ])?;
+        // Lock per step so concurrent calls can interleave their steps.
+        let mut session = decoder.lock();
+        let mut outputs = rec.time("decoder", || {
+            session.run(ort::inputs![
+                "input_ids" => input_ids_tensor,
+                "encoder_embeddings" => encoder_embeddings,
+                "encoder_mask" => encoder_mask,
+                "decoder_mems" => decoder_mems
+            ])
+        })?;
 
         // Extract logits in a scoped borrow, then release before remove()
         let last_logits = {
@@ -91,6 +103,7 @@ pub fn decode_autoregressive(
         })?;
     }
 
+    rec.add_tokens(all_tokens.len() - prompt_len);
     let text = vocab.decode_tokens(&all_tokens);
     Ok(text)
 }
@@ -2,15 +2,19 @@ mod decoder;
 mod vocab;
 
 use std::path::Path;
+use std::sync::Arc;
 use std::time::Instant;
 
-use ort::session::Session;
 use ort::value::Tensor;
 
 use self::decoder::decode_autoregressive;
 use self::vocab::Vocab;
+use super::session::SharedSession;
+use super::{ModelFiles, ModelSource, SessionConfig};
+use crate::metrics::{MetricsSink, Recorder};
 use crate::{
-    ModelCapabilities, SpeechModel, TranscribeError, TranscribeOptions, TranscriptionResult,
+    CancellationToken, ModelCapabilities, SpeechModel, TranscribeError, TranscribeOptions,
+    TranscriptionResult,
 };
 
 /// Known Canary model variants, auto-detected from vocabulary size.
@@ -71,6 +75,10 @@ pub struct CanaryParams {
     pub use_itn: bool,
     /// Maximum number of tokens to generate. Defaults to 1024.
     pub max_sequence_length: usize,
+    /// Checked before each decoder step.
+    pub cancel: Option<CancellationToken>,
+    /// Receives stage timings of the call; see [`crate::metrics`].
+    pub metrics: Option<MetricsSink>,
 }
 
 impl Default for CanaryParams {
@@ -81,16 +89,22 @@ impl Default for CanaryParams {
             use_pnc: true,
             use_itn: true,
             max_sequence_length: 1024,
+            cancel: None,
+            metrics: None,
         }
     }
 }
 
 /// Canary speech model backed by three ONNX sessions (preprocessor, encoder, decoder).
+///
+/// Clones share the loaded sessions and vocabulary, so one load can serve
+/// several threads; each call keeps its decoder cache to itself.
+#[derive(Clone)]
 pub struct CanaryModel {
-    preprocessor: Session,
-    encoder: Session,
-    decoder: Session,
-    vocab: Vocab,
+    preprocessor: SharedSession,
+    encoder: SharedSession,
+    decoder: SharedSession,
+    vocab: Arc<Vocab>,
     variant: CanaryVariant,
 }
 
@@ -105,35 +119,75 @@ impl CanaryModel {
     pub fn load(
         model_dir: &Path,
         quantization: &super::Quantization,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_with_config(model_dir, quantization, &SessionConfig::default())
+    }
+
+    /// Load with explicit ONNX session settings (execution providers,
+    /// threads, ...).
+    pub fn load_with_config(
+        model_dir: &Path,
+        quantization: &super::Quantization,
+        config: &SessionConfig,
     ) -> Result<Self, TranscribeError> {
         if !model_dir.exists() {
             return Err(TranscribeError::ModelNotFound(model_dir.to_path_buf()));
         }
+        Self::load_from(ModelSource::Dir(model_dir), quantization, config)
+    }
 
+    /// Load a Canary model from in-memory files named as in [`load`](Self::load).
+    pub fn load_from_memory(
+        files: &ModelFiles,
+        quantization: &super::Quantization,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_from_memory_with_config(files, quantization, &SessionConfig::default())
+    }
+
+    /// [`load_from_memory`](Self::load_from_memory) with explicit ONNX
+    /// session settings.
+    pub fn load_from_memory_with_config(
+        files: &ModelFiles,
+        quantization: &super::Quantization,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_from(ModelSource::Memory(files), quantization, config)
+    }
+
+    fn load_from(
+        source: ModelSource,
+        quantization: &super::Quantization,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
         let load_start = Instant::now();
 
         // Preprocessor is always FP32
-        let preprocessor_path = model_dir.join("nemo128.onnx");
         log::info!(
             "Loading Canary preprocessor from {:?}...",
-            preprocessor_path
+            source.path("nemo128.onnx")
         );
-        let preprocessor = super::session::create_session(&preprocessor_path)?;
+        let preprocessor = source.session("nemo128.onnx", config)?;
 
         // Encoder and decoder respect quantization
-        let encoder_path =
-            super::session::resolve_model_path(model_dir, "encoder-model", quantization);
-        log::info!("Loading Canary encoder from {:?}...", encoder_path);
-        let encoder = super::session::create_session(&encoder_path)?;
+        let encoder_file = source.resolve("encoder-model", quantization, config)?;
+        log::info!(
+            "Loading Canary encoder from {:?}...",
+            source.path(&encoder_file)
+        );
+        let encoder = source.session(&encoder_file, config)?;
 
-        let decoder_path =
-            super::session::resolve_model_path(model_dir, "decoder-model", quantization);
-        log::info!("Loading Canary decoder from {:?}...", decoder_path);
-        let decoder = super::session::create_session(&decoder_path)?;
+        let decoder_file = source.resolve("decoder-model", quantization, config)?;
+        log::info!(
+            "Loading Canary decoder from {:?}...",
+            source.path(&decoder_file)
+        );
+        let decoder = source.session(&decoder_file, config)?;
 
         // Vocabulary
-        let vocab_path = model_dir.join("vocab.txt");
-        let vocab = Vocab::load(&vocab_path)?;
+        let vocab_text = source
+            .read_to_string("vocab.txt")
+            .map_err(|e| TranscribeError::Config(format!("Failed to read vocab file: {e}")))?;
+        let vocab = Vocab::parse(&vocab_text)?;
 
         let variant = CanaryVariant::detect(vocab.size());
         log::info!(
@@ -144,17 +198,17 @@ impl CanaryModel {
         );
 
         Ok(Self {
-            preprocessor,
-            encoder,
-            decoder,
-            vocab,
+            preprocessor: preprocessor.into(),
+            encoder: encoder.into(),
+            decoder: decoder.into(),
+            vocab: Arc::new(vocab),
             variant,
         })
     }
 
     /// Transcribe with model-specific parameters.
     pub fn transcribe_with(
-        &mut self,
+        &self,
         samples: &[f32],
         params: &CanaryParams,
     ) -> Result<TranscriptionResult, TranscribeError> {
@@ -165,6 +219,7 @@ impl CanaryModel {
         let use_itn = params.use_itn && self.variant != CanaryVariant::Flash;
 
         let total_start = Instant::now();
+        let mut rec = Recorder::new(params.metrics.as_ref(), "canary", samples.len(), 16000);
 
         // --- Step 1: Preprocess audio -> mel features ---
         let preprocess_start = Instant::now();
@@ -179,10 +234,13 @@ impl CanaryModel {
         let waveforms_lens =
             Tensor::from_array((vec![1i64], vec![num_samples as i64].into_boxed_slice()))?;
 
-        let mut preprocess_out = self.preprocessor.run(ort::inputs![
-            "waveforms" => waveforms,
-            "waveforms_lens" => waveforms_lens
-        ])?;
+        let mut preprocessor = self.preprocessor.lock();
+        let mut preprocess_out = rec.time("preprocessor", || {
+            preprocessor.run(ort::inputs![
+                "waveforms" => waveforms,
+                "waveforms_lens" => waveforms_lens
+            ])
+        })?;
 
         log::debug!(
             "Preprocessor output: features shape {:?} ({:.2?})",
@@ -201,10 +259,13 @@ impl CanaryModel {
         // --- Step 2: Encode mel features -> encoder embeddings ---
         let encode_start = Instant::now();
 
-        let mut encoder_out = self.encoder.run(ort::inputs![
-            "audio_signal" => features,
-            "length" => features_lens
-        ])?;
+        let mut encoder = self.encoder.lock();
+        let mut encoder_out = rec.time("encoder", || {
+            encoder.run(ort::inputs![
+                "audio_signal" => features,
+                "length" => features_lens
+            ])
+        })?;
 
         log::debug!(
             "Encoder output: embeddings shape {:?}, mask shape {:?} ({:.2?})",
@@ -236,13 +297,16 @@ impl CanaryModel {
         let decode_start = Instant::now();
 
         let text = decode_autoregressive(
-            &mut self.decoder,
+            &self.decoder,
             &encoder_embeddings,
             &encoder_mask,
             prompt_tokens,
             &self.vocab,
             params.max_sequence_length,
+            params.cancel.as_ref(),
+            &mut rec,
         )?;
+        rec.finish();
 
         log::debug!("Decoding completed in {:.2?}", decode_start.elapsed());
         log::info!(
@@ -266,6 +330,7 @@ impl SpeechModel for CanaryModel {
             sample_rate: 16000,
             languages: self.variant.languages(),
             supports_timestamps: false,
+            supports_word_timestamps: false,
             supports_translation: true,
             supports_streaming: false,
         }
@@ -281,6 +346,8 @@ impl SpeechModel for CanaryModel {
         let params = CanaryParams {
             language: Some(src_lang.to_string()),
             target_language: Some(tgt_lang.to_string()),
+            cancel: options.cancel.clone(),
+            metrics: options.metrics.clone(),
             ..Default::default()
         };
         self.transcribe_with(samples, &params)
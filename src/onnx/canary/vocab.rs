@@ -1,6 +1,4 @@
 use std::collections::HashMap;
-use std::fs;
-use std::path::Path;
 
 use crate::TranscribeError;
 
@@ -12,10 +10,8 @@ pub struct Vocab {
 }
 
 impl Vocab {
-    pub fn load(path: &Path) -> Result<Self, TranscribeError> {
-        let content = fs::read_to_string(path)
-            .map_err(|e| TranscribeError::Config(format!("Failed to read vocab file: {e}")))?;
-
+    /// Parse the contents of a `vocab.txt` file.
+    pub fn parse(content: &str) -> Result<Self, TranscribeError> {
         let mut token_to_id_map = HashMap::new();
         let mut id_to_token_map = HashMap::new();
 
@@ -49,12 +45,6 @@ impl Vocab {
 
         let size = token_to_id_map.len();
 
-        log::info!(
-            "Loaded vocabulary with {} tokens from {}",
-            size,
-            path.display()
-        );
-
         Ok(Self {
             token_to_id_map,
             id_to_token_map,
@@ -134,49 +124,25 @@ impl Vocab {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::io::Write;
-
-    fn create_temp_dir(name: &str) -> std::path::PathBuf {
-        let dir =
-            std::env::temp_dir().join(format!("canary_vocab_test_{name}_{}", std::process::id()));
-        let _ = fs::create_dir_all(&dir);
-        dir
-    }
 
     #[test]
     fn test_vocab_load_and_lookup() {
-        let dir = create_temp_dir("load");
-        let vocab_path = dir.join("vocab.txt");
-        let mut f = fs::File::create(&vocab_path).unwrap();
-        writeln!(f, "<|endoftext|> 3").unwrap();
-        writeln!(f, "hello 10").unwrap();
-        writeln!(f, "world 20").unwrap();
-
-        let vocab = Vocab::load(&vocab_path).unwrap();
+        let vocab = Vocab::parse("<|endoftext|> 3\nhello 10\nworld 20\n").unwrap();
 
         assert_eq!(vocab.token_to_id("<|endoftext|>"), Some(3));
         assert_eq!(vocab.token_to_id("hello"), Some(10));
         assert_eq!(vocab.id_to_token(20), Some("world"));
         assert_eq!(vocab.eos_token_id(), 3);
-
-        let _ = fs::remove_dir_all(&dir);
     }
 
     #[test]
     fn test_decode_tokens_filters_special() {
-        let dir = create_temp_dir("decode");
-        let vocab_path = dir.join("vocab.txt");
-        let mut f = fs::File::create(&vocab_path).unwrap();
-        writeln!(f, "<|endoftext|> 3").unwrap();
-        writeln!(f, "<|startoftranscript|> 1").unwrap();
-        writeln!(f, "\u{2581}Hello 10").unwrap();
-        writeln!(f, "\u{2581}world 20").unwrap();
-
-        let vocab = Vocab::load(&vocab_path).unwrap();
+        let vocab = Vocab::parse(
+            "<|endoftext|> 3\n<|startoftranscript|> 1\n\u{2581}Hello 10\n\u{2581}world 20\n",
+        )
+        .unwrap();
         let text = vocab.decode_tokens(&[1, 10, 20, 3]);
 
         assert_eq!(text, "Hello world");
-
-        let _ = fs::remove_dir_all(&dir);
     }
 }
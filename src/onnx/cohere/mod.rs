@@ -2,17 +2,20 @@
 
 use std::borrow::Cow;
 use std::collections::HashMap;
-use std::path::{Path, PathBuf};
+use std::path::Path;
+use std::sync::Arc;
 
 use ndarray::{Array2, ArrayD, Ix3, IxDyn};
-use ort::session::Session;
 use ort::session::SessionInputValue;
 use ort::value::DynValue;
 
-use super::{session, Quantization};
-use crate::decode::{load_vocab, parse_byte_token, GreedyDecoder};
+use super::session::SharedSession;
+use super::{quantization_candidates, ModelFiles, ModelSource, Quantization, SessionConfig};
+use crate::decode::{parse_byte_token, parse_vocab, GreedyDecoder};
+use crate::metrics::{MetricsSink, Recorder};
 use crate::{
-    ModelCapabilities, SpeechModel, TranscribeError, TranscribeOptions, TranscriptionResult,
+    cancel, CancellationToken, ModelCapabilities, SpeechModel, TranscribeError, TranscribeOptions,
+    TranscriptionResult,
 };
 
 const SAMPLE_RATE: u32 = 16000;
@@ -30,6 +33,7 @@ const CAPABILITIES: ModelCapabilities = ModelCapabilities {
         "en", "de", "fr", "it", "es", "pt", "el", "nl", "pl", "ar", "vi", "zh", "ja", "ko",
     ],
     supports_timestamps: false,
+    supports_word_timestamps: false,
     supports_translation: false,
     supports_streaming: false,
 };
@@ -42,13 +46,22 @@ pub struct CohereParams {
     pub translate: bool,
     /// Maximum number of autoregressive tokens to emit per chunk.
     pub max_new_tokens: Option<usize>,
+    /// Checked before each decoder step.
+    pub cancel: Option<CancellationToken>,
+    /// Receives stage timings of the call; see [`crate::metrics`].
+    pub metrics: Option<MetricsSink>,
 }
 
+/// Cohere Transcribe model.
+///
+/// Clones share the loaded sessions and vocabulary, so one load can serve
+/// several threads; each call keeps its KV cache to itself.
+#[derive(Clone)]
 pub struct CohereModel {
-    encoder: Session,
-    decoder: Session,
-    vocab: Vec<String>,
-    token_to_id: HashMap<String, i64>,
+    encoder: SharedSession,
+    decoder: SharedSession,
+    vocab: Arc<[String]>,
+    token_to_id: Arc<HashMap<String, i64>>,
     eos_id: i64,
     encoder_input_name: String,
     decoder_input_names: Vec<String>,
@@ -56,26 +69,77 @@ pub struct CohereModel {
 
 impl CohereModel {
     pub fn load(model_dir: &Path, quantization: &Quantization) -> Result<Self, TranscribeError> {
-        let encoder_path = resolve_model_file(
-            model_dir,
-            encoder_candidates(quantization),
+        Self::load_with_config(model_dir, quantization, &SessionConfig::default())
+    }
+
+    /// Load with explicit ONNX session settings (execution providers,
+    /// threads, ...).
+    pub fn load_with_config(
+        model_dir: &Path,
+        quantization: &Quantization,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_from(ModelSource::Dir(model_dir), quantization, config)
+    }
+
+    /// Load from in-memory files named as on disk (e.g.
+    /// `cohere-encoder.int4.onnx` or `onnx/encoder_model.int8.onnx`, and
+    /// `tokens.txt`).
+    pub fn load_from_memory(
+        files: &ModelFiles,
+        quantization: &Quantization,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_from_memory_with_config(files, quantization, &SessionConfig::default())
+    }
+
+    /// [`load_from_memory`](Self::load_from_memory) with explicit ONNX
+    /// session settings.
+    pub fn load_from_memory_with_config(
+        files: &ModelFiles,
+        quantization: &Quantization,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_from(ModelSource::Memory(files), quantization, config)
+    }
+
+    fn load_from(
+        source: ModelSource,
+        quantization: &Quantization,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        let variants = quantization_candidates(quantization, config);
+        let encoder_file = resolve_model_file(
+            &source,
+            &variants
+                .iter()
+                .flat_map(|q| encoder_candidates(q).iter().copied())
+                .collect::<Vec<_>>(),
             "cohere-encoder.int4.onnx",
         )?;
-        let decoder_path = resolve_model_file(
-            model_dir,
-            decoder_candidates(quantization),
+        let decoder_file = resolve_model_file(
+            &source,
+            &variants
+                .iter()
+                .flat_map(|q| decoder_candidates(q).iter().copied())
+                .collect::<Vec<_>>(),
             "cohere-decoder.int4.onnx",
         )?;
-        let vocab_path =
-            resolve_model_file(model_dir, &["tokens.txt", "vocabulary.txt"], "tokens.txt")?;
+        let vocab_file =
+            resolve_model_file(&source, &["tokens.txt", "vocabulary.txt"], "tokens.txt")?;
 
-        log::info!("Loading Cohere encoder from {:?}...", encoder_path);
-        let encoder = session::create_session(&encoder_path)?;
+        log::info!(
+            "Loading Cohere encoder from {:?}...",
+            source.path(&encoder_file)
+        );
+        let encoder = source.session(&encoder_file, config)?;
 
-        log::info!("Loading Cohere decoder from {:?}...", decoder_path);
-        let decoder = session::create_session(&decoder_path)?;
+        log::info!(
+            "Loading Cohere decoder from {:?}...",
+            source.path(&decoder_file)
+        );
+        let decoder = source.session(&decoder_file, config)?;
 
-        let (vocab, _) = load_vocab(&vocab_path)?;
+        let (vocab, _) = parse_vocab(&source.read_to_string(&vocab_file)?);
         let token_to_id = vocab
             .iter()
             .enumerate()
@@ -96,10 +160,10 @@ impl CohereModel {
         let eos_id = token_to_id.get("<|endoftext|>").copied().unwrap_or(3);
 
         Ok(Self {
-            encoder,
-            decoder,
-            vocab,
-            token_to_id,
+            encoder: encoder.into(),
+            decoder: decoder.into(),
+            vocab: vocab.into(),
+            token_to_id: Arc::new(token_to_id),
             eos_id,
             encoder_input_name,
             decoder_input_names,
@@ -107,7 +171,7 @@ impl CohereModel {
     }
 
     pub fn transcribe_with(
-        &mut self,
+        &self,
         samples: &[f32],
         params: &CohereParams,
     ) -> Result<TranscriptionResult, TranscribeError> {
@@ -130,7 +194,20 @@ impl CohereModel {
             .unwrap_or(DEFAULT_MAX_NEW_TOKENS)
             .min(MAX_SEQ_LEN.saturating_sub(prompt_ids.len()));
 
-        let text = self.transcribe_chunk(samples, &prompt_ids, max_new_tokens)?;
+        let mut rec = Recorder::new(
+            params.metrics.as_ref(),
+            CAPABILITIES.engine_id,
+            samples.len(),
+            SAMPLE_RATE,
+        );
+        let text = self.transcribe_chunk(
+            samples,
+            &prompt_ids,
+            max_new_tokens,
+            params.cancel.as_ref(),
+            &mut rec,
+        )?;
+        rec.finish();
 
         Ok(TranscriptionResult {
             text,
@@ -139,17 +216,20 @@ impl CohereModel {
     }
 
     fn transcribe_chunk(
-        &mut self,
+        &self,
         samples: &[f32],
         prompt_ids: &[i64],
         max_new_tokens: usize,
+        cancel: Option<&CancellationToken>,
+        rec: &mut Recorder,
     ) -> Result<String, TranscribeError> {
         let audio = Array2::from_shape_vec((1, samples.len()), samples.to_vec())?.into_dyn();
         let (cross_k, cross_v) = {
-            let mut encoder_outputs = self.encoder.run(vec![(
-                Cow::Owned(self.encoder_input_name.clone()),
-                ort::value::Value::from_array(audio)?.into_dyn(),
-            )])?;
+            let mut encoder = self.encoder.lock();
+            let audio = ort::value::Value::from_array(audio)?.into_dyn();
+            let mut encoder_outputs = rec.time("encoder", || {
+                encoder.run(vec![(Cow::Owned(self.encoder_input_name.clone()), audio)])
+            })?;
             let cross_k = remove_output(&mut encoder_outputs, "n_layer_cross_k")?;
             let cross_v = remove_output(&mut encoder_outputs, "n_layer_cross_v")?;
             (cross_k, cross_v)
@@ -192,6 +272,7 @@ impl CohereModel {
             .into_dyn();
 
         for _ in 0..max_new_tokens {
+            cancel::check(cancel)?;
             let n_tokens = current_tokens.len();
             let tokens = Array2::from_shape_vec((1, n_tokens), current_tokens.clone())?.into_dyn();
             let offset_tensor = ndarray::arr0(offset).into_dyn();
@@ -225,7 +306,9 @@ impl CohereModel {
                 ),
             ];
 
-            let mut decoder_outputs = self.decoder.run(inputs)?;
+            // Lock per step so concurrent calls can interleave their steps.
+            let mut decoder = self.decoder.lock();
+            let mut decoder_outputs = rec.time("decoder", || decoder.run(inputs))?;
 
             // Extract last-position logits in a scoped borrow, then release before remove().
             let last_logits = {
@@ -254,6 +337,7 @@ impl CohereModel {
             self_v_cache = remove_output(&mut decoder_outputs, "out_n_layer_self_v_cache")?;
         }
 
+        rec.add_tokens(generated_ids.len());
         Ok(self.decode_ids(&generated_ids))
     }
 
@@ -359,26 +443,28 @@ impl SpeechModel for CohereModel {
                 language: options.language.clone(),
                 translate: options.translate,
                 max_new_tokens: None,
+                cancel: options.cancel.clone(),
+                metrics: options.metrics.clone(),
             },
         )
     }
 }
 
 fn resolve_model_file(
-    model_dir: &Path,
+    source: &ModelSource,
     candidates: &[&str],
     missing_name: &str,
-) -> Result<PathBuf, TranscribeError> {
-    for base_dir in [model_dir.to_path_buf(), model_dir.join("onnx")] {
+) -> Result<String, TranscribeError> {
+    for base_dir in ["", "onnx/"] {
         for candidate in candidates {
-            let path = base_dir.join(candidate);
-            if path.exists() {
-                return Ok(path);
+            let name = format!("{base_dir}{candidate}");
+            if source.exists(&name) {
+                return Ok(name);
             }
         }
     }
 
-    Err(TranscribeError::ModelNotFound(model_dir.join(missing_name)))
+    Err(TranscribeError::ModelNotFound(source.path(missing_name)))
 }
 
 fn encoder_candidates(quantization: &Quantization) -> &'static [&'static str] {
@@ -387,6 +473,8 @@ fn encoder_candidates(quantization: &Quantization) -> &'static [&'static str] {
         Quantization::Int8 => &["cohere-encoder.int8.onnx", "encoder_model.int8.onnx"],
         Quantization::FP16 => &["cohere-encoder.fp16.onnx", "encoder_model_fp16.onnx"],
         Quantization::FP32 => &["cohere-encoder.onnx", "encoder_model.onnx"],
+        // Expanded by `quantization_candidates`.
+        Quantization::Auto => &[],
     }
 }
 
@@ -396,6 +484,8 @@ fn decoder_candidates(quantization: &Quantization) -> &'static [&'static str] {
         Quantization::Int8 => &["cohere-decoder.int8.onnx", "decoder_model_merged.int8.onnx"],
         Quantization::FP16 => &["cohere-decoder.fp16.onnx", "decoder_model_merged_fp16.onnx"],
         Quantization::FP32 => &["cohere-decoder.onnx", "decoder_model_merged.onnx"],
+        // Expanded by `quantization_candidates`.
+        Quantization::Auto => &[],
     }
 }
 
@@ -1,13 +1,14 @@
 use ort::inputs;
-use ort::session::Session;
 use ort::value::TensorRef;
 use std::path::Path;
+use std::sync::Arc;
 
-use super::session;
-use super::Quantization;
-use crate::decode::tokens::load_vocab;
+use super::session::SharedSession;
+use super::{ModelFiles, ModelSource, Quantization, SessionConfig};
+use crate::decode::tokens::parse_vocab;
 use crate::decode::{ctc_greedy_decode, sentencepiece_to_text};
 use crate::features::{compute_mel, MelConfig, WindowType};
+use crate::metrics::{MetricsSink, Recorder};
 use crate::TranscribeError;
 use crate::{ModelCapabilities, SpeechModel, TranscribeOptions, TranscriptionResult};
 
@@ -17,6 +18,7 @@ const CAPABILITIES: ModelCapabilities = ModelCapabilities {
     sample_rate: 16000,
     languages: &["ru"],
     supports_timestamps: false,
+    supports_word_timestamps: false,
     supports_translation: false,
     supports_streaming: false,
 };
@@ -26,31 +28,77 @@ const CAPABILITIES: ModelCapabilities = ModelCapabilities {
 pub struct GigaAMParams {
     /// Language hint (currently unused, GigaAM is Russian-only).
     pub language: Option<String>,
+    /// Receives stage timings of the call; see [`crate::metrics`].
+    pub metrics: Option<MetricsSink>,
 }
 
+/// GigaAM CTC model.
+///
+/// Clones share the loaded session and vocabulary, so one load can serve
+/// several threads.
+#[derive(Clone)]
 pub struct GigaAMModel {
-    session: Session,
+    session: SharedSession,
     mel_config: MelConfig,
-    vocab: Vec<String>,
+    vocab: Arc<[String]>,
     blank_idx: i64,
 }
 
 impl GigaAMModel {
     pub fn load(model_dir: &Path, quantization: &Quantization) -> Result<Self, TranscribeError> {
-        let model_path = session::resolve_model_path(model_dir, "model", quantization);
-        let vocab_path = model_dir.join("vocab.txt");
+        Self::load_with_config(model_dir, quantization, &SessionConfig::default())
+    }
+
+    /// Load with explicit ONNX session settings (execution providers,
+    /// threads, ...).
+    pub fn load_with_config(
+        model_dir: &Path,
+        quantization: &Quantization,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_from(ModelSource::Dir(model_dir), quantization, config)
+    }
+
+    /// Load from in-memory files named as on disk (`model[.int8].onnx`,
+    /// `vocab.txt`).
+    pub fn load_from_memory(
+        files: &ModelFiles,
+        quantization: &Quantization,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_from_memory_with_config(files, quantization, &SessionConfig::default())
+    }
+
+    /// [`load_from_memory`](Self::load_from_memory) with explicit ONNX
+    /// session settings.
+    pub fn load_from_memory_with_config(
+        files: &ModelFiles,
+        quantization: &Quantization,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_from(ModelSource::Memory(files), quantization, config)
+    }
+
+    fn load_from(
+        source: ModelSource,
+        quantization: &Quantization,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        let model_file = source.resolve("model", quantization, config)?;
 
-        if !model_path.exists() {
-            return Err(TranscribeError::ModelNotFound(model_path));
+        if !source.exists(&model_file) {
+            return Err(TranscribeError::ModelNotFound(source.path(&model_file)));
         }
-        if !vocab_path.exists() {
-            return Err(TranscribeError::ModelNotFound(vocab_path));
+        if !source.exists("vocab.txt") {
+            return Err(TranscribeError::ModelNotFound(source.path("vocab.txt")));
         }
 
-        log::info!("Loading GigaAM model from {:?}...", model_path);
-        let session = session::create_session(&model_path)?;
+        log::info!(
+            "Loading GigaAM model from {:?}...",
+            source.path(&model_file)
+        );
+        let session = source.session(&model_file, config)?;
 
-        let (vocab, blank_idx) = load_vocab(&vocab_path)?;
+        let (vocab, blank_idx) = parse_vocab(&source.read_to_string("vocab.txt")?);
         let blank_idx = blank_idx.unwrap_or(vocab.len() as i32) as i64;
 
         log::info!(
@@ -73,23 +121,27 @@ impl GigaAMModel {
         };
 
         Ok(Self {
-            session,
+            session: session.into(),
             mel_config,
-            vocab,
+            vocab: vocab.into(),
             blank_idx,
         })
     }
 
     /// Transcribe with model-specific parameters.
     pub fn transcribe_with(
-        &mut self,
+        &self,
         samples: &[f32],
-        _params: &GigaAMParams,
+        params: &GigaAMParams,
     ) -> Result<TranscriptionResult, TranscribeError> {
-        self.infer(samples)
+        self.infer(samples, params.metrics.as_ref())
     }
 
-    fn infer(&mut self, samples: &[f32]) -> Result<TranscriptionResult, TranscribeError> {
+    fn infer(
+        &self,
+        samples: &[f32],
+        metrics: Option<&MetricsSink>,
+    ) -> Result<TranscriptionResult, TranscribeError> {
         if samples.len() < self.mel_config.n_fft {
             return Ok(TranscriptionResult {
                 text: String::new(),
@@ -97,8 +149,15 @@ impl GigaAMModel {
             });
         }
 
+        let mut rec = Recorder::new(
+            metrics,
+            CAPABILITIES.engine_id,
+            samples.len(),
+            CAPABILITIES.sample_rate,
+        );
+
         // 1. Compute mel spectrogram [frames, mels]
-        let mel = compute_mel(samples, &self.mel_config);
+        let mel = rec.time("features", || compute_mel(samples, &self.mel_config));
         let time_steps = mel.shape()[0];
 
         log::debug!(
@@ -120,18 +179,22 @@ impl GigaAMModel {
             "features" => t_features,
             "feature_lengths" => t_lengths,
         };
-        let outputs = self.session.run(inputs)?;
-
         // 4. Extract log_probs [1, T', vocab_size]
-        let log_probs = outputs[0].try_extract_array::<f32>()?;
-        let log_probs = log_probs.to_owned().into_dimensionality::<ndarray::Ix3>()?;
+        let log_probs = rec.time("encoder", || -> Result<_, TranscribeError> {
+            let mut session = self.session.lock();
+            let outputs = session.run(inputs)?;
+            let log_probs = outputs[0].try_extract_array::<f32>()?;
+            Ok(log_probs.to_owned().into_dimensionality::<ndarray::Ix3>()?)
+        })?;
 
         log::debug!("Log probs shape: {:?}", log_probs.shape());
 
         // 5. CTC greedy decode
         let num_frames = log_probs.shape()[1] as i64;
         let logits_lengths = vec![num_frames];
-        let results = ctc_greedy_decode(&log_probs.view(), &logits_lengths, self.blank_idx);
+        let results = rec.time("decoder", || {
+            ctc_greedy_decode(&log_probs.view(), &logits_lengths, self.blank_idx)
+        });
 
         // 6. Convert token IDs to text
         let tokens: Vec<&str> = results[0]
@@ -153,6 +216,8 @@ impl GigaAMModel {
             .collect();
 
         let text = sentencepiece_to_text(&tokens);
+        rec.add_tokens(tokens.len());
+        rec.finish();
 
         Ok(TranscriptionResult {
             text,
@@ -169,8 +234,8 @@ impl SpeechModel for GigaAMModel {
     fn transcribe_raw(
         &mut self,
         samples: &[f32],
-        _options: &TranscribeOptions,
+        options: &TranscribeOptions,
     ) -> Result<TranscriptionResult, TranscribeError> {
-        self.infer(samples)
+        self.infer(samples, options.metrics.as_ref())
     }
 }
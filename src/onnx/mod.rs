@@ -4,20 +4,47 @@
 //! and implements the `SpeechModel` trait for a unified transcription API.
 
 pub mod session;
+mod source;
+
+pub use session::SessionConfig;
+pub use source::{available_quantizations, ModelFiles};
+pub(crate) use source::{quantization_candidates, quantization_suffix, ModelSource};
 
 /// Preferred precision for ONNX model loading.
 ///
 /// This selects which model file variant to load. If the requested
-/// variant is not found on disk, falls back to FP32 with a warning.
+/// variant is not found on disk, falls back to FP32 with a warning, or
+/// fails with [`SessionConfig::with_strict_quantization`].
 /// ONNX quantization is baked into the model file — this enum controls
-/// file selection, not runtime behavior.
-#[derive(Debug, Clone, Default, PartialEq)]
+/// file selection, not runtime behavior. Use [`available_quantizations`]
+/// to see which variants a model directory has.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum Quantization {
     #[default]
     FP32,
     FP16,
     Int8,
     Int4,
+    /// Best variant on disk for the session's execution providers: int8,
+    /// then FP32, on CPU (and XNNPACK); FP16, then FP32, on GPU providers
+    /// (CUDA, TensorRT, ROCm, DirectML, CoreML, WebGPU). Other variants are
+    /// used only when neither is present.
+    Auto,
+}
+
+impl std::str::FromStr for Quantization {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "fp32" | "f32" => Ok(Self::FP32),
+            "fp16" | "f16" => Ok(Self::FP16),
+            "int8" | "q8" => Ok(Self::Int8),
+            "int4" | "q4" => Ok(Self::Int4),
+            "auto" => Ok(Self::Auto),
+            other => Err(format!("unknown quantization: {other}")),
+        }
+    }
 }
 
 pub mod canary;
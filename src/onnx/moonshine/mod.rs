@@ -64,6 +64,26 @@ impl MoonshineVariant {
     }
 }
 
+impl std::str::FromStr for MoonshineVariant {
+    type Err = String;
+
+    /// Parse `tiny`, `tiny-ar`, `base-es`, etc. (`_` also accepted).
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().replace('_', "-").as_str() {
+            "tiny" => Ok(Self::Tiny),
+            "tiny-ar" => Ok(Self::TinyAr),
+            "tiny-zh" => Ok(Self::TinyZh),
+            "tiny-ja" => Ok(Self::TinyJa),
+            "tiny-ko" => Ok(Self::TinyKo),
+            "tiny-uk" => Ok(Self::TinyUk),
+            "tiny-vi" => Ok(Self::TinyVi),
+            "base" => Ok(Self::Base),
+            "base-es" => Ok(Self::BaseEs),
+            other => Err(format!("unknown Moonshine variant: {other}")),
+        }
+    }
+}
+
 impl Default for MoonshineVariant {
     fn default() -> Self {
         MoonshineVariant::Tiny
@@ -1,17 +1,17 @@
 use ndarray::{Array2, ArrayD, IxDyn};
 use ort::inputs;
-use ort::session::Session;
 use ort::value::TensorRef;
 use std::collections::HashMap;
-use std::fs::File;
-use std::io::BufReader;
 use std::path::Path;
+use std::sync::Arc;
 
 use crate::decode::{parse_byte_token, GreedyDecoder};
-use crate::onnx::session;
-use crate::onnx::Quantization;
+use crate::metrics::{MetricsSink, Recorder};
+use crate::onnx::session::SharedSession;
+use crate::onnx::{ModelFiles, ModelSource, Quantization, SessionConfig};
 use crate::{
-    ModelCapabilities, SpeechModel, TranscribeError, TranscribeOptions, TranscriptionResult,
+    cancel, CancellationToken, ModelCapabilities, SpeechModel, TranscribeError, TranscribeOptions,
+    TranscriptionResult,
 };
 
 use super::{MoonshineVariant, SAMPLE_RATE};
@@ -25,6 +25,7 @@ const CAPABILITIES: ModelCapabilities = ModelCapabilities {
     sample_rate: 16000,
     languages: &["en"],
     supports_timestamps: false,
+    supports_word_timestamps: false,
     supports_translation: false,
     supports_streaming: false,
 };
@@ -36,12 +37,21 @@ pub struct MoonshineParams {
     pub language: Option<String>,
     /// Maximum number of tokens to generate.
     pub max_length: Option<usize>,
+    /// Checked before each decoder step.
+    pub cancel: Option<CancellationToken>,
+    /// Receives stage timings of the call; see [`crate::metrics`].
+    pub metrics: Option<MetricsSink>,
 }
 
+/// Moonshine encoder-decoder model.
+///
+/// Clones share the loaded sessions and tokenizer, so one load can serve
+/// several threads; each call keeps its KV cache to itself.
+#[derive(Clone)]
 pub struct MoonshineModel {
-    encoder: Session,
-    decoder: Session,
-    tokenizer: MoonshineTokenizer,
+    encoder: SharedSession,
+    decoder: SharedSession,
+    tokenizer: Arc<MoonshineTokenizer>,
     variant: MoonshineVariant,
     encoder_input_names: Vec<String>,
     decoder_input_names: Vec<String>,
@@ -53,22 +63,69 @@ impl MoonshineModel {
         variant: MoonshineVariant,
         quantization: &Quantization,
     ) -> Result<Self, TranscribeError> {
-        let encoder_path = session::resolve_model_path(model_dir, "encoder_model", quantization);
-        let decoder_path =
-            session::resolve_model_path(model_dir, "decoder_model_merged", quantization);
+        Self::load_with_config(model_dir, variant, quantization, &SessionConfig::default())
+    }
+
+    /// Load with explicit ONNX session settings (execution providers,
+    /// threads, ...).
+    pub fn load_with_config(
+        model_dir: &Path,
+        variant: MoonshineVariant,
+        quantization: &Quantization,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_from(ModelSource::Dir(model_dir), variant, quantization, config)
+    }
 
-        if !encoder_path.exists() {
-            return Err(TranscribeError::ModelNotFound(encoder_path));
+    /// Load from in-memory files named as on disk
+    /// (`encoder_model[.int8].onnx`, `decoder_model_merged[.int8].onnx`,
+    /// `tokenizer.json`).
+    pub fn load_from_memory(
+        files: &ModelFiles,
+        variant: MoonshineVariant,
+        quantization: &Quantization,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_from_memory_with_config(files, variant, quantization, &SessionConfig::default())
+    }
+
+    /// [`load_from_memory`](Self::load_from_memory) with explicit ONNX
+    /// session settings.
+    pub fn load_from_memory_with_config(
+        files: &ModelFiles,
+        variant: MoonshineVariant,
+        quantization: &Quantization,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_from(ModelSource::Memory(files), variant, quantization, config)
+    }
+
+    fn load_from(
+        source: ModelSource,
+        variant: MoonshineVariant,
+        quantization: &Quantization,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        let encoder_file = source.resolve("encoder_model", quantization, config)?;
+        let decoder_file = source.resolve("decoder_model_merged", quantization, config)?;
+
+        if !source.exists(&encoder_file) {
+            return Err(TranscribeError::ModelNotFound(source.path(&encoder_file)));
         }
-        if !decoder_path.exists() {
-            return Err(TranscribeError::ModelNotFound(decoder_path));
+        if !source.exists(&decoder_file) {
+            return Err(TranscribeError::ModelNotFound(source.path(&decoder_file)));
         }
 
-        log::info!("Loading Moonshine encoder from {:?}...", encoder_path);
-        let encoder = session::create_session(&encoder_path)?;
+        log::info!(
+            "Loading Moonshine encoder from {:?}...",
+            source.path(&encoder_file)
+        );
+        let encoder = source.session(&encoder_file, config)?;
 
-        log::info!("Loading Moonshine decoder from {:?}...", decoder_path);
-        let decoder = session::create_session(&decoder_path)?;
+        log::info!(
+            "Loading Moonshine decoder from {:?}...",
+            source.path(&decoder_file)
+        );
+        let decoder = source.session(&decoder_file, config)?;
 
         let encoder_input_names: Vec<String> = encoder
             .inputs()
@@ -81,12 +138,12 @@ impl MoonshineModel {
             .map(|i| i.name().to_string())
             .collect();
 
-        let tokenizer = MoonshineTokenizer::new(model_dir)?;
+        let tokenizer = MoonshineTokenizer::new(&source)?;
 
         Ok(Self {
-            encoder,
-            decoder,
-            tokenizer,
+            encoder: encoder.into(),
+            decoder: decoder.into(),
+            tokenizer: Arc::new(tokenizer),
             variant,
             encoder_input_names,
             decoder_input_names,
@@ -95,7 +152,7 @@ impl MoonshineModel {
 
     /// Transcribe with model-specific parameters.
     pub fn transcribe_with(
-        &mut self,
+        &self,
         samples: &[f32],
         params: &MoonshineParams,
     ) -> Result<TranscriptionResult, TranscribeError> {
@@ -104,13 +161,20 @@ impl MoonshineModel {
             (audio_duration_sec * self.variant.token_rate() as f32).ceil() as usize
         });
 
-        self.infer(samples, max_length)
+        self.infer(
+            samples,
+            max_length,
+            params.cancel.as_ref(),
+            params.metrics.as_ref(),
+        )
     }
 
     fn infer(
-        &mut self,
+        &self,
         samples: &[f32],
         max_length: usize,
+        cancel: Option<&CancellationToken>,
+        metrics: Option<&MetricsSink>,
     ) -> Result<TranscriptionResult, TranscribeError> {
         log::debug!(
             "Transcribing {} samples ({:.2}s), max_length={}",
@@ -119,8 +183,12 @@ impl MoonshineModel {
             max_length
         );
 
-        let tokens = self.generate(samples, max_length)?;
+        let mut rec = Recorder::new(metrics, CAPABILITIES.engine_id, samples.len(), SAMPLE_RATE);
+        let tokens = self.generate(samples, max_length, cancel, &mut rec)?;
         let text = self.decode_tokens(&tokens)?;
+        // Don't count the decoder start token.
+        rec.add_tokens(tokens.len().saturating_sub(1));
+        rec.finish();
 
         Ok(TranscriptionResult {
             text,
@@ -128,8 +196,9 @@ impl MoonshineModel {
         })
     }
 
-    fn encode(&mut self, audio: &Array2<f32>) -> Result<ArrayD<f32>, TranscribeError> {
+    fn encode(&self, audio: &Array2<f32>) -> Result<ArrayD<f32>, TranscribeError> {
         let audio_dyn = audio.clone().into_dyn();
+        let mut encoder = self.encoder.lock();
 
         let outputs = if self
             .encoder_input_names
@@ -143,13 +212,13 @@ impl MoonshineModel {
                 "input_values" => t_input_values,
                 "attention_mask" => t_attention_mask,
             ];
-            self.encoder.run(inputs)?
+            encoder.run(inputs)?
         } else {
             let t_input_values = TensorRef::from_array_view(audio_dyn.view())?;
             let inputs = inputs![
                 "input_values" => t_input_values,
             ];
-            self.encoder.run(inputs)?
+            encoder.run(inputs)?
         };
 
         let hidden_state = outputs
@@ -163,9 +232,11 @@ impl MoonshineModel {
     }
 
     fn generate(
-        &mut self,
+        &self,
         samples: &[f32],
         max_length: usize,
+        cancel: Option<&CancellationToken>,
+        rec: &mut Recorder,
     ) -> Result<Vec<i64>, TranscribeError> {
         let audio_duration = samples.len() as f32 / SAMPLE_RATE as f32;
         if audio_duration < 0.1 || audio_duration > 64.0 {
@@ -178,7 +249,7 @@ impl MoonshineModel {
         let audio = Array2::from_shape_vec((1, samples.len()), samples.to_vec())?;
         let audio_attention_mask = Array2::<i64>::ones((1, samples.len()));
 
-        let encoder_hidden_states = self.encode(&audio)?;
+        let encoder_hidden_states = rec.time("encoder", || self.encode(&audio))?;
 
         let mut greedy = GreedyDecoder::new(EOS_TOKEN_ID);
         let mut cache = KVCache::new(&self.variant);
@@ -186,6 +257,7 @@ impl MoonshineModel {
         let mut input_ids = Array2::from_shape_vec((1, 1), vec![DECODER_START_TOKEN_ID])?;
 
         for i in 0..max_length {
+            cancel::check(cancel)?;
             let use_cache_branch = i > 0;
 
             let input_ids_dyn = input_ids.clone().into_dyn();
@@ -223,7 +295,9 @@ impl MoonshineModel {
                 ort_inputs.push((name.into(), ort::value::Value::from_array(arr)?.into_dyn()));
             }
 
-            let outputs = self.decoder.run(ort_inputs)?;
+            let mut decoder = self.decoder.lock();
+
+            let outputs = rec.time("decoder", || decoder.run(ort_inputs))?;
 
             let logits = outputs
                 .get("logits")
@@ -262,13 +336,18 @@ impl SpeechModel for MoonshineModel {
     fn transcribe_raw(
         &mut self,
         samples: &[f32],
-        _options: &TranscribeOptions,
+        options: &TranscribeOptions,
     ) -> Result<TranscriptionResult, TranscribeError> {
         let max_length = {
             let audio_duration_sec = samples.len() as f32 / SAMPLE_RATE as f32;
             (audio_duration_sec * self.variant.token_rate() as f32).ceil() as usize
         };
-        self.infer(samples, max_length)
+        self.infer(
+            samples,
+            max_length,
+            options.cancel.as_ref(),
+            options.metrics.as_ref(),
+        )
     }
 }
 
@@ -352,16 +431,14 @@ struct MoonshineTokenizer {
 }
 
 impl MoonshineTokenizer {
-    fn new(model_dir: &Path) -> Result<Self, TranscribeError> {
-        let tokenizer_path = model_dir.join("tokenizer.json");
-
-        if !tokenizer_path.exists() {
-            return Err(TranscribeError::ModelNotFound(tokenizer_path));
+    fn new(source: &ModelSource) -> Result<Self, TranscribeError> {
+        if !source.exists("tokenizer.json") {
+            return Err(TranscribeError::ModelNotFound(
+                source.path("tokenizer.json"),
+            ));
         }
 
-        let file = File::open(&tokenizer_path)?;
-        let reader = BufReader::new(file);
-        let json: serde_json::Value = serde_json::from_reader(reader)?;
+        let json: serde_json::Value = serde_json::from_slice(&source.read("tokenizer.json")?)?;
 
         let mut vocab = HashMap::new();
         if let Some(model) = json.get("model") {
@@ -2,21 +2,24 @@ use ndarray::{ArrayD, ArrayViewD, IxDyn};
 use ort::inputs;
 use ort::session::Session;
 use ort::value::TensorRef;
-use std::fs;
-use std::fs::File;
-use std::io::Read;
 use std::path::Path;
 
 use crate::decode::GreedyDecoder;
-use crate::onnx::session;
-use crate::onnx::Quantization;
+use crate::onnx::{
+    quantization_candidates, quantization_suffix, ModelFiles, ModelSource, Quantization,
+    SessionConfig,
+};
+use crate::streaming::driver::{StreamDriver, UtteranceDecoder};
+use crate::streaming::{no_active_stream, StreamEvent, StreamOptions, StreamingSpeechModel};
 use crate::{
-    ModelCapabilities, SpeechModel, TranscribeError, TranscribeOptions, TranscriptionResult,
+    cancel, CancellationToken, ModelCapabilities, SpeechModel, TranscribeError, TranscribeOptions,
+    TranscriptionResult,
 };
 
 use super::SAMPLE_RATE;
 
 const CHUNK_SIZE: usize = 1280;
+const MAX_TOKENS_PER_SECOND: f32 = 6.5;
 
 const STREAMING_CAPABILITIES: ModelCapabilities = ModelCapabilities {
     name: "Moonshine Streaming",
@@ -24,6 +27,7 @@ const STREAMING_CAPABILITIES: ModelCapabilities = ModelCapabilities {
     sample_rate: 16000,
     languages: &["en"],
     supports_timestamps: false,
+    supports_word_timestamps: false,
     supports_translation: false,
     supports_streaming: true,
 };
@@ -35,6 +39,8 @@ pub struct MoonshineStreamingParams {
     pub language: Option<String>,
     /// Maximum number of tokens to generate.
     pub max_length: Option<usize>,
+    /// Checked between audio chunks and before each decoder step.
+    pub cancel: Option<CancellationToken>,
 }
 
 /// Streaming model configuration parsed from `streaming_config.json`.
@@ -57,13 +63,14 @@ pub struct StreamingConfig {
 }
 
 impl StreamingConfig {
-    fn load(model_dir: &Path) -> Result<Self, TranscribeError> {
-        let config_path = model_dir.join("streaming_config.json");
-        if !config_path.exists() {
-            return Err(TranscribeError::ModelNotFound(config_path));
+    fn load(source: &ModelSource) -> Result<Self, TranscribeError> {
+        if !source.exists("streaming_config.json") {
+            return Err(TranscribeError::ModelNotFound(
+                source.path("streaming_config.json"),
+            ));
         }
 
-        let contents = fs::read_to_string(&config_path)?;
+        let contents = source.read_to_string("streaming_config.json")?;
         let json: serde_json::Value = serde_json::from_str(&contents)?;
 
         let get_usize =
@@ -191,16 +198,12 @@ struct BinTokenizer {
 }
 
 impl BinTokenizer {
-    fn new(path: &Path) -> Result<Self, TranscribeError> {
-        let tokenizer_path = path.join("tokenizer.bin");
-
-        if !tokenizer_path.exists() {
-            return Err(TranscribeError::ModelNotFound(tokenizer_path));
+    fn new(source: &ModelSource) -> Result<Self, TranscribeError> {
+        if !source.exists("tokenizer.bin") {
+            return Err(TranscribeError::ModelNotFound(source.path("tokenizer.bin")));
         }
 
-        let mut file = File::open(&tokenizer_path)?;
-        let mut data = Vec::new();
-        file.read_to_end(&mut data)?;
+        let data = source.read("tokenizer.bin")?;
 
         let mut tokens_to_bytes = Vec::new();
         let mut offset = 0;
@@ -270,6 +273,10 @@ impl BinTokenizer {
 }
 
 /// Streaming Moonshine model with 5 ONNX sessions.
+///
+/// Implements [`StreamingSpeechModel`] natively: pushed audio runs through
+/// the incremental frontend and encoder as it arrives, and each hypothesis
+/// update only re-runs the decoder over the accumulated encoder memory.
 pub struct StreamingModel {
     frontend: Session,
     encoder: Session,
@@ -278,6 +285,24 @@ pub struct StreamingModel {
     decoder_kv: Session,
     tokenizer: BinTokenizer,
     config: StreamingConfig,
+    stream: Option<ActiveStream>,
+}
+
+/// State of the stream driven through [`StreamingSpeechModel`].
+struct ActiveStream {
+    driver: StreamDriver,
+    state: StreamingState,
+    utterance_samples: usize,
+    cancel: Option<CancellationToken>,
+}
+
+/// Borrows the model sessions and the stream's encoder state for one
+/// [`StreamDriver`] call.
+struct UtteranceContext<'a> {
+    model: &'a mut StreamingModel,
+    state: &'a mut StreamingState,
+    utterance_samples: &'a mut usize,
+    cancel: Option<&'a CancellationToken>,
 }
 
 impl StreamingModel {
@@ -286,41 +311,84 @@ impl StreamingModel {
         num_threads: usize,
         quantization: &Quantization,
     ) -> Result<Self, TranscribeError> {
-        let config = StreamingConfig::load(model_dir)?;
+        let config = SessionConfig::default().with_intra_threads(num_threads);
+        Self::load_with_config(model_dir, quantization, &config)
+    }
+
+    /// Load with explicit ONNX session settings (execution providers,
+    /// threads, ...).
+    pub fn load_with_config(
+        model_dir: &Path,
+        quantization: &Quantization,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_from(ModelSource::Dir(model_dir), quantization, config)
+    }
+
+    /// Load from in-memory files named as on disk (`streaming_config.json`,
+    /// `tokenizer.bin`, and `frontend`, `encoder`, `adapter`, `cross_kv`,
+    /// `decoder_kv` as `.ort` or `.onnx`).
+    pub fn load_from_memory(
+        files: &ModelFiles,
+        num_threads: usize,
+        quantization: &Quantization,
+    ) -> Result<Self, TranscribeError> {
+        let config = SessionConfig::default().with_intra_threads(num_threads);
+        Self::load_from_memory_with_config(files, quantization, &config)
+    }
+
+    /// [`load_from_memory`](Self::load_from_memory) with explicit ONNX
+    /// session settings.
+    pub fn load_from_memory_with_config(
+        files: &ModelFiles,
+        quantization: &Quantization,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_from(ModelSource::Memory(files), quantization, config)
+    }
+
+    fn load_from(
+        source: ModelSource,
+        quantization: &Quantization,
+        session_config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        let config = StreamingConfig::load(&source)?;
 
         let load = |name: &str| -> Result<Session, TranscribeError> {
-            // Try quantized variants first if requested, preferring .ort format
-            let suffix = match quantization {
-                Quantization::FP32 => None,
-                Quantization::FP16 => Some("fp16"),
-                Quantization::Int8 => Some("int8"),
-                Quantization::Int4 => Some("int4"),
+            let files = |quantization: &Quantization| match quantization_suffix(quantization) {
+                Some(suffix) => vec![
+                    format!("{}.{}.ort", name, suffix),
+                    format!("{}.{}.onnx", name, suffix),
+                ],
+                None => vec![format!("{}.ort", name), format!("{}.onnx", name)],
             };
-
-            let candidates: Vec<std::path::PathBuf> = if let Some(suffix) = suffix {
-                vec![
-                    model_dir.join(format!("{}.{}.ort", name, suffix)),
-                    model_dir.join(format!("{}.ort", name)),
-                    model_dir.join(format!("{}.{}.onnx", name, suffix)),
-                    model_dir.join(format!("{}.onnx", name)),
-                ]
-            } else {
-                vec![
-                    model_dir.join(format!("{}.ort", name)),
-                    model_dir.join(format!("{}.onnx", name)),
-                ]
+            let candidates: Vec<String> = match (quantization, quantization_suffix(quantization)) {
+                (Quantization::Auto, _) => quantization_candidates(quantization, session_config)
+                    .iter()
+                    .flat_map(files)
+                    .collect(),
+                (_, Some(_)) if session_config.strict_quantization => files(quantization),
+                // Try the quantized variant first, preferring .ort format
+                (_, Some(suffix)) => vec![
+                    format!("{}.{}.ort", name, suffix),
+                    format!("{}.ort", name),
+                    format!("{}.{}.onnx", name, suffix),
+                    format!("{}.onnx", name),
+                ],
+                (_, None) => files(quantization),
             };
 
-            for path in &candidates {
-                if path.exists() {
-                    log::info!("Loading streaming model component: {}", path.display());
-                    return Ok(session::create_session_with_threads(path, num_threads)?);
+            for file in &candidates {
+                if source.exists(file) {
+                    log::info!(
+                        "Loading streaming model component: {}",
+                        source.path(file).display()
+                    );
+                    return source.session(file, session_config);
                 }
             }
 
-            Err(TranscribeError::ModelNotFound(
-                candidates.into_iter().next().unwrap(),
-            ))
+            Err(TranscribeError::ModelNotFound(source.path(&candidates[0])))
         };
 
         let frontend = load("frontend")?;
@@ -329,9 +397,9 @@ impl StreamingModel {
         let cross_kv = load("cross_kv")?;
         let decoder_kv = load("decoder_kv")?;
 
-        let tokenizer = BinTokenizer::new(model_dir)?;
+        let tokenizer = BinTokenizer::new(&source)?;
 
-        log::info!("Loaded streaming model from {:?}", model_dir);
+        log::info!("Loaded streaming model from {:?}", source.path(""));
 
         Ok(Self {
             frontend,
@@ -341,6 +409,7 @@ impl StreamingModel {
             decoder_kv,
             tokenizer,
             config,
+            stream: None,
         })
     }
 
@@ -350,7 +419,12 @@ impl StreamingModel {
         samples: &[f32],
         params: &MoonshineStreamingParams,
     ) -> Result<TranscriptionResult, TranscribeError> {
-        let tokens = self.generate(samples, 6.5, params.max_length)?;
+        let tokens = self.generate(
+            samples,
+            MAX_TOKENS_PER_SECOND,
+            params.max_length,
+            params.cancel.as_ref(),
+        )?;
         let text = self.tokenizer.decode(&tokens)?;
 
         Ok(TranscriptionResult {
@@ -738,36 +812,59 @@ impl StreamingModel {
         samples: &[f32],
         max_tokens_per_second: f32,
         max_tokens_override: Option<usize>,
+        cancel: Option<&CancellationToken>,
     ) -> Result<Vec<i64>, TranscribeError> {
         let mut state = self.create_state();
 
         for chunk in samples.chunks(CHUNK_SIZE) {
+            cancel::check(cancel)?;
             self.process_audio_chunk(&mut state, chunk)?;
         }
 
         self.encode_streaming(&mut state, true)?;
 
+        let max_tokens = match max_tokens_override {
+            Some(m) => m.min(self.config.max_seq_len),
+            None => self.max_tokens_for(samples.len(), max_tokens_per_second),
+        };
+
+        self.decode_memory(&mut state, max_tokens, cancel)
+    }
+
+    /// Token budget for `num_samples` of audio, capped at `max_seq_len`.
+    fn max_tokens_for(&self, num_samples: usize, max_tokens_per_second: f32) -> usize {
+        let duration_sec = num_samples as f32 / SAMPLE_RATE as f32;
+        ((duration_sec * max_tokens_per_second).ceil() as usize).min(self.config.max_seq_len)
+    }
+
+    /// Greedy-decode the encoder memory accumulated in `state`.
+    ///
+    /// Starts from BOS with an empty self-attention cache, so it can be
+    /// called repeatedly as memory grows to re-decode the hypothesis.
+    fn decode_memory(
+        &mut self,
+        state: &mut StreamingState,
+        max_tokens: usize,
+        cancel: Option<&CancellationToken>,
+    ) -> Result<Vec<i64>, TranscribeError> {
         if state.memory_len == 0 {
             return Ok(Vec::new());
         }
 
-        self.compute_cross_kv(&mut state)?;
-
-        let max_tokens = match max_tokens_override {
-            Some(m) => m.min(self.config.max_seq_len),
-            None => {
-                let duration_sec = samples.len() as f32 / SAMPLE_RATE as f32;
-                ((duration_sec * max_tokens_per_second).ceil() as usize)
-                    .min(self.config.max_seq_len)
-            }
-        };
+        if !state.cross_kv_valid {
+            self.compute_cross_kv(state)?;
+        }
+        state.k_self.clear();
+        state.v_self.clear();
+        state.cache_seq_len = 0;
 
         let mut greedy = GreedyDecoder::new(self.config.eos_id);
         let mut tokens: Vec<i64> = Vec::new();
         let mut current_token = self.config.bos_id;
 
         for _step in 0..max_tokens {
-            let logits = self.decode_step_logits(&mut state, current_token)?;
+            cancel::check(cancel)?;
+            let logits = self.decode_step_logits(state, current_token)?;
 
             let next_token = match greedy.next_token(&logits) {
                 Some(t) => t,
@@ -782,6 +879,96 @@ impl StreamingModel {
     }
 }
 
+impl UtteranceDecoder for UtteranceContext<'_> {
+    fn append(&mut self, samples: &[f32]) -> Result<(), TranscribeError> {
+        for chunk in samples.chunks(CHUNK_SIZE) {
+            self.model.process_audio_chunk(self.state, chunk)?;
+        }
+        self.model.encode_streaming(self.state, false)?;
+        *self.utterance_samples += samples.len();
+        Ok(())
+    }
+
+    fn hypothesis(&mut self) -> Result<String, TranscribeError> {
+        let max_tokens = self
+            .model
+            .max_tokens_for(*self.utterance_samples, MAX_TOKENS_PER_SECOND);
+        let tokens = self
+            .model
+            .decode_memory(self.state, max_tokens, self.cancel)?;
+        self.model.tokenizer.decode(&tokens)
+    }
+
+    fn finish_utterance(&mut self) -> Result<TranscriptionResult, TranscribeError> {
+        self.model.encode_streaming(self.state, true)?;
+        let max_tokens = self
+            .model
+            .max_tokens_for(*self.utterance_samples, MAX_TOKENS_PER_SECOND);
+        let tokens = self
+            .model
+            .decode_memory(self.state, max_tokens, self.cancel)?;
+        let text = self.model.tokenizer.decode(&tokens)?;
+
+        self.state.reset(&self.model.config);
+        *self.utterance_samples = 0;
+
+        Ok(TranscriptionResult {
+            text,
+            segments: None,
+        })
+    }
+}
+
+impl StreamingModel {
+    /// Run `f` against the active stream, restoring it afterwards even if
+    /// `f` fails so the stream stays usable.
+    fn with_stream<T>(
+        &mut self,
+        f: impl FnOnce(&mut StreamDriver, &mut UtteranceContext<'_>) -> Result<T, TranscribeError>,
+    ) -> Result<T, TranscribeError> {
+        let mut stream = self.stream.take().ok_or_else(no_active_stream)?;
+        let result = {
+            let ActiveStream {
+                driver,
+                state,
+                utterance_samples,
+                cancel,
+            } = &mut stream;
+            let mut ctx = UtteranceContext {
+                model: self,
+                state,
+                utterance_samples,
+                cancel: cancel.as_ref(),
+            };
+            f(driver, &mut ctx)
+        };
+        self.stream = Some(stream);
+        result
+    }
+}
+
+impl StreamingSpeechModel for StreamingModel {
+    fn create_stream(&mut self, options: &StreamOptions) -> Result<(), TranscribeError> {
+        self.stream = Some(ActiveStream {
+            driver: StreamDriver::new(options),
+            state: self.create_state(),
+            utterance_samples: 0,
+            cancel: options.transcribe.cancel.clone(),
+        });
+        Ok(())
+    }
+
+    fn push_audio(&mut self, samples: &[f32]) -> Result<Vec<StreamEvent>, TranscribeError> {
+        self.with_stream(|driver, ctx| driver.push(ctx, samples))
+    }
+
+    fn finalize(&mut self) -> Result<TranscriptionResult, TranscribeError> {
+        let result = self.with_stream(|driver, ctx| driver.finalize(ctx));
+        self.stream = None;
+        result
+    }
+}
+
 impl SpeechModel for StreamingModel {
     fn capabilities(&self) -> ModelCapabilities {
         STREAMING_CAPABILITIES
@@ -790,9 +977,14 @@ impl SpeechModel for StreamingModel {
     fn transcribe_raw(
         &mut self,
         samples: &[f32],
-        _options: &TranscribeOptions,
+        options: &TranscribeOptions,
     ) -> Result<TranscriptionResult, TranscribeError> {
-        let tokens = self.generate(samples, 6.5, None)?;
+        let tokens = self.generate(
+            samples,
+            MAX_TOKENS_PER_SECOND,
+            None,
+            options.cancel.as_ref(),
+        )?;
         let text = self.tokenizer.decode(&tokens)?;
 
         Ok(TranscriptionResult {
@@ -1,27 +1,21 @@
 use ndarray::{Array, Array1, Array2, Array3, ArrayD, ArrayViewD, IxDyn};
 use once_cell::sync::Lazy;
 use ort::inputs;
-use ort::session::Session;
 use ort::value::TensorRef;
 use regex::Regex;
 use std::path::Path;
+use std::sync::Arc;
 
-use super::session;
-use super::Quantization;
-use crate::decode::tokens::load_vocab;
+use super::session::SharedSession;
+use super::{ModelFiles, ModelSource, Quantization, SessionConfig};
+use crate::decode::tokens::parse_vocab;
+use crate::metrics::{MetricsSink, Recorder};
 use crate::{
-    ModelCapabilities, SpeechModel, TranscribeError, TranscribeOptions, TranscriptionResult,
-    TranscriptionSegment,
+    cancel, CancellationToken, ModelCapabilities, SpeechModel, TranscribeError, TranscribeOptions,
+    TranscriptionResult, TranscriptionSegment,
 };
 
-/// Timestamp granularity for Parakeet output.
-#[derive(Debug, Clone, Default, PartialEq)]
-pub enum TimestampGranularity {
-    #[default]
-    Token,
-    Word,
-    Segment,
-}
+pub use crate::TimestampGranularity;
 
 /// Per-model inference parameters for Parakeet.
 #[derive(Debug, Clone, Default)]
@@ -30,6 +24,10 @@ pub struct ParakeetParams {
     pub language: Option<String>,
     /// Timestamp granularity for output segments.
     pub timestamp_granularity: Option<TimestampGranularity>,
+    /// Checked before each decoder step.
+    pub cancel: Option<CancellationToken>,
+    /// Receives stage timings of the call; see [`crate::metrics`].
+    pub metrics: Option<MetricsSink>,
 }
 
 const CAPABILITIES: ModelCapabilities = ModelCapabilities {
@@ -38,6 +36,7 @@ const CAPABILITIES: ModelCapabilities = ModelCapabilities {
     sample_rate: 16000,
     languages: &["en"],
     supports_timestamps: true,
+    supports_word_timestamps: true,
     supports_translation: false,
     supports_streaming: false,
 };
@@ -82,28 +81,67 @@ struct TimestampedResult {
     tokens: Vec<String>,
 }
 
+/// NVIDIA Parakeet transducer model.
+///
+/// Clones share the loaded sessions and vocabulary, so one load can serve
+/// several threads; each call keeps its decoder state to itself.
+#[derive(Clone)]
 pub struct ParakeetModel {
-    encoder: Session,
-    decoder_joint: Session,
-    preprocessor: Session,
-    vocab: Vec<String>,
+    encoder: SharedSession,
+    decoder_joint: SharedSession,
+    preprocessor: SharedSession,
+    vocab: Arc<[String]>,
     blank_idx: i32,
     vocab_size: usize,
 }
 
 impl ParakeetModel {
     pub fn load(model_dir: &Path, quantization: &Quantization) -> Result<Self, TranscribeError> {
-        let encoder_path = session::resolve_model_path(model_dir, "encoder-model", quantization);
-        let decoder_path =
-            session::resolve_model_path(model_dir, "decoder_joint-model", quantization);
-        let preprocessor_path = model_dir.join("nemo128.onnx");
+        Self::load_with_config(model_dir, quantization, &SessionConfig::default())
+    }
+
+    /// Load with explicit ONNX session settings (execution providers,
+    /// threads, ...).
+    pub fn load_with_config(
+        model_dir: &Path,
+        quantization: &Quantization,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_from(ModelSource::Dir(model_dir), quantization, config)
+    }
+
+    /// Load from in-memory files named as on disk (`nemo128.onnx`,
+    /// `encoder-model[.int8].onnx`, `decoder_joint-model[.int8].onnx`,
+    /// `vocab.txt`).
+    pub fn load_from_memory(
+        files: &ModelFiles,
+        quantization: &Quantization,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_from_memory_with_config(files, quantization, &SessionConfig::default())
+    }
 
-        let encoder = session::create_session(&encoder_path)?;
-        let decoder_joint = session::create_session(&decoder_path)?;
-        let preprocessor = session::create_session(&preprocessor_path)?;
+    /// [`load_from_memory`](Self::load_from_memory) with explicit ONNX
+    /// session settings.
+    pub fn load_from_memory_with_config(
+        files: &ModelFiles,
+        quantization: &Quantization,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_from(ModelSource::Memory(files), quantization, config)
+    }
 
-        let vocab_path = model_dir.join("vocab.txt");
-        let (vocab, blank_idx) = load_vocab(&vocab_path)?;
+    fn load_from(
+        source: ModelSource,
+        quantization: &Quantization,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        let encoder_file = source.resolve("encoder-model", quantization, config)?;
+        let decoder_joint_file = source.resolve("decoder_joint-model", quantization, config)?;
+        let encoder = source.session(&encoder_file, config)?;
+        let decoder_joint = source.session(&decoder_joint_file, config)?;
+        let preprocessor = source.session("nemo128.onnx", config)?;
+
+        let (vocab, blank_idx) = parse_vocab(&source.read_to_string("vocab.txt")?);
         let blank_idx = blank_idx.ok_or_else(|| {
             std::io::Error::new(
                 std::io::ErrorKind::InvalidData,
@@ -119,10 +157,10 @@ impl ParakeetModel {
         );
 
         Ok(Self {
-            encoder,
-            decoder_joint,
-            preprocessor,
-            vocab,
+            encoder: encoder.into(),
+            decoder_joint: decoder_joint.into(),
+            preprocessor: preprocessor.into(),
+            vocab: vocab.into(),
             blank_idx,
             vocab_size,
         })
@@ -136,25 +174,41 @@ impl ParakeetModel {
     /// Applies leading silence padding (default 250 ms) and adjusts
     /// timestamps, matching the behaviour of [`SpeechModel::transcribe`].
     pub fn transcribe_with(
-        &mut self,
+        &self,
         samples: &[f32],
         params: &ParakeetParams,
     ) -> Result<TranscriptionResult, TranscribeError> {
         let granularity = params.timestamp_granularity.clone().unwrap_or_default();
         let lead_ms = Self::DEFAULT_LEADING_SILENCE_MS;
         let padded = crate::audio::prepend_silence(samples, lead_ms);
-        let mut result = self.infer(&padded, &granularity)?;
+        let mut result = self.infer(
+            &padded,
+            &granularity,
+            params.cancel.as_ref(),
+            params.metrics.as_ref(),
+        )?;
         result.offset_timestamps(-(lead_ms as f32 / 1000.0));
         Ok(result)
     }
 
     fn infer(
-        &mut self,
+        &self,
         samples: &[f32],
         granularity: &TimestampGranularity,
+        cancel: Option<&CancellationToken>,
+        metrics: Option<&MetricsSink>,
     ) -> Result<TranscriptionResult, TranscribeError> {
-        let timestamped_result = self.transcribe_samples_internal(samples.to_vec())?;
+        let mut rec = Recorder::new(
+            metrics,
+            CAPABILITIES.engine_id,
+            samples.len(),
+            CAPABILITIES.sample_rate,
+        );
+        let timestamped_result =
+            self.transcribe_samples_internal(samples.to_vec(), cancel, &mut rec)?;
         let segments = convert_timestamps(&timestamped_result, granularity);
+        rec.add_tokens(timestamped_result.tokens.len());
+        rec.finish();
 
         Ok(TranscriptionResult {
             text: timestamped_result.text,
@@ -163,7 +217,7 @@ impl ParakeetModel {
     }
 
     fn preprocess(
-        &mut self,
+        &self,
         waveforms: &ArrayViewD<f32>,
         waveforms_lens: &ArrayViewD<i64>,
     ) -> Result<(ArrayD<f32>, ArrayD<i64>), TranscribeError> {
@@ -173,7 +227,8 @@ impl ParakeetModel {
             "waveforms" => t_waveforms,
             "waveforms_lens" => t_waveforms_lens,
         ];
-        let outputs = self.preprocessor.run(inputs)?;
+        let mut preprocessor = self.preprocessor.lock();
+        let outputs = preprocessor.run(inputs)?;
 
         let features = outputs
             .get("features")
@@ -188,7 +243,7 @@ impl ParakeetModel {
     }
 
     fn encode(
-        &mut self,
+        &self,
         audio_signal: &ArrayViewD<f32>,
         length: &ArrayViewD<i64>,
     ) -> Result<(ArrayD<f32>, ArrayD<i64>), TranscribeError> {
@@ -198,7 +253,8 @@ impl ParakeetModel {
             "audio_signal" => t_audio_signal,
             "length" => t_length,
         ];
-        let outputs = self.encoder.run(inputs)?;
+        let mut encoder = self.encoder.lock();
+        let outputs = encoder.run(inputs)?;
 
         let encoder_output = outputs
             .get("outputs")
@@ -217,7 +273,8 @@ impl ParakeetModel {
     }
 
     fn create_decoder_state(&self) -> Result<DecoderState, TranscribeError> {
-        let inputs = self.decoder_joint.inputs();
+        let decoder_joint = self.decoder_joint.lock();
+        let inputs = decoder_joint.inputs();
 
         let state1_shape = inputs
             .iter()
@@ -251,7 +308,7 @@ impl ParakeetModel {
     }
 
     fn decode_step(
-        &mut self,
+        &self,
         prev_tokens: &[i32],
         prev_state: &DecoderState,
         encoder_out: &ArrayViewD<f32>,
@@ -278,7 +335,9 @@ impl ParakeetModel {
             "input_states_2" => t_input_states_2,
         ];
 
-        let outputs = self.decoder_joint.run(inputs)?;
+        let mut decoder_joint = self.decoder_joint.lock();
+
+        let outputs = decoder_joint.run(inputs)?;
 
         let logits = outputs
             .get("outputs")
@@ -306,18 +365,22 @@ impl ParakeetModel {
     }
 
     fn recognize_batch(
-        &mut self,
+        &self,
         waveforms: &ArrayViewD<f32>,
         waveforms_len: &ArrayViewD<i64>,
+        cancel: Option<&CancellationToken>,
+        rec: &mut Recorder,
     ) -> Result<Vec<TimestampedResult>, TranscribeError> {
-        let (features, features_lens) = self.preprocess(waveforms, waveforms_len)?;
-        let (encoder_out, encoder_out_lens) =
-            self.encode(&features.view(), &features_lens.view())?;
+        let (features, features_lens) =
+            rec.time("preprocessor", || self.preprocess(waveforms, waveforms_len))?;
+        let (encoder_out, encoder_out_lens) = rec.time("encoder", || {
+            self.encode(&features.view(), &features_lens.view())
+        })?;
 
         let mut results = Vec::new();
         for (encodings, &encodings_len) in encoder_out.outer_iter().zip(encoder_out_lens.iter()) {
             let (tokens, timestamps) =
-                self.decode_sequence(&encodings.view(), encodings_len as usize)?;
+                self.decode_sequence(&encodings.view(), encodings_len as usize, cancel, rec)?;
             let result = self.decode_tokens(tokens, timestamps);
             results.push(result);
         }
@@ -326,9 +389,11 @@ impl ParakeetModel {
     }
 
     fn decode_sequence(
-        &mut self,
+        &self,
         encodings: &ArrayViewD<f32>,
         encodings_len: usize,
+        cancel: Option<&CancellationToken>,
+        rec: &mut Recorder,
     ) -> Result<(Vec<i32>, Vec<usize>), TranscribeError> {
         let mut prev_state = self.create_decoder_state()?;
         let mut tokens = Vec::new();
@@ -338,10 +403,12 @@ impl ParakeetModel {
         let mut emitted_tokens = 0;
 
         while t < encodings_len {
+            cancel::check(cancel)?;
             let encoder_step = encodings.slice(ndarray::s![t, ..]);
             let encoder_step_dyn = encoder_step.to_owned().into_dyn();
-            let (probs, new_state) =
-                self.decode_step(&tokens, &prev_state, &encoder_step_dyn.view())?;
+            let (probs, new_state) = rec.time("decoder", || {
+                self.decode_step(&tokens, &prev_state, &encoder_step_dyn.view())
+            })?;
 
             let vocab_logits_slice = probs
                 .as_slice()
@@ -415,8 +482,10 @@ impl ParakeetModel {
     }
 
     fn transcribe_samples_internal(
-        &mut self,
+        &self,
         samples: Vec<f32>,
+        cancel: Option<&CancellationToken>,
+        rec: &mut Recorder,
     ) -> Result<TimestampedResult, TranscribeError> {
         let batch_size = 1;
         let samples_len = samples.len();
@@ -424,7 +493,8 @@ impl ParakeetModel {
         let waveforms = Array2::from_shape_vec((batch_size, samples_len), samples)?.into_dyn();
         let waveforms_lens = Array1::from_vec(vec![samples_len as i64]).into_dyn();
 
-        let results = self.recognize_batch(&waveforms.view(), &waveforms_lens.view())?;
+        let results =
+            self.recognize_batch(&waveforms.view(), &waveforms_lens.view(), cancel, rec)?;
 
         results.into_iter().next().ok_or_else(|| {
             TranscribeError::Inference("No transcription result returned".to_string())
@@ -444,9 +514,14 @@ impl SpeechModel for ParakeetModel {
     fn transcribe_raw(
         &mut self,
         samples: &[f32],
-        _options: &TranscribeOptions,
+        options: &TranscribeOptions,
     ) -> Result<TranscriptionResult, TranscribeError> {
-        self.infer(samples, &TimestampGranularity::default())
+        self.infer(
+            samples,
+            &options.timestamp_granularity.clone().unwrap_or_default(),
+            options.cancel.as_ref(),
+            options.metrics.as_ref(),
+        )
     }
 }
 
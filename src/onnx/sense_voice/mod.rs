@@ -4,11 +4,13 @@ use ort::session::Session;
 use ort::value::TensorRef;
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
 
-use super::session;
-use super::Quantization;
+use super::session::{self, SharedSession};
+use super::{ModelFiles, ModelSource, Quantization, SessionConfig};
 use crate::decode::{ctc_greedy_decode, CtcDecoderResult, SymbolTable};
 use crate::features::{apply_cmvn, apply_lfr, compute_mel, MelConfig, WindowType};
+use crate::metrics::{MetricsSink, Recorder};
 use crate::TranscribeError;
 use crate::{
     ModelCapabilities, SpeechModel, TranscribeOptions, TranscriptionResult, TranscriptionSegment,
@@ -20,6 +22,7 @@ const CAPABILITIES: ModelCapabilities = ModelCapabilities {
     sample_rate: 16000,
     languages: &["zh", "en", "ja", "ko", "yue"],
     supports_timestamps: true,
+    supports_word_timestamps: false,
     supports_translation: false,
     supports_streaming: false,
 };
@@ -31,6 +34,8 @@ pub struct SenseVoiceParams {
     pub language: Option<String>,
     /// Whether to apply inverse text normalization. Defaults to true.
     pub use_itn: Option<bool>,
+    /// Receives stage timings of the call; see [`crate::metrics`].
+    pub metrics: Option<MetricsSink>,
 }
 
 // ---- Model ----
@@ -49,27 +54,71 @@ struct SenseVoiceMetadata {
     is_funasr_nano: bool,
 }
 
+/// SenseVoice model.
+///
+/// Clones share the loaded session, metadata and symbol table, so one load
+/// can serve several threads.
+#[derive(Clone)]
 pub struct SenseVoiceModel {
-    session: Session,
-    metadata: SenseVoiceMetadata,
-    symbol_table: SymbolTable,
+    session: SharedSession,
+    metadata: Arc<SenseVoiceMetadata>,
+    symbol_table: Arc<SymbolTable>,
     input_names: Vec<String>,
 }
 
 impl SenseVoiceModel {
     pub fn load(model_dir: &Path, quantization: &Quantization) -> Result<Self, TranscribeError> {
-        let model_path = session::resolve_model_path(model_dir, "model", quantization);
-        let tokens_path = model_dir.join("tokens.txt");
+        Self::load_with_config(model_dir, quantization, &SessionConfig::default())
+    }
+
+    /// Load with explicit ONNX session settings (execution providers,
+    /// threads, ...).
+    pub fn load_with_config(
+        model_dir: &Path,
+        quantization: &Quantization,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_from(ModelSource::Dir(model_dir), quantization, config)
+    }
 
-        if !model_path.exists() {
-            return Err(TranscribeError::ModelNotFound(model_path));
+    /// Load from in-memory files named as on disk (`model[.int8].onnx`,
+    /// `tokens.txt`).
+    pub fn load_from_memory(
+        files: &ModelFiles,
+        quantization: &Quantization,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_from_memory_with_config(files, quantization, &SessionConfig::default())
+    }
+
+    /// [`load_from_memory`](Self::load_from_memory) with explicit ONNX
+    /// session settings.
+    pub fn load_from_memory_with_config(
+        files: &ModelFiles,
+        quantization: &Quantization,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        Self::load_from(ModelSource::Memory(files), quantization, config)
+    }
+
+    fn load_from(
+        source: ModelSource,
+        quantization: &Quantization,
+        config: &SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        let model_file = source.resolve("model", quantization, config)?;
+
+        if !source.exists(&model_file) {
+            return Err(TranscribeError::ModelNotFound(source.path(&model_file)));
         }
-        if !tokens_path.exists() {
-            return Err(TranscribeError::ModelNotFound(tokens_path));
+        if !source.exists("tokens.txt") {
+            return Err(TranscribeError::ModelNotFound(source.path("tokens.txt")));
         }
 
-        log::info!("Loading SenseVoice model from {:?}...", model_path);
-        let session = session::create_session(&model_path)?;
+        log::info!(
+            "Loading SenseVoice model from {:?}...",
+            source.path(&model_file)
+        );
+        let session = source.session(&model_file, config)?;
 
         let input_names: Vec<String> = session
             .inputs()
@@ -87,16 +136,16 @@ impl SenseVoiceModel {
             metadata.is_funasr_nano,
         );
 
-        let mut symbol_table = SymbolTable::load(&tokens_path)?;
+        let mut symbol_table = SymbolTable::parse(&source.read_to_string("tokens.txt")?);
         if metadata.is_funasr_nano {
             log::info!("FunASR Nano model detected, applying base64 decode to tokens");
             symbol_table.apply_base64_decode();
         }
 
         Ok(Self {
-            session,
-            metadata,
-            symbol_table,
+            session: session.into(),
+            metadata: Arc::new(metadata),
+            symbol_table: Arc::new(symbol_table),
             input_names,
         })
     }
@@ -184,22 +233,29 @@ impl SenseVoiceModel {
 
     /// Transcribe with model-specific parameters.
     pub fn transcribe_with(
-        &mut self,
+        &self,
         samples: &[f32],
         params: &SenseVoiceParams,
     ) -> Result<TranscriptionResult, TranscribeError> {
         let language = params.language.as_deref().unwrap_or("auto");
         let use_itn = params.use_itn.unwrap_or(true);
-        self.infer(samples, language, use_itn)
+        self.infer(samples, language, use_itn, params.metrics.as_ref())
     }
 
     fn infer(
-        &mut self,
+        &self,
         samples: &[f32],
         language: &str,
         use_itn: bool,
+        metrics: Option<&MetricsSink>,
     ) -> Result<TranscriptionResult, TranscribeError> {
-        // Copy metadata values to avoid borrow conflicts with &mut self
+        let mut rec = Recorder::new(
+            metrics,
+            CAPABILITIES.engine_id,
+            samples.len(),
+            CAPABILITIES.sample_rate,
+        );
+
         let normalize_samples = self.metadata.normalize_samples;
         let lfr_window_size = self.metadata.lfr_window_size;
         let lfr_window_shift = self.metadata.lfr_window_shift;
@@ -222,7 +278,7 @@ impl SenseVoiceModel {
             snip_edges: true,
             normalize_samples,
         };
-        let features = compute_mel(samples, &mel_config);
+        let features = rec.time("features", || compute_mel(samples, &mel_config));
 
         log::debug!(
             "FBANK features: [{}, {}]",
@@ -231,7 +287,9 @@ impl SenseVoiceModel {
         );
 
         // 2. Apply LFR
-        let features = apply_lfr(&features, lfr_window_size, lfr_window_shift);
+        let features = rec.time("features", || {
+            apply_lfr(&features, lfr_window_size, lfr_window_shift)
+        });
         log::debug!("After LFR: [{}, {}]", features.nrows(), features.ncols());
 
         if features.nrows() == 0 {
@@ -244,17 +302,21 @@ impl SenseVoiceModel {
         // 3. Apply CMVN
         let mut features = features;
         if has_cmvn {
-            apply_cmvn(&mut features, &neg_mean, &inv_stddev);
+            rec.time("features", || {
+                apply_cmvn(&mut features, &neg_mean, &inv_stddev)
+            });
         }
 
         let num_feature_frames = features.nrows();
 
         // 4. Run ONNX forward pass
-        let logits = if is_funasr_nano {
-            self.forward_nano(&features.view())?
-        } else {
-            self.forward(&features.view(), language, use_itn)?
-        };
+        let logits = rec.time("encoder", || {
+            if is_funasr_nano {
+                self.forward_nano(&features.view())
+            } else {
+                self.forward(&features.view(), language, use_itn)
+            }
+        })?;
 
         log::debug!("Logits shape: {:?}", logits.shape());
 
@@ -266,15 +328,19 @@ impl SenseVoiceModel {
         };
         let logits_lengths = vec![num_frames];
         let logits_view = logits.view();
-        let decoder_results = ctc_greedy_decode(&logits_view, &logits_lengths, blank_id);
+        let decoder_results = rec.time("decoder", || {
+            ctc_greedy_decode(&logits_view, &logits_lengths, blank_id)
+        });
 
         // 6. Convert result
         let result = self.convert_result(&decoder_results[0]);
+        rec.add_tokens(decoder_results[0].tokens.len());
+        rec.finish();
         Ok(result)
     }
 
     fn forward(
-        &mut self,
+        &self,
         features: &ndarray::ArrayView2<f32>,
         language: &str,
         use_itn: bool,
@@ -323,7 +389,9 @@ impl SenseVoiceModel {
             self.input_names[3].as_str() => t_norm,
         ];
 
-        let outputs = self.session.run(inputs)?;
+        let mut session = self.session.lock();
+
+        let outputs = session.run(inputs)?;
         let logits = outputs[0].try_extract_array::<f32>()?;
         let logits_owned = logits.to_owned().into_dimensionality::<ndarray::Ix3>()?;
 
@@ -331,7 +399,7 @@ impl SenseVoiceModel {
     }
 
     fn forward_nano(
-        &mut self,
+        &self,
         features: &ndarray::ArrayView2<f32>,
     ) -> Result<ndarray::Array3<f32>, TranscribeError> {
         let feat_3d =
@@ -347,7 +415,9 @@ impl SenseVoiceModel {
             self.input_names[0].as_str() => t_feat,
         ];
 
-        let outputs = self.session.run(inputs)?;
+        let mut session = self.session.lock();
+
+        let outputs = session.run(inputs)?;
         let logits = outputs[0].try_extract_array::<f32>()?;
         let logits_owned = logits.to_owned().into_dimensionality::<ndarray::Ix3>()?;
 
@@ -429,6 +499,11 @@ impl SpeechModel for SenseVoiceModel {
         samples: &[f32],
         options: &TranscribeOptions,
     ) -> Result<TranscriptionResult, TranscribeError> {
-        self.infer(samples, options.language.as_deref().unwrap_or("auto"), true)
+        self.infer(
+            samples,
+            options.language.as_deref().unwrap_or("auto"),
+            true,
+            options.metrics.as_ref(),
+        )
     }
 }
@@ -14,15 +14,213 @@ use ort::ep::CUDA;
 #[cfg(feature = "ort-xnnpack")]
 use ort::ep::XNNPACK;
 
-use ort::session::builder::GraphOptimizationLevel;
+pub use ort::session::builder::GraphOptimizationLevel;
+use ort::session::builder::SessionBuilder;
 use ort::session::Session;
-use std::path::Path;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
 
 use crate::accel::{get_ort_accelerator, OrtAccelerator};
 
-/// Build the execution provider list based on the global accelerator preference.
-fn execution_providers() -> Vec<ort::ep::ExecutionProviderDispatch> {
-    let pref = get_ort_accelerator();
+/// Settings for the ONNX sessions of one model.
+///
+/// Pass it to an engine's `load_with_config` to give that model its own
+/// execution providers and thread budget instead of the process-wide
+/// [`set_ort_accelerator`](crate::set_ort_accelerator) preference. The
+/// default is what `load` uses.
+///
+/// ```ignore
+/// use transcribe_rs::onnx::parakeet::ParakeetModel;
+/// use transcribe_rs::onnx::session::SessionConfig;
+/// use transcribe_rs::onnx::Quantization;
+/// use transcribe_rs::vad::SileroVad;
+/// use transcribe_rs::OrtAccelerator;
+///
+/// // Small CPU model next to a large GPU one.
+/// let cpu = SessionConfig::default()
+///     .with_execution_providers(vec![OrtAccelerator::CpuOnly])
+///     .with_intra_threads(1)
+///     .with_inter_threads(1);
+/// let gpu = SessionConfig::default()
+///     .with_execution_providers(vec![OrtAccelerator::Cuda])
+///     .with_intra_threads(8);
+/// let vad = SileroVad::new_with_config("silero_vad.onnx", 0.3, &cpu)?;
+/// let asr = ParakeetModel::load_with_config(&dir, &Quantization::Int8, &gpu)?;
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionConfig {
+    /// Accelerators to try, in order. CPU is always added as the final
+    /// fallback. `None` uses the global preference.
+    pub execution_providers: Option<Vec<OrtAccelerator>>,
+    /// Intra-op threads (`None` or 0 = ORT default, one per core). With
+    /// XNNPACK this sizes the XNNPACK pool instead.
+    pub intra_threads: Option<usize>,
+    /// Inter-op threads, used when `parallel_execution` is on.
+    pub inter_threads: Option<usize>,
+    /// Run independent graph nodes in parallel. Forced off for DirectML
+    /// and WebGPU.
+    pub parallel_execution: bool,
+    pub optimization_level: GraphOptimizationLevel,
+    /// Memory pattern optimization (`None` = ORT default). Forced off for
+    /// DirectML and WebGPU.
+    pub memory_pattern: Option<bool>,
+    /// CPU arena allocator (`None` = ORT default). Disabling it lowers peak
+    /// memory at some cost in speed.
+    pub cpu_arena: Option<bool>,
+    /// Directory to write each session's optimized graph to, as
+    /// `{model file stem}.opt.onnx`; see
+    /// [`with_optimized_model_path`](Self::with_optimized_model_path).
+    pub optimized_model_path: Option<PathBuf>,
+    /// Directory for optimized graphs reused across loads; see
+    /// [`with_optimized_model_cache`](Self::with_optimized_model_cache).
+    pub optimized_model_cache: Option<PathBuf>,
+    /// Enable ONNX Runtime's profiler; see
+    /// [`with_profiling`](Self::with_profiling).
+    pub profiling: Option<PathBuf>,
+    /// Fail instead of falling back to FP32 when the requested quantized
+    /// file is missing.
+    pub strict_quantization: bool,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            execution_providers: None,
+            intra_threads: None,
+            inter_threads: None,
+            parallel_execution: true,
+            optimization_level: GraphOptimizationLevel::Level3,
+            memory_pattern: None,
+            cpu_arena: None,
+            optimized_model_path: None,
+            optimized_model_cache: None,
+            profiling: None,
+            strict_quantization: false,
+        }
+    }
+}
+
+impl SessionConfig {
+    pub fn with_execution_providers(mut self, accelerators: Vec<OrtAccelerator>) -> Self {
+        self.execution_providers = Some(accelerators);
+        self
+    }
+
+    pub fn with_intra_threads(mut self, n: usize) -> Self {
+        self.intra_threads = Some(n);
+        self
+    }
+
+    pub fn with_inter_threads(mut self, n: usize) -> Self {
+        self.inter_threads = Some(n);
+        self
+    }
+
+    pub fn with_parallel_execution(mut self, enable: bool) -> Self {
+        self.parallel_execution = enable;
+        self
+    }
+
+    pub fn with_optimization_level(mut self, level: GraphOptimizationLevel) -> Self {
+        self.optimization_level = level;
+        self
+    }
+
+    pub fn with_memory_pattern(mut self, enable: bool) -> Self {
+        self.memory_pattern = Some(enable);
+        self
+    }
+
+    pub fn with_cpu_arena(mut self, enable: bool) -> Self {
+        self.cpu_arena = Some(enable);
+        self
+    }
+
+    /// Write each session's optimized graph into the existing directory
+    /// `dir`, named after its model file: `encoder_model.onnx` becomes
+    /// `encoder_model.opt.onnx`. Models loaded from memory without a file
+    /// name are written as `model.opt.onnx`. Combined with
+    /// [`with_optimized_model_cache`](Self::with_optimized_model_cache),
+    /// the cached graph is copied there.
+    pub fn with_optimized_model_path(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.optimized_model_path = Some(dir.into());
+        self
+    }
+
+    /// Cache optimized graphs in `dir`.
+    ///
+    /// The first load optimizes each model as usual and saves the result;
+    /// later loads with the same model bytes, ONNX Runtime build, execution
+    /// providers and optimization level read it back with optimization
+    /// turned off. Graphs optimized at `Level3` can be specific to the
+    /// machine, so don't share the directory between different hardware.
+    /// Cache errors never fail a load — the model is then loaded uncached.
+    /// Models whose optimized graph ONNX Runtime can't save (e.g. nodes
+    /// compiled by an execution provider) are marked so later loads don't
+    /// retry.
+    pub fn with_optimized_model_cache(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.optimized_model_cache = Some(dir.into());
+        self
+    }
+
+    /// Profile every run with ONNX Runtime's built-in profiler.
+    ///
+    /// Each session writes a Chrome-trace JSON file named
+    /// `{prefix}_{timestamp}.json` (one per session, so a model with an
+    /// encoder and a decoder writes two) when the model is dropped. Load it
+    /// in `chrome://tracing` or Perfetto for per-operator timings.
+    pub fn with_profiling(mut self, prefix: impl Into<PathBuf>) -> Self {
+        self.profiling = Some(prefix.into());
+        self
+    }
+
+    /// Return [`TranscribeError::ModelNotFound`](crate::TranscribeError::ModelNotFound)
+    /// when a requested FP16/int8/int4 file is missing instead of loading
+    /// the FP32 file. Has no effect on [`Quantization::Auto`](super::Quantization::Auto).
+    pub fn with_strict_quantization(mut self, strict: bool) -> Self {
+        self.strict_quantization = strict;
+        self
+    }
+
+    /// The accelerators this config selects.
+    pub(crate) fn accelerators(&self) -> Vec<OrtAccelerator> {
+        match &self.execution_providers {
+            Some(list) => list.clone(),
+            None => vec![get_ort_accelerator()],
+        }
+    }
+}
+
+/// Build the execution provider list for the given accelerators.
+fn execution_providers(
+    accelerators: &[OrtAccelerator],
+    config: &SessionConfig,
+) -> Vec<ort::ep::ExecutionProviderDispatch> {
+    let mut eps = Vec::new();
+
+    for &pref in accelerators {
+        eps.extend(accelerator_providers(pref, config));
+    }
+
+    // CPU is always the final fallback
+    let cpu = match config.cpu_arena {
+        Some(enable) => CPU::default().with_arena_allocator(enable),
+        None => CPU::default(),
+    };
+    eps.push(cpu.build());
+    eps
+}
+
+/// Execution providers for one accelerator, without the CPU fallback.
+#[allow(unused_mut, unused_variables)]
+fn accelerator_providers(
+    pref: OrtAccelerator,
+    config: &SessionConfig,
+) -> Vec<ort::ep::ExecutionProviderDispatch> {
     let mut eps = Vec::new();
 
     match pref {
@@ -82,13 +280,15 @@ fn execution_providers() -> Vec<ort::ep::ExecutionProviderDispatch> {
         OrtAccelerator::Xnnpack => {
             #[cfg(feature = "ort-xnnpack")]
             {
-                // XNNPACK manages its own threadpool. Configure it with the
-                // available logical core count; the session-level intra-op
-                // pool is forced to 1 in build_session() when XNNPACK is
-                // active to avoid contention.
-                let n = std::thread::available_parallelism()
-                    .map(|n| n.get())
-                    .unwrap_or(1);
+                // XNNPACK manages its own threadpool. Size it from the
+                // config's intra-op budget, or the available logical core
+                // count; the session-level intra-op pool is forced to 1 in
+                // build_session() when XNNPACK is active to avoid contention.
+                let n = config.intra_threads.filter(|&n| n > 0).unwrap_or_else(|| {
+                    std::thread::available_parallelism()
+                        .map(|n| n.get())
+                        .unwrap_or(1)
+                });
                 if let Some(nz) = core::num::NonZeroUsize::new(n) {
                     eps.push(XNNPACK::default().with_intra_op_num_threads(nz).build());
                 } else {
@@ -123,63 +323,58 @@ fn execution_providers() -> Vec<ort::ep::ExecutionProviderDispatch> {
         }
     }
 
-    // CPU is always the final fallback
-    eps.push(CPU::default().build());
     eps
 }
 
-/// Returns true if the selected execution provider requires sequential execution
+/// Returns true if a selected execution provider requires sequential execution
 /// and disabled memory patterns (DirectML, WebGPU).
-fn requires_sequential_session() -> bool {
-    let pref = get_ort_accelerator();
-    (pref == OrtAccelerator::DirectMl && cfg!(feature = "ort-directml"))
-        || (pref == OrtAccelerator::WebGpu && cfg!(feature = "ort-webgpu"))
+fn requires_sequential_session(accelerators: &[OrtAccelerator]) -> bool {
+    accelerators.iter().any(|&pref| {
+        (pref == OrtAccelerator::DirectMl && cfg!(feature = "ort-directml"))
+            || (pref == OrtAccelerator::WebGpu && cfg!(feature = "ort-webgpu"))
+    })
 }
 
 /// Returns true if the XNNPACK EP is selected and compiled in. XNNPACK runs
 /// its own threadpool, so the session intra-op pool should be reduced to a
 /// single non-spinning thread to avoid contention.
-fn is_xnnpack_active() -> bool {
-    let pref = get_ort_accelerator();
-    pref == OrtAccelerator::Xnnpack && cfg!(feature = "ort-xnnpack")
+fn is_xnnpack_active(accelerators: &[OrtAccelerator]) -> bool {
+    accelerators.contains(&OrtAccelerator::Xnnpack) && cfg!(feature = "ort-xnnpack")
 }
 
-/// Internal session builder with full control over threading and EP selection.
-fn build_session(
-    path: &Path,
-    intra_threads: Option<usize>,
-    parallel_execution: bool,
-) -> Result<Session, ort::Error> {
-    let mut builder =
-        Session::builder()?.with_optimization_level(GraphOptimizationLevel::Level3)?;
+/// Where a session's model comes from.
+#[derive(Clone, Copy)]
+enum ModelBytes<'a> {
+    File(&'a Path),
+    /// Model bytes and, when known, the file name they were read as.
+    Memory(&'a [u8], Option<&'a str>),
+}
 
-    if is_xnnpack_active() {
-        // See ort::ep::XNNPACK docs: disable session intra-op spinning and
-        // force a single intra-op thread when XNNPACK is the active EP.
-        builder = builder.with_intra_op_spinning(false)?;
-        builder = builder.with_intra_threads(1)?;
-    } else if let Some(n) = intra_threads {
-        if n > 0 {
-            builder = builder.with_intra_threads(n)?;
-        }
+impl<'a> ModelBytes<'a> {
+    fn commit(self, mut builder: SessionBuilder) -> Result<Session, ort::Error> {
+        Ok(match self {
+            ModelBytes::File(path) => builder.commit_from_file(path)?,
+            ModelBytes::Memory(bytes, _) => builder.commit_from_memory(bytes)?,
+        })
     }
 
-    // DirectML and WebGPU require parallel_execution(false) and memory_pattern(false)
-    let use_parallel = if requires_sequential_session() {
-        false
-    } else {
-        parallel_execution
-    };
-
-    builder = builder.with_parallel_execution(use_parallel)?;
-
-    if requires_sequential_session() {
-        builder = builder.with_memory_pattern(false)?;
+    /// File stem for names derived from the model, `model` if unknown.
+    fn stem(self) -> &'a str {
+        let path = match self {
+            ModelBytes::File(path) => path,
+            ModelBytes::Memory(_, Some(name)) => Path::new(name),
+            ModelBytes::Memory(_, None) => return "model",
+        };
+        path.file_stem().and_then(|s| s.to_str()).unwrap_or("model")
     }
+}
 
-    let session = builder
-        .with_execution_providers(execution_providers())?
-        .commit_from_file(path)?;
+/// Internal session builder with full control over threading and EP selection.
+fn build_session(model: ModelBytes, config: &SessionConfig) -> Result<Session, ort::Error> {
+    let session = match &config.optimized_model_cache {
+        Some(dir) => build_cached_session(model, config, dir)?,
+        None => model.commit(model_builder(model, config)?)?,
+    };
 
     for input in session.inputs() {
         log::info!(
@@ -199,14 +394,265 @@ fn build_session(
     Ok(session)
 }
 
+/// Session builder configured from `config`, ready to commit.
+fn session_builder(config: &SessionConfig) -> Result<SessionBuilder, ort::Error> {
+    let accelerators = config.accelerators();
+    let mut builder = Session::builder()?.with_optimization_level(config.optimization_level)?;
+
+    if is_xnnpack_active(&accelerators) {
+        // See ort::ep::XNNPACK docs: disable session intra-op spinning and
+        // force a single intra-op thread when XNNPACK is the active EP.
+        builder = builder.with_intra_op_spinning(false)?;
+        builder = builder.with_intra_threads(1)?;
+    } else if let Some(n) = config.intra_threads {
+        if n > 0 {
+            builder = builder.with_intra_threads(n)?;
+        }
+    }
+
+    if let Some(n) = config.inter_threads {
+        if n > 0 {
+            builder = builder.with_inter_threads(n)?;
+        }
+    }
+
+    // DirectML and WebGPU require parallel_execution(false) and memory_pattern(false)
+    let sequential = requires_sequential_session(&accelerators);
+    builder = builder.with_parallel_execution(config.parallel_execution && !sequential)?;
+
+    if sequential {
+        builder = builder.with_memory_pattern(false)?;
+    } else if let Some(enable) = config.memory_pattern {
+        builder = builder.with_memory_pattern(enable)?;
+    }
+
+    if let Some(prefix) = &config.profiling {
+        builder = builder.with_profiling(prefix)?;
+    }
+
+    Ok(builder.with_execution_providers(execution_providers(&accelerators, config))?)
+}
+
+/// [`session_builder`] that also writes `model`'s optimized graph when
+/// `config.optimized_model_path` is set.
+fn model_builder(model: ModelBytes, config: &SessionConfig) -> Result<SessionBuilder, ort::Error> {
+    let mut builder = session_builder(config)?;
+    if let Some(dir) = &config.optimized_model_path {
+        builder = builder.with_optimized_model_path(optimized_model_file(dir, model))?;
+    }
+    Ok(builder)
+}
+
+fn optimized_model_file(dir: &Path, model: ModelBytes) -> PathBuf {
+    dir.join(format!("{}.opt.onnx", model.stem()))
+}
+
+/// Load `model` through the optimized graph cache in `dir`, filling it on a
+/// miss. Falls back to a plain load whenever the cache can't be used.
+fn build_cached_session(
+    model: ModelBytes,
+    config: &SessionConfig,
+    dir: &Path,
+) -> Result<Session, ort::Error> {
+    let key = match cache_key(model, config, ort::info()) {
+        Ok(key) => key,
+        Err(e) => {
+            log::warn!(
+                "Optimized model cache disabled: failed to hash model: {}",
+                e
+            );
+            return model.commit(model_builder(model, config)?);
+        }
+    };
+    let cached = dir.join(format!("{}.onnx", key));
+    // Written when ORT can't serialize the optimized graph (e.g. EPs that
+    // compile nodes), so later loads don't pay for a failed attempt.
+    let unsupported = dir.join(format!("{}.unsupported", key));
+
+    if cached.exists() {
+        let reload = SessionConfig {
+            optimization_level: GraphOptimizationLevel::Disable,
+            ..config.clone()
+        };
+        match ModelBytes::File(&cached).commit(session_builder(&reload)?) {
+            Ok(session) => {
+                log::info!("Loaded optimized model from cache: {}", cached.display());
+                export_optimized_model(&cached, model, config);
+                return Ok(session);
+            }
+            Err(e) => {
+                log::warn!(
+                    "Discarding unusable cached model {}: {}",
+                    cached.display(),
+                    e
+                );
+                let _ = fs::remove_file(&cached);
+            }
+        }
+    }
+
+    if unsupported.exists() {
+        return model.commit(model_builder(model, config)?);
+    }
+    if let Err(e) = fs::create_dir_all(dir) {
+        log::warn!(
+            "Optimized model cache disabled: cannot create {}: {}",
+            dir.display(),
+            e
+        );
+        return model.commit(model_builder(model, config)?);
+    }
+
+    // ORT writes the file while committing; go through a temp name so a
+    // crash never leaves a truncated model under the final name. The
+    // counter keeps threads of one process loading the same model apart.
+    static NEXT_TMP: AtomicU64 = AtomicU64::new(0);
+    let tmp = dir.join(format!(
+        "{}.{}.{}.tmp",
+        key,
+        std::process::id(),
+        NEXT_TMP.fetch_add(1, Ordering::Relaxed)
+    ));
+    match model.commit(session_builder(config)?.with_optimized_model_path(&tmp)?) {
+        Ok(session) => {
+            match fs::rename(&tmp, &cached) {
+                Ok(()) => {
+                    log::info!("Cached optimized model: {}", cached.display());
+                    export_optimized_model(&cached, model, config);
+                }
+                Err(e) => {
+                    log::warn!("Failed to cache optimized model: {}", e);
+                    export_optimized_model(&tmp, model, config);
+                    let _ = fs::remove_file(&tmp);
+                }
+            }
+            Ok(session)
+        }
+        Err(e) => {
+            let _ = fs::remove_file(&tmp);
+            log::warn!("Cannot cache optimized model, loading without cache: {}", e);
+            // Only a graph ORT can't serialize fails the same way next time;
+            // other errors (disk full, out of memory) may be transient.
+            if is_unserializable(e.message()) {
+                let _ = fs::write(&unsupported, e.to_string());
+            }
+            model.commit(model_builder(model, config)?)
+        }
+    }
+}
+
+/// Whether an ORT error `message` says the optimized graph can't be
+/// serialized, e.g. because it contains nodes compiled by an execution
+/// provider.
+fn is_unserializable(message: &str) -> bool {
+    message.contains("Unable to serialize model")
+}
+
+/// Copy the optimized graph at `optimized` to `config.optimized_model_path`,
+/// which the cache takes the place of. Failures are only logged.
+fn export_optimized_model(optimized: &Path, model: ModelBytes, config: &SessionConfig) {
+    if let Some(dir) = &config.optimized_model_path {
+        let path = optimized_model_file(dir, model);
+        if let Err(e) = fs::copy(optimized, &path) {
+            log::warn!("Failed to write optimized model {}: {}", path.display(), e);
+        }
+    }
+}
+
+/// Cache file stem for `model` under `config`: a SHA-256 of the model
+/// bytes, the ONNX Runtime build, the execution providers and the
+/// optimization level.
+fn cache_key(model: ModelBytes, config: &SessionConfig, ort_build: &str) -> io::Result<String> {
+    let mut hasher = Sha256::new();
+    match model {
+        ModelBytes::File(path) => {
+            io::copy(&mut fs::File::open(path)?, &mut hasher)?;
+        }
+        ModelBytes::Memory(bytes, _) => hasher.update(bytes),
+    }
+
+    // Auto expands to whatever EPs are compiled in, so key on those too.
+    let runtime = format!(
+        "{}\0{:?}\0{:?}\0{:?}",
+        ort_build,
+        config.accelerators(),
+        OrtAccelerator::available(),
+        config.optimization_level
+    );
+    hasher.update(b"\0");
+    hasher.update(runtime.as_bytes());
+
+    let digest: String = hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    Ok(format!("{}-{}", model.stem(), digest))
+}
+
+/// A session shared between clones of a model.
+///
+/// ort's `Session::run` takes `&mut self`, so concurrent runs of the same
+/// session take turns; runs of different sessions (one call's encoder while
+/// another call decodes) proceed in parallel. Cloning shares the session
+/// instead of loading the weights again.
+#[derive(Clone)]
+pub(crate) struct SharedSession(Arc<Mutex<Session>>);
+
+impl SharedSession {
+    /// Lock the session for a run. A panic during another run doesn't leave
+    /// the session unusable, so poisoning is ignored.
+    pub(crate) fn lock(&self) -> MutexGuard<'_, Session> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl From<Session> for SharedSession {
+    fn from(session: Session) -> Self {
+        Self(Arc::new(Mutex::new(session)))
+    }
+}
+
 /// Create an ONNX session with standard settings.
 pub fn create_session(path: &Path) -> Result<Session, ort::Error> {
-    build_session(path, None, true)
+    build_session(ModelBytes::File(path), &SessionConfig::default())
 }
 
 /// Create an ONNX session with configurable thread count.
 pub fn create_session_with_threads(path: &Path, num_threads: usize) -> Result<Session, ort::Error> {
-    build_session(path, Some(num_threads), true)
+    let config = SessionConfig::default().with_intra_threads(num_threads);
+    build_session(ModelBytes::File(path), &config)
+}
+
+/// Create an ONNX session with explicit settings.
+pub fn create_session_with_config(
+    path: &Path,
+    config: &SessionConfig,
+) -> Result<Session, ort::Error> {
+    build_session(ModelBytes::File(path), config)
+}
+
+/// Create an ONNX session from model bytes with standard settings.
+pub fn create_session_from_memory(bytes: &[u8]) -> Result<Session, ort::Error> {
+    build_session(ModelBytes::Memory(bytes, None), &SessionConfig::default())
+}
+
+/// Create an ONNX session from model bytes with explicit settings.
+pub fn create_session_from_memory_with_config(
+    bytes: &[u8],
+    config: &SessionConfig,
+) -> Result<Session, ort::Error> {
+    build_session(ModelBytes::Memory(bytes, None), config)
+}
+
+/// [`create_session_from_memory_with_config`] for the bytes of the model
+/// file `name`, so derived files are named after it.
+pub(crate) fn create_named_session_from_memory(
+    name: &str,
+    bytes: &[u8],
+    config: &SessionConfig,
+) -> Result<Session, ort::Error> {
+    build_session(ModelBytes::Memory(bytes, Some(name)), config)
 }
 
 /// Resolve a model file path for the requested quantization level.
@@ -218,28 +664,12 @@ pub fn resolve_model_path(
     name: &str,
     quantization: &super::Quantization,
 ) -> std::path::PathBuf {
-    let suffix = match quantization {
-        super::Quantization::FP32 => None,
-        super::Quantization::FP16 => Some("fp16"),
-        super::Quantization::Int8 => Some("int8"),
-        super::Quantization::Int4 => Some("int4"),
-    };
-
-    if let Some(suffix) = suffix {
-        let path = dir.join(format!("{}.{}.onnx", name, suffix));
-        if path.exists() {
-            log::info!("Loading {} model: {}", suffix, path.display());
-            return path;
-        }
-        log::warn!(
-            "{} model not found at {}, falling back to {}.onnx",
-            suffix,
-            path.display(),
-            name
-        );
-    }
-
-    dir.join(format!("{}.onnx", name))
+    let config = SessionConfig::default();
+    // Only strict mode fails, and the default config isn't strict.
+    let file = super::ModelSource::Dir(dir)
+        .resolve(name, quantization, &config)
+        .unwrap_or_else(|_| format!("{}.onnx", name));
+    dir.join(file)
 }
 
 /// Read a custom metadata string from an ONNX session.
@@ -287,3 +717,87 @@ pub fn read_metadata_float_vec(
         None => Ok(None),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offline_models_can_be_shared_between_threads() {
+        fn assert_shared<T: Clone + Send + Sync>() {}
+        assert_shared::<crate::onnx::canary::CanaryModel>();
+        assert_shared::<crate::onnx::cohere::CohereModel>();
+        assert_shared::<crate::onnx::gigaam::GigaAMModel>();
+        assert_shared::<crate::onnx::moonshine::MoonshineModel>();
+        assert_shared::<crate::onnx::parakeet::ParakeetModel>();
+        assert_shared::<crate::onnx::sense_voice::SenseVoiceModel>();
+    }
+
+    #[test]
+    fn optimized_model_files_are_named_per_model() {
+        let dir = Path::new("opt");
+        let encoder = Path::new("models/canary/encoder-model.int8.onnx");
+        assert_eq!(
+            optimized_model_file(dir, ModelBytes::File(encoder)),
+            dir.join("encoder-model.int8.opt.onnx")
+        );
+        assert_eq!(
+            optimized_model_file(dir, ModelBytes::Memory(b"", Some("decoder_model.onnx"))),
+            dir.join("decoder_model.opt.onnx")
+        );
+        assert_eq!(
+            optimized_model_file(dir, ModelBytes::Memory(b"", None)),
+            dir.join("model.opt.onnx")
+        );
+    }
+
+    #[test]
+    fn only_unserializable_graphs_disable_the_cache() {
+        assert!(is_unserializable(
+            "Unable to serialize model as it contains compiled nodes. \
+             Please disable any execution providers which generate compiled nodes."
+        ));
+        assert!(!is_unserializable(
+            "failed to write optimized model: No space left on device"
+        ));
+    }
+
+    #[test]
+    fn cache_key_tracks_model_runtime_and_providers() {
+        let cpu = SessionConfig::default().with_execution_providers(vec![OrtAccelerator::CpuOnly]);
+        let key = |bytes: &[u8], config: &SessionConfig, build: &str| {
+            cache_key(ModelBytes::Memory(bytes, None), config, build).unwrap()
+        };
+
+        let base = key(b"model-a", &cpu, "ort 1.22");
+        assert!(base.starts_with("model-"));
+        assert_eq!(base, key(b"model-a", &cpu, "ort 1.22"));
+        assert_ne!(base, key(b"model-b", &cpu, "ort 1.22"));
+        assert_ne!(base, key(b"model-a", &cpu, "ort 1.23"));
+        assert_ne!(
+            base,
+            key(
+                b"model-a",
+                &cpu.clone()
+                    .with_execution_providers(vec![OrtAccelerator::Cuda]),
+                "ort 1.22"
+            )
+        );
+        assert_ne!(
+            base,
+            key(
+                b"model-a",
+                &cpu.clone()
+                    .with_optimization_level(GraphOptimizationLevel::Level1),
+                "ort 1.22"
+            )
+        );
+
+        let path = std::env::temp_dir().join(format!("encoder-{}.onnx", std::process::id()));
+        fs::write(&path, b"model-a").unwrap();
+        let from_file = cache_key(ModelBytes::File(&path), &cpu, "ort 1.22").unwrap();
+        fs::remove_file(&path).unwrap();
+        assert!(from_file.starts_with("encoder-"));
+        assert_eq!(from_file.rsplit('-').next(), base.rsplit('-').next());
+    }
+}
@@ -0,0 +1,437 @@
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+
+use ort::session::Session;
+
+use super::session::{self, SessionConfig};
+use super::Quantization;
+use crate::{OrtAccelerator, TranscribeError};
+
+/// Model files held in memory, keyed by the names they have on disk.
+///
+/// Every ONNX engine's `load_from_memory` takes one of these and finds its
+/// files exactly as `load` does in a directory, including quantized
+/// variants (`encoder-model.int8.onnx`) and their FP32 fallback. Use it to
+/// embed models in the binary or to load them from an archive without
+/// writing temp files:
+///
+/// ```ignore
+/// use transcribe_rs::onnx::{ModelFiles, Quantization};
+/// use transcribe_rs::onnx::sense_voice::SenseVoiceModel;
+///
+/// let files = ModelFiles::new()
+///     .with("model.int8.onnx", &include_bytes!("../models/sense-voice/model.int8.onnx")[..])
+///     .with("tokens.txt", &include_bytes!("../models/sense-voice/tokens.txt")[..]);
+/// let model = SenseVoiceModel::load_from_memory(&files, &Quantization::Int8)?;
+/// ```
+///
+/// Borrowed slices are used as-is; [`insert_reader`](Self::insert_reader)
+/// reads a stream (e.g. a decrypting reader) into an owned buffer. Files
+/// only need to stay alive until the model is loaded.
+#[derive(Clone, Default)]
+pub struct ModelFiles<'a> {
+    files: HashMap<String, Cow<'a, [u8]>>,
+}
+
+impl<'a> ModelFiles<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a file, e.g. `"vocab.txt"` or `"onnx/encoder_model.onnx"`.
+    pub fn with(mut self, name: impl Into<String>, bytes: impl Into<Cow<'a, [u8]>>) -> Self {
+        self.insert(name, bytes);
+        self
+    }
+
+    /// Add a file, replacing any previous one with the same name.
+    pub fn insert(&mut self, name: impl Into<String>, bytes: impl Into<Cow<'a, [u8]>>) {
+        self.files.insert(name.into(), bytes.into());
+    }
+
+    /// Add a file by reading `reader` to the end.
+    pub fn insert_reader(
+        &mut self,
+        name: impl Into<String>,
+        mut reader: impl Read,
+    ) -> Result<(), TranscribeError> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        self.insert(name, bytes);
+        Ok(())
+    }
+
+    pub fn get(&self, name: &str) -> Option<&[u8]> {
+        self.files.get(name).map(|b| b.as_ref())
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.files.contains_key(name)
+    }
+
+    /// File names, in no particular order.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.files.keys().map(String::as_str)
+    }
+
+    /// Quantization variants among the files; see [`available_quantizations`].
+    pub fn quantizations(&self) -> Vec<Quantization> {
+        quantizations_in(self.names())
+    }
+}
+
+impl fmt::Debug for ModelFiles<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map()
+            .entries(self.files.iter().map(|(name, bytes)| (name, bytes.len())))
+            .finish()
+    }
+}
+
+/// Where an engine's `load` reads its files from.
+#[derive(Clone, Copy)]
+pub(crate) enum ModelSource<'a> {
+    Dir(&'a Path),
+    Memory(&'a ModelFiles<'a>),
+}
+
+impl ModelSource<'_> {
+    /// Path of `name` for errors and logs.
+    pub(crate) fn path(&self, name: &str) -> PathBuf {
+        match self {
+            ModelSource::Dir(dir) => dir.join(name),
+            ModelSource::Memory(_) => PathBuf::from(name),
+        }
+    }
+
+    pub(crate) fn exists(&self, name: &str) -> bool {
+        match self {
+            ModelSource::Dir(dir) => dir.join(name).exists(),
+            ModelSource::Memory(files) => files.contains(name),
+        }
+    }
+
+    /// Read a whole file.
+    pub(crate) fn read(&self, name: &str) -> Result<Cow<'_, [u8]>, TranscribeError> {
+        match self {
+            ModelSource::Dir(dir) => Ok(Cow::Owned(fs::read(dir.join(name))?)),
+            ModelSource::Memory(files) => files
+                .get(name)
+                .map(Cow::Borrowed)
+                .ok_or_else(|| TranscribeError::ModelNotFound(self.path(name))),
+        }
+    }
+
+    /// Read a UTF-8 text file.
+    pub(crate) fn read_to_string(&self, name: &str) -> Result<String, TranscribeError> {
+        let bytes = self.read(name)?;
+        String::from_utf8(bytes.into_owned()).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {e}", self.path(name).display()),
+            )
+            .into()
+        })
+    }
+
+    /// Create a session for `name`.
+    pub(crate) fn session(
+        &self,
+        name: &str,
+        config: &SessionConfig,
+    ) -> Result<Session, TranscribeError> {
+        let session = match self {
+            ModelSource::Dir(dir) => session::create_session_with_config(&dir.join(name), config)?,
+            ModelSource::Memory(_) => {
+                session::create_named_session_from_memory(name, &self.read(name)?, config)?
+            }
+        };
+        Ok(session)
+    }
+
+    /// File name for `name` at the requested quantization: `{name}.{suffix}.onnx`
+    /// if present, otherwise `{name}.onnx` (FP32). With
+    /// `config.strict_quantization` a missing quantized file is an error.
+    pub(crate) fn resolve(
+        &self,
+        name: &str,
+        quantization: &Quantization,
+        config: &SessionConfig,
+    ) -> Result<String, TranscribeError> {
+        for candidate in quantization_candidates(quantization, config) {
+            let file = match quantization_suffix(&candidate) {
+                Some(suffix) => format!("{}.{}.onnx", name, suffix),
+                None => format!("{}.onnx", name),
+            };
+            if self.exists(&file) {
+                if let Some(suffix) = quantization_suffix(&candidate) {
+                    log::info!("Loading {} model: {}", suffix, self.path(&file).display());
+                } else if *quantization == Quantization::Auto {
+                    log::info!("Loading fp32 model: {}", self.path(&file).display());
+                }
+                return Ok(file);
+            }
+        }
+
+        // The requested quantized file is missing (a missing FP32 file is
+        // reported when the session is created).
+        if let Some(suffix) = quantization_suffix(quantization) {
+            let file = self.path(&format!("{}.{}.onnx", name, suffix));
+            if config.strict_quantization {
+                return Err(TranscribeError::ModelNotFound(file));
+            }
+            log::warn!(
+                "{} model not found at {}, falling back to {}.onnx",
+                suffix,
+                file.display(),
+                name
+            );
+        }
+        Ok(format!("{}.onnx", name))
+    }
+}
+
+/// Quantization variants of the ONNX models in `dir` (and its `onnx/`
+/// subdirectory), in declaration order.
+///
+/// A variant is listed when a model file carries its suffix
+/// (`encoder-model.int8.onnx`, `encoder_model_fp16.onnx`, `.ort` files
+/// too). FP32 is listed when a quantized file has an unsuffixed sibling or
+/// when no file is quantized, so an FP32-only component such as Parakeet's
+/// `nemo128.onnx` preprocessor doesn't make an int8-only bundle look FP32.
+/// Engines pick each file separately, so a variant may cover only part of
+/// a model.
+pub fn available_quantizations(dir: &Path) -> Result<Vec<Quantization>, TranscribeError> {
+    let mut names = Vec::new();
+    for subdir in [dir.to_path_buf(), dir.join("onnx")] {
+        if subdir != dir && !subdir.is_dir() {
+            continue;
+        }
+        for entry in fs::read_dir(&subdir)? {
+            names.push(entry?.file_name().to_string_lossy().into_owned());
+        }
+    }
+    Ok(quantizations_in(names.iter().map(String::as_str)))
+}
+
+fn quantizations_in<'n>(names: impl IntoIterator<Item = &'n str>) -> Vec<Quantization> {
+    let mut fp32_stems = HashSet::new();
+    let mut quantized = Vec::new();
+    for name in names {
+        let name = name.rsplit('/').next().unwrap_or(name);
+        let Some(stem) = name
+            .strip_suffix(".onnx")
+            .or_else(|| name.strip_suffix(".ort"))
+        else {
+            continue;
+        };
+        match split_quantization(stem) {
+            Some(variant) => quantized.push(variant),
+            None => {
+                fp32_stems.insert(stem);
+            }
+        }
+    }
+
+    let has_fp32 = if quantized.is_empty() {
+        !fp32_stems.is_empty()
+    } else {
+        quantized.iter().any(|(base, _)| fp32_stems.contains(base))
+    };
+    [
+        Quantization::FP32,
+        Quantization::FP16,
+        Quantization::Int8,
+        Quantization::Int4,
+    ]
+    .into_iter()
+    .filter(|q| match q {
+        Quantization::FP32 => has_fp32,
+        q => quantized.iter().any(|(_, found)| found == q),
+    })
+    .collect()
+}
+
+/// Split `encoder-model.int8` or `encoder_model_fp16` into base name and
+/// variant.
+fn split_quantization(stem: &str) -> Option<(&str, Quantization)> {
+    [Quantization::FP16, Quantization::Int8, Quantization::Int4]
+        .into_iter()
+        .find_map(|q| {
+            let base = stem.strip_suffix(quantization_suffix(&q)?)?;
+            let base = base.strip_suffix('.').or_else(|| base.strip_suffix('_'))?;
+            Some((base, q))
+        })
+}
+
+/// Concrete variants to look for, best first: `quantization` itself, or
+/// the [`Quantization::Auto`] order for the config's execution providers.
+pub(crate) fn quantization_candidates(
+    quantization: &Quantization,
+    config: &SessionConfig,
+) -> Vec<Quantization> {
+    match quantization {
+        Quantization::Auto if uses_gpu(&config.accelerators()) => vec![
+            Quantization::FP16,
+            Quantization::FP32,
+            Quantization::Int8,
+            Quantization::Int4,
+        ],
+        Quantization::Auto => vec![
+            Quantization::Int8,
+            Quantization::FP32,
+            Quantization::Int4,
+            Quantization::FP16,
+        ],
+        q => vec![*q],
+    }
+}
+
+/// Whether the sessions will run on a compiled-in GPU provider.
+fn uses_gpu(accelerators: &[OrtAccelerator]) -> bool {
+    let available = OrtAccelerator::available();
+    accelerators.iter().any(|accel| match accel {
+        OrtAccelerator::CpuOnly | OrtAccelerator::Xnnpack => false,
+        // Same set the Auto provider list is built from.
+        OrtAccelerator::Auto => available.iter().any(|a| {
+            matches!(
+                a,
+                OrtAccelerator::TensorRt
+                    | OrtAccelerator::Cuda
+                    | OrtAccelerator::Rocm
+                    | OrtAccelerator::CoreMl
+            )
+        }),
+        gpu => available.contains(gpu),
+    })
+}
+
+/// File-name suffix of a quantized variant, `None` for FP32.
+pub(crate) fn quantization_suffix(quantization: &Quantization) -> Option<&'static str> {
+    match quantization {
+        Quantization::FP32 => None,
+        Quantization::FP16 => Some("fp16"),
+        Quantization::Int8 => Some("int8"),
+        Quantization::Int4 => Some("int4"),
+        // Expanded by `quantization_candidates` before any lookup.
+        Quantization::Auto => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_source_resolves_like_a_directory() {
+        let files = ModelFiles::new()
+            .with("encoder-model.int8.onnx", &b"int8"[..])
+            .with("encoder-model.onnx", b"fp32".to_vec())
+            .with("decoder-model.onnx", &b"fp32"[..]);
+        let source = ModelSource::Memory(&files);
+
+        let config = SessionConfig::default();
+        assert_eq!(
+            source
+                .resolve("encoder-model", &Quantization::Int8, &config)
+                .unwrap(),
+            "encoder-model.int8.onnx"
+        );
+        assert_eq!(
+            source
+                .resolve("decoder-model", &Quantization::Int8, &config)
+                .unwrap(),
+            "decoder-model.onnx"
+        );
+        assert_eq!(&*source.read("encoder-model.onnx").unwrap(), b"fp32");
+        assert!(matches!(
+            source.read("vocab.txt"),
+            Err(TranscribeError::ModelNotFound(_))
+        ));
+
+        let mut files = files;
+        files.insert_reader("vocab.txt", &b"<blk> 0\n"[..]).unwrap();
+        assert_eq!(
+            ModelSource::Memory(&files)
+                .read_to_string("vocab.txt")
+                .unwrap(),
+            "<blk> 0\n"
+        );
+    }
+
+    #[test]
+    fn strict_and_auto_quantization() {
+        let files = ModelFiles::new()
+            .with("encoder-model.int8.onnx", &b""[..])
+            .with("encoder-model.onnx", &b""[..])
+            .with("decoder-model.fp16.onnx", &b""[..])
+            .with("decoder-model.onnx", &b""[..]);
+        let source = ModelSource::Memory(&files);
+
+        let strict = SessionConfig::default().with_strict_quantization(true);
+        assert!(matches!(
+            source.resolve("decoder-model", &Quantization::Int8, &strict),
+            Err(TranscribeError::ModelNotFound(_))
+        ));
+        assert_eq!(
+            source
+                .resolve("decoder-model", &Quantization::FP16, &strict)
+                .unwrap(),
+            "decoder-model.fp16.onnx"
+        );
+
+        let cpu = SessionConfig::default().with_execution_providers(vec![OrtAccelerator::CpuOnly]);
+        assert_eq!(
+            source
+                .resolve("encoder-model", &Quantization::Auto, &cpu)
+                .unwrap(),
+            "encoder-model.int8.onnx"
+        );
+        assert_eq!(
+            source
+                .resolve("decoder-model", &Quantization::Auto, &cpu)
+                .unwrap(),
+            "decoder-model.onnx"
+        );
+    }
+
+    #[test]
+    fn lists_available_quantizations() {
+        let names = |names: &[&str]| {
+            let mut files = ModelFiles::new();
+            for name in names {
+                files.insert(*name, &b""[..]);
+            }
+            files.quantizations()
+        };
+        use Quantization::*;
+
+        assert_eq!(
+            names(&[
+                "nemo128.onnx",
+                "encoder-model.int8.onnx",
+                "decoder_joint-model.int8.onnx",
+                "vocab.txt"
+            ]),
+            [Int8]
+        );
+        assert_eq!(
+            names(&["model.onnx", "model.int8.onnx", "tokens.txt"]),
+            [FP32, Int8]
+        );
+        assert_eq!(
+            names(&[
+                "onnx/encoder_model.onnx",
+                "onnx/encoder_model_fp16.onnx",
+                "onnx/encoder_model.int4.onnx"
+            ]),
+            [FP32, FP16, Int4]
+        );
+        assert_eq!(names(&["frontend.ort", "encoder.ort"]), [FP32]);
+        assert!(names(&["tokens.txt"]).is_empty());
+    }
+}
@@ -0,0 +1,493 @@
+//! Load any engine by `engine_id` from a [`ModelSpec`].
+//!
+//! Each engine has its own constructor (`SenseVoiceModel::load(dir, quant)`,
+//! `MoonshineModel::load(dir, variant, quant)`, `WhisperEngine::load(path)`,
+//! ...). [`ModelRegistry`] maps [`ModelCapabilities::engine_id`] strings to
+//! those constructors so apps can pick a model from a config file and get a
+//! `Box<dyn SpeechModel>` back. When the spec has no `engine`, it is detected
+//! from the model directory's file layout with [`detect_engine`].
+//!
+//! ```ignore
+//! use transcribe_rs::ModelSpec;
+//!
+//! // e.g. from `{"path": "models/parakeet-v3", "quantization": "int8"}`
+//! let spec: ModelSpec = serde_json::from_str(&config)?;
+//! let mut model = spec.load()?;
+//! let result = model.transcribe_file(&wav, &TranscribeOptions::default())?;
+//! ```
+//!
+//! The default registry contains the engines enabled by crate features; use
+//! [`ModelRegistry::register`] to add your own.
+//!
+//! [`ModelCapabilities::engine_id`]: crate::ModelCapabilities::engine_id
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{SpeechModel, TranscribeError};
+
+/// Where a model lives and how to load it.
+///
+/// Only `path` is required. Fields an engine doesn't use are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelSpec {
+    /// Engine identifier (e.g. "parakeet", "whisper_cpp"). Detected from
+    /// `path` when `None`.
+    pub engine: Option<String>,
+    /// Model directory, or the model file for whisper.cpp and Whisperfile.
+    pub path: PathBuf,
+    /// ONNX quantization: "fp32", "fp16", "int8", "int4" or "auto".
+    /// Defaults to FP32.
+    pub quantization: Option<String>,
+    /// Fail when the requested quantized files are missing instead of
+    /// loading FP32 ones.
+    #[serde(default)]
+    pub strict_quantization: bool,
+    /// Model variant, e.g. the Moonshine size ("tiny", "base-es", ...).
+    pub variant: Option<String>,
+    /// Whisperfile executable.
+    pub binary: Option<PathBuf>,
+    /// Intra-op threads for ONNX engines (default: ORT's choice, 4 for
+    /// Moonshine streaming).
+    pub num_threads: Option<usize>,
+}
+
+impl ModelSpec {
+    /// Spec for the model at `path` with the engine auto-detected.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_engine(mut self, engine: impl Into<String>) -> Self {
+        self.engine = Some(engine.into());
+        self
+    }
+
+    pub fn with_quantization(mut self, quantization: impl Into<String>) -> Self {
+        self.quantization = Some(quantization.into());
+        self
+    }
+
+    pub fn with_strict_quantization(mut self, strict: bool) -> Self {
+        self.strict_quantization = strict;
+        self
+    }
+
+    pub fn with_variant(mut self, variant: impl Into<String>) -> Self {
+        self.variant = Some(variant.into());
+        self
+    }
+
+    /// The engine to load: `engine` if set, otherwise "whisperfile" when a
+    /// `binary` is given, otherwise [`detect_engine`] on `path`.
+    pub fn resolve_engine(&self) -> Result<String, TranscribeError> {
+        if let Some(engine) = &self.engine {
+            return Ok(engine.clone());
+        }
+        if self.binary.is_some() {
+            return Ok("whisperfile".to_string());
+        }
+        if !self.path.exists() {
+            return Err(TranscribeError::ModelNotFound(self.path.clone()));
+        }
+        detect_engine(&self.path)
+            .map(str::to_string)
+            .ok_or_else(|| {
+                TranscribeError::Config(format!(
+                    "cannot detect engine from {}; set `engine` explicitly",
+                    self.path.display()
+                ))
+            })
+    }
+
+    /// Load with the default [`ModelRegistry`].
+    pub fn load(&self) -> Result<Box<dyn SpeechModel>, TranscribeError> {
+        ModelRegistry::default().load(self)
+    }
+}
+
+/// Builds a model from a spec.
+pub type ModelConstructor =
+    Box<dyn Fn(&ModelSpec) -> Result<Box<dyn SpeechModel>, TranscribeError> + Send + Sync>;
+
+/// Map from `engine_id` to constructor.
+///
+/// [`ModelRegistry::default()`] registers every built-in engine enabled by
+/// crate features; [`ModelRegistry::new()`] starts empty.
+pub struct ModelRegistry {
+    constructors: BTreeMap<String, ModelConstructor>,
+}
+
+impl ModelRegistry {
+    /// An empty registry.
+    pub fn new() -> Self {
+        Self {
+            constructors: BTreeMap::new(),
+        }
+    }
+
+    /// Register `constructor` for `engine_id`, replacing any previous one.
+    pub fn register(
+        &mut self,
+        engine_id: impl Into<String>,
+        constructor: impl Fn(&ModelSpec) -> Result<Box<dyn SpeechModel>, TranscribeError>
+            + Send
+            + Sync
+            + 'static,
+    ) {
+        self.constructors
+            .insert(engine_id.into(), Box::new(constructor));
+    }
+
+    /// Registered engine ids, sorted.
+    pub fn engines(&self) -> impl Iterator<Item = &str> {
+        self.constructors.keys().map(String::as_str)
+    }
+
+    pub fn contains(&self, engine_id: &str) -> bool {
+        self.constructors.contains_key(engine_id)
+    }
+
+    /// Load the model described by `spec`.
+    pub fn load(&self, spec: &ModelSpec) -> Result<Box<dyn SpeechModel>, TranscribeError> {
+        let engine = spec.resolve_engine()?;
+        let constructor = self.constructors.get(&engine).ok_or_else(|| {
+            let reason = match required_feature(&engine) {
+                Some(feature) => format!("requires the `{feature}` feature"),
+                None => format!(
+                    "is unknown (available: {})",
+                    self.engines().collect::<Vec<_>>().join(", ")
+                ),
+            };
+            TranscribeError::Config(format!("engine `{engine}` {reason}"))
+        })?;
+        log::info!("Loading {} model from {}", engine, spec.path.display());
+        constructor(spec)
+    }
+}
+
+impl Default for ModelRegistry {
+    fn default() -> Self {
+        #[allow(unused_mut)]
+        let mut registry = Self::new();
+
+        #[cfg(feature = "onnx")]
+        {
+            use crate::onnx::canary::CanaryModel;
+            use crate::onnx::cohere::CohereModel;
+            use crate::onnx::gigaam::GigaAMModel;
+            use crate::onnx::moonshine::{MoonshineModel, StreamingModel};
+            use crate::onnx::parakeet::ParakeetModel;
+            use crate::onnx::sense_voice::SenseVoiceModel;
+
+            registry.register("parakeet", |spec| {
+                Ok(Box::new(ParakeetModel::load_with_config(
+                    &spec.path,
+                    &quantization(spec)?,
+                    &session_config(spec, None),
+                )?))
+            });
+            registry.register("canary", |spec| {
+                Ok(Box::new(CanaryModel::load_with_config(
+                    &spec.path,
+                    &quantization(spec)?,
+                    &session_config(spec, None),
+                )?))
+            });
+            registry.register("cohere", |spec| {
+                Ok(Box::new(CohereModel::load_with_config(
+                    &spec.path,
+                    &quantization(spec)?,
+                    &session_config(spec, None),
+                )?))
+            });
+            registry.register("gigaam", |spec| {
+                Ok(Box::new(GigaAMModel::load_with_config(
+                    &spec.path,
+                    &quantization(spec)?,
+                    &session_config(spec, None),
+                )?))
+            });
+            registry.register("sense_voice", |spec| {
+                Ok(Box::new(SenseVoiceModel::load_with_config(
+                    &spec.path,
+                    &quantization(spec)?,
+                    &session_config(spec, None),
+                )?))
+            });
+            registry.register("moonshine", |spec| {
+                let variant = spec
+                    .variant
+                    .as_deref()
+                    .map_or(Ok(Default::default()), str::parse)
+                    .map_err(TranscribeError::Config)?;
+                Ok(Box::new(MoonshineModel::load_with_config(
+                    &spec.path,
+                    variant,
+                    &quantization(spec)?,
+                    &session_config(spec, None),
+                )?))
+            });
+            registry.register("moonshine_streaming", |spec| {
+                Ok(Box::new(StreamingModel::load_with_config(
+                    &spec.path,
+                    &quantization(spec)?,
+                    &session_config(spec, Some(DEFAULT_NUM_THREADS)),
+                )?))
+            });
+        }
+
+        #[cfg(feature = "whisper-cpp")]
+        registry.register("whisper_cpp", |spec| {
+            Ok(Box::new(crate::whisper_cpp::WhisperEngine::load(
+                &spec.path,
+            )?))
+        });
+
+        #[cfg(feature = "whisperfile")]
+        registry.register("whisperfile", |spec| {
+            let binary = spec.binary.as_ref().ok_or_else(|| {
+                TranscribeError::Config("whisperfile requires `binary`".to_string())
+            })?;
+            Ok(Box::new(crate::whisperfile::WhisperfileEngine::load(
+                binary, &spec.path,
+            )?))
+        });
+
+        registry
+    }
+}
+
+/// Thread count for engines that need one when the spec doesn't say.
+#[cfg(feature = "onnx")]
+const DEFAULT_NUM_THREADS: usize = 4;
+
+#[cfg(feature = "onnx")]
+fn session_config(spec: &ModelSpec, default_threads: Option<usize>) -> crate::onnx::SessionConfig {
+    crate::onnx::SessionConfig {
+        intra_threads: spec.num_threads.or(default_threads),
+        strict_quantization: spec.strict_quantization,
+        ..Default::default()
+    }
+}
+
+#[cfg(feature = "onnx")]
+fn quantization(spec: &ModelSpec) -> Result<crate::onnx::Quantization, TranscribeError> {
+    spec.quantization
+        .as_deref()
+        .map_or(Ok(Default::default()), str::parse)
+        .map_err(TranscribeError::Config)
+}
+
+/// Feature that provides a built-in engine, for error messages.
+fn required_feature(engine: &str) -> Option<&'static str> {
+    match engine {
+        "parakeet"
+        | "canary"
+        | "cohere"
+        | "gigaam"
+        | "sense_voice"
+        | "moonshine"
+        | "moonshine_streaming" => Some("onnx"),
+        "whisper_cpp" => Some("whisper-cpp"),
+        "whisperfile" => Some("whisperfile"),
+        _ => None,
+    }
+}
+
+/// Guess the `engine_id` of the model at `path` from its files.
+///
+/// A `.bin`/`.gguf` file is a whisper.cpp model. For directories:
+///
+/// | Files | Engine |
+/// |---|---|
+/// | `streaming_config.json` | `moonshine_streaming` |
+/// | `nemo128.onnx` + `decoder_joint-model*.onnx` | `parakeet` |
+/// | `nemo128.onnx` + `decoder-model*.onnx` | `canary` |
+/// | `encoder_model*.onnx` + `tokenizer.json` | `moonshine` |
+/// | `cohere-encoder*.onnx`, or `encoder_model*.onnx` + `tokens.txt`/`vocabulary.txt` (also under `onnx/`) | `cohere` |
+/// | `model*.onnx` + `tokens.txt` | `sense_voice` |
+/// | `model*.onnx` + `vocab.txt` | `gigaam` |
+///
+/// Quantized file names (`model.int8.onnx`, ...) count. Returns `None` if
+/// nothing matches. Detection only looks at names; it doesn't check that
+/// the files are complete.
+pub fn detect_engine(path: &Path) -> Option<&'static str> {
+    if path.is_file() {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        return matches!(ext.as_str(), "bin" | "gguf").then_some("whisper_cpp");
+    }
+
+    let files = list_files(path);
+    let has = |name: &str| files.iter().any(|f| f == name);
+    let has_onnx = |stem: &str| files.iter().any(|f| is_onnx_variant(f, stem));
+
+    if has("streaming_config.json") {
+        return Some("moonshine_streaming");
+    }
+    if has("nemo128.onnx") {
+        if has_onnx("decoder_joint-model") {
+            return Some("parakeet");
+        }
+        if has_onnx("decoder-model") {
+            return Some("canary");
+        }
+    }
+    if has_onnx("encoder_model") && has("tokenizer.json") {
+        return Some("moonshine");
+    }
+
+    let nested = list_files(&path.join("onnx"));
+    let any_has = |name: &str| has(name) || nested.iter().any(|f| f == name);
+    let any_onnx = |stem: &str| has_onnx(stem) || nested.iter().any(|f| is_onnx_variant(f, stem));
+    if any_onnx("cohere-encoder")
+        || (any_onnx("encoder_model") && (any_has("tokens.txt") || any_has("vocabulary.txt")))
+    {
+        return Some("cohere");
+    }
+
+    if has_onnx("model") {
+        if has("tokens.txt") {
+            return Some("sense_voice");
+        }
+        if has("vocab.txt") {
+            return Some("gigaam");
+        }
+    }
+    None
+}
+
+fn list_files(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.file_name().into_string().ok())
+        .collect()
+}
+
+/// `stem.onnx`, `stem.int8.onnx`, `stem_fp16.onnx`, ...
+fn is_onnx_variant(file: &str, stem: &str) -> bool {
+    file.strip_prefix(stem)
+        .and_then(|rest| rest.strip_suffix(".onnx"))
+        .is_some_and(|mid| mid.is_empty() || mid.starts_with('.') || mid.starts_with('_'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcriber::test_helpers::MockModel;
+    use crate::TranscribeOptions;
+
+    fn create_temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("registry_test_{name}_{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn layout(name: &str, files: &[&str]) -> PathBuf {
+        let dir = create_temp_dir(name);
+        for file in files {
+            let path = dir.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, b"").unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn detects_engine_from_layout() {
+        let cases: &[(&str, &[&str])] = &[
+            (
+                "parakeet",
+                &[
+                    "nemo128.onnx",
+                    "encoder-model.int8.onnx",
+                    "decoder_joint-model.int8.onnx",
+                    "vocab.txt",
+                ],
+            ),
+            (
+                "canary",
+                &[
+                    "nemo128.onnx",
+                    "encoder-model.onnx",
+                    "decoder-model.onnx",
+                    "vocab.txt",
+                ],
+            ),
+            (
+                "moonshine",
+                &[
+                    "encoder_model.onnx",
+                    "decoder_model_merged.onnx",
+                    "tokenizer.json",
+                ],
+            ),
+            (
+                "moonshine_streaming",
+                &["streaming_config.json", "tokenizer.bin", "encoder.ort"],
+            ),
+            (
+                "cohere",
+                &[
+                    "onnx/encoder_model_fp16.onnx",
+                    "onnx/decoder_model_merged_fp16.onnx",
+                    "tokens.txt",
+                ],
+            ),
+            ("sense_voice", &["model.int8.onnx", "tokens.txt"]),
+            ("gigaam", &["model.onnx", "vocab.txt"]),
+        ];
+        for (engine, files) in cases {
+            let dir = layout(engine, files);
+            assert_eq!(detect_engine(&dir), Some(*engine), "{files:?}");
+            fs::remove_dir_all(dir).unwrap();
+        }
+
+        let dir = layout("whisper", &["ggml-base.bin", "unrelated.txt"]);
+        assert_eq!(
+            detect_engine(&dir.join("ggml-base.bin")),
+            Some("whisper_cpp")
+        );
+        assert_eq!(detect_engine(&dir), None);
+        assert_eq!(detect_engine(&dir.join("unrelated.txt")), None);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn registry_loads_spec_from_config() {
+        let mut registry = ModelRegistry::new();
+        registry.register("mock", |_| Ok(Box::new(MockModel)));
+        assert_eq!(registry.engines().collect::<Vec<_>>(), ["mock"]);
+
+        let spec: ModelSpec =
+            serde_json::from_str(r#"{"engine": "mock", "path": "models/mock"}"#).unwrap();
+        assert_eq!(spec, ModelSpec::new("models/mock").with_engine("mock"));
+        let mut model = registry.load(&spec).unwrap();
+        let result = model
+            .transcribe(&[0.0; 160], &TranscribeOptions::default())
+            .unwrap();
+        assert_eq!(result.text, "chunk_160");
+
+        let err = registry
+            .load(&spec.clone().with_engine("nope"))
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("available: mock"), "{err}");
+
+        let missing = ModelSpec::new("/nonexistent/model/dir");
+        assert!(matches!(
+            registry.load(&missing),
+            Err(TranscribeError::ModelNotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,198 @@
+//! Adapters between local and remote backends.
+//!
+//! - [`RemoteSpeechModel`] implements [`SpeechModel`] for any
+//!   [`RemoteTranscriptionEngine`], so a cloud engine can be used wherever a
+//!   `Box<dyn SpeechModel>` is expected (including the chunked
+//!   [`transcriber`](crate::transcriber) strategies).
+//! - With the `async` feature, [`AsyncSpeechModel`](crate::AsyncSpeechModel)
+//!   implements [`RemoteTranscriptionEngine`] with
+//!   [`TranscribeOptions`] as its request parameters, so async code can
+//!   drive local and remote engines through one trait.
+
+use super::RemoteTranscriptionEngine;
+use crate::{
+    audio, ModelCapabilities, SpeechModel, TranscribeError, TranscribeOptions, TranscriptionResult,
+};
+
+/// Builds an engine's request parameters from [`TranscribeOptions`].
+type ParamsFn<P> = Box<dyn Fn(&TranscribeOptions) -> P + Send>;
+
+/// A [`RemoteTranscriptionEngine`] behind the synchronous [`SpeechModel`]
+/// interface.
+///
+/// Samples are encoded to an in-memory WAV and uploaded with
+/// [`transcribe_wav_bytes`](RemoteTranscriptionEngine::transcribe_wav_bytes),
+/// blocking on an internal single-threaded runtime.
+///
+/// Like other `SpeechModel`s, call it from a blocking context: calling it
+/// from inside an async runtime panics. From async code, wrap it in an
+/// [`AsyncSpeechModel`](crate::AsyncSpeechModel) or use the engine directly.
+///
+/// ```ignore
+/// let model: Box<dyn SpeechModel> = if use_cloud {
+///     Box::new(openai::speech_model(openai::default_engine(), OpenAIModel::Whisper1)?)
+/// } else {
+///     Box::new(ParakeetModel::load(&dir, &Quantization::Int8)?)
+/// };
+/// ```
+pub struct RemoteSpeechModel<E: RemoteTranscriptionEngine> {
+    engine: E,
+    capabilities: ModelCapabilities,
+    make_params: ParamsFn<E::RequestParams>,
+    runtime: tokio::runtime::Runtime,
+}
+
+impl<E: RemoteTranscriptionEngine> RemoteSpeechModel<E> {
+    /// Wrap `engine`. `make_params` maps the options of each call to the
+    /// engine's request parameters; `capabilities` is reported as-is.
+    pub fn new(
+        engine: E,
+        capabilities: ModelCapabilities,
+        make_params: impl Fn(&TranscribeOptions) -> E::RequestParams + Send + 'static,
+    ) -> Result<Self, TranscribeError> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        Ok(Self {
+            engine,
+            capabilities,
+            make_params: Box::new(make_params),
+            runtime,
+        })
+    }
+
+    /// The wrapped engine.
+    pub fn engine(&self) -> &E {
+        &self.engine
+    }
+}
+
+impl<E: RemoteTranscriptionEngine> SpeechModel for RemoteSpeechModel<E> {
+    fn capabilities(&self) -> ModelCapabilities {
+        self.capabilities.clone()
+    }
+
+    fn transcribe_raw(
+        &mut self,
+        samples: &[f32],
+        options: &TranscribeOptions,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        let wav = audio::encode_wav(samples)?;
+        let params = (self.make_params)(options);
+        log::debug!(
+            "{}: uploading {:.2}s of audio",
+            self.capabilities.name,
+            samples.len() as f32 / self.capabilities.sample_rate as f32
+        );
+        self.runtime
+            .block_on(self.engine.transcribe_wav_bytes(wav, params))
+    }
+
+    /// Does nothing: there is no local session to warm up, and a request
+    /// would be a billed call to the remote service.
+    fn warm_up(&mut self) -> Result<(), TranscribeError> {
+        Ok(())
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl RemoteTranscriptionEngine for crate::AsyncSpeechModel {
+    type RequestParams = TranscribeOptions;
+
+    async fn transcribe_file(
+        &self,
+        wav_path: &std::path::Path,
+        params: TranscribeOptions,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        crate::AsyncSpeechModel::transcribe_file(self, wav_path, params).await
+    }
+
+    async fn transcribe_wav_bytes(
+        &self,
+        wav: Vec<u8>,
+        params: TranscribeOptions,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        let samples = audio::decode_wav(&wav)?;
+        self.transcribe(samples, params).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    /// Echoes the language and the number of uploaded samples.
+    struct EchoEngine;
+
+    #[async_trait::async_trait]
+    impl RemoteTranscriptionEngine for EchoEngine {
+        type RequestParams = String;
+
+        async fn transcribe_file(
+            &self,
+            wav_path: &Path,
+            language: String,
+        ) -> Result<TranscriptionResult, TranscribeError> {
+            let samples = audio::read_wav_samples(wav_path)?;
+            Ok(TranscriptionResult {
+                text: format!("{language}:{}", samples.len()),
+                segments: None,
+            })
+        }
+    }
+
+    /// Fails every request.
+    struct OfflineEngine;
+
+    #[async_trait::async_trait]
+    impl RemoteTranscriptionEngine for OfflineEngine {
+        type RequestParams = ();
+
+        async fn transcribe_file(
+            &self,
+            _wav_path: &Path,
+            _params: (),
+        ) -> Result<TranscriptionResult, TranscribeError> {
+            Err(TranscribeError::Inference("offline".into()))
+        }
+    }
+
+    fn capabilities() -> ModelCapabilities {
+        ModelCapabilities {
+            name: "Echo",
+            engine_id: "echo",
+            sample_rate: 16000,
+            languages: &[],
+            supports_timestamps: false,
+            supports_word_timestamps: false,
+            supports_translation: false,
+            supports_streaming: false,
+        }
+    }
+
+    #[test]
+    fn remote_engine_as_speech_model() {
+        let mut model = RemoteSpeechModel::new(EchoEngine, capabilities(), |options| {
+            options.language.clone().unwrap_or_default()
+        })
+        .unwrap();
+
+        let options = TranscribeOptions {
+            language: Some("de".into()),
+            ..Default::default()
+        };
+        let result = model.transcribe(&[0.1; 1600], &options).unwrap();
+        assert_eq!(result.text, "de:1600");
+    }
+
+    #[test]
+    fn warm_up_sends_no_request() {
+        let mut model = RemoteSpeechModel::new(OfflineEngine, capabilities(), |_| ()).unwrap();
+        model.warm_up().unwrap();
+        assert!(model
+            .transcribe(&[0.0; 1600], &TranscribeOptions::default())
+            .is_err());
+    }
+}
@@ -1,11 +1,19 @@
-use std::path::Path;
+use std::collections::hash_map::RandomState;
+use std::fs::{File, OpenOptions};
+use std::hash::{BuildHasher, Hasher};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 use async_trait::async_trait;
 
 use crate::{TranscribeError, TranscriptionResult};
 
+mod bridge;
 pub mod openai;
 
+pub use bridge::RemoteSpeechModel;
+
 /// Common interface for speech transcription through remote APIs.
 ///
 /// Unlike local inference engines, remote APIs can handle concurren requests
@@ -19,4 +27,70 @@ pub trait RemoteTranscriptionEngine: Send + Sync {
         wav_path: &Path,
         params: Self::RequestParams,
     ) -> Result<TranscriptionResult, TranscribeError>;
+
+    /// Transcribe an in-memory WAV file.
+    ///
+    /// The default implementation writes it to a temporary file and calls
+    /// [`transcribe_file`](Self::transcribe_file). Engines that can upload
+    /// bytes directly should override it.
+    async fn transcribe_wav_bytes(
+        &self,
+        wav: Vec<u8>,
+        params: Self::RequestParams,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        let (path, mut file) = create_temp_wav()?;
+        let written = file.write_all(&wav);
+        drop(file);
+        let result = match written {
+            Ok(()) => self.transcribe_file(&path, params).await,
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = std::fs::remove_file(&path) {
+            log::warn!("failed to remove {}: {e}", path.display());
+        }
+        result
+    }
+}
+
+/// Create a new, owner-only file in the system temp directory for an upload.
+///
+/// The name has a random part and the file must not exist yet, so a file or
+/// symlink planted at a guessable path in a shared temp directory is never
+/// written through.
+fn create_temp_wav() -> io::Result<(PathBuf, File)> {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+    let mut attempts = 0;
+    loop {
+        let random = RandomState::new().build_hasher().finish();
+        let path = std::env::temp_dir().join(format!(
+            "transcribe-rs-{}-{}-{random:016x}.wav",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+        match options.open(&path) {
+            Ok(file) => return Ok((path, file)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists && attempts < 8 => attempts += 1,
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn temp_wav_files_are_new_and_distinct() {
+        let (a, _) = create_temp_wav().unwrap();
+        let (b, _) = create_temp_wav().unwrap();
+        assert_ne!(a, b);
+        for path in [a, b] {
+            assert!(std::fs::symlink_metadata(&path).unwrap().is_file());
+            std::fs::remove_file(path).unwrap();
+        }
+    }
 }
@@ -49,8 +49,10 @@ use async_openai::{
 use async_trait::async_trait;
 use derive_builder::Builder;
 
+use super::RemoteSpeechModel;
 use crate::{
-    RemoteTranscriptionEngine, TranscribeError, TranscriptionResult, TranscriptionSegment,
+    ModelCapabilities, RemoteTranscriptionEngine, TimestampGranularity, TranscribeError,
+    TranscriptionResult, TranscriptionSegment,
 };
 
 #[derive(Debug)]
@@ -80,6 +82,39 @@ pub fn default_engine() -> OpenAIEngine<OpenAIConfig> {
 
 pub use async_openai::types::TimestampGranularity as OpenAITimestampGranularity;
 
+/// Use an OpenAI model as a [`SpeechModel`](crate::SpeechModel).
+///
+/// The language of each call is passed through. `whisper-1` returns segment
+/// timestamps; the GPT-4o models return text only.
+pub fn speech_model<T>(
+    engine: OpenAIEngine<T>,
+    model: OpenAIModel,
+) -> Result<RemoteSpeechModel<OpenAIEngine<T>>, TranscribeError>
+where
+    T: async_openai::config::Config,
+{
+    let supports_timestamps = matches!(model, OpenAIModel::Whisper1);
+    let capabilities = ModelCapabilities {
+        name: model.as_str(),
+        engine_id: "openai",
+        sample_rate: 16000,
+        languages: &[],
+        supports_timestamps,
+        supports_word_timestamps: supports_timestamps,
+        supports_translation: false,
+        supports_streaming: false,
+    };
+    RemoteSpeechModel::new(engine, capabilities, move |options| OpenAIRequestParams {
+        model: model.clone(),
+        language: options.language.clone(),
+        timestamp_granularity: supports_timestamps.then_some(match options.timestamp_granularity {
+            TimestampGranularity::Word => OpenAITimestampGranularity::Word,
+            _ => OpenAITimestampGranularity::Segment,
+        }),
+        ..Default::default()
+    })
+}
+
 /// https://docs.rs/async-openai/latest/src/async_openai/types/audio.rs.html#72-99
 #[derive(Builder, Debug)]
 #[builder(setter(into), default)]
@@ -153,11 +188,36 @@ where
         wav_path: &std::path::Path,
         params: Self::RequestParams,
     ) -> Result<crate::TranscriptionResult, TranscribeError> {
-        let source = AudioInput {
-            source: InputSource::Path {
-                path: wav_path.to_path_buf(),
-            },
+        let source = InputSource::Path {
+            path: wav_path.to_path_buf(),
         };
+        self.transcribe_source(source, params).await
+    }
+
+    /// Uploads the bytes directly, without a temporary file.
+    async fn transcribe_wav_bytes(
+        &self,
+        wav: Vec<u8>,
+        params: Self::RequestParams,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        let source = InputSource::VecU8 {
+            filename: "audio.wav".to_string(),
+            vec: wav,
+        };
+        self.transcribe_source(source, params).await
+    }
+}
+
+impl<T> OpenAIEngine<T>
+where
+    T: async_openai::config::Config,
+{
+    async fn transcribe_source(
+        &self,
+        source: InputSource,
+        params: OpenAIRequestParams,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        let source = AudioInput { source };
 
         let mut request = CreateTranscriptionRequestArgs::default();
 
@@ -0,0 +1,682 @@
+//! OpenAI-compatible HTTP transcription server.
+//!
+//! Requires the `server` feature. [`TranscriptionServer`] serves local
+//! models over OpenAI's audio API, so existing OpenAI SDK clients can be
+//! pointed at a self-hosted Parakeet or Canary by changing the base URL:
+//!
+//! - `POST /v1/audio/transcriptions`
+//! - `POST /v1/audio/translations` (models that support translation)
+//! - `GET /v1/models`
+//!
+//! Requests are `multipart/form-data` with OpenAI's fields: `file`,
+//! `model`, `language`, `prompt`, `response_format` (`json`, `text`,
+//! `srt`, `vtt` or `verbose_json`) and `timestamp_granularities[]` (`word`,
+//! `segment`). `prompt` and `temperature` are accepted and ignored. The
+//! uploaded file must be a 16 kHz mono 16-bit WAV. `verbose_json` only
+//! includes `words` for models whose [`ModelCapabilities`] report
+//! `supports_word_timestamps`.
+//!
+//! Each model runs on its own [`AsyncSpeechModel`] thread. Uploads longer
+//! than [`ServerConfig::chunk_threshold_secs`] are split with a
+//! [`Transcriber`] ([`EnergyAdaptiveChunked`] unless set with
+//! [`TranscriptionServer::with_transcriber`]).
+//!
+//! ```ignore
+//! use transcribe_rs::server::TranscriptionServer;
+//! use transcribe_rs::{AsyncSpeechModel, ModelSpec};
+//!
+//! let model = AsyncSpeechModel::new(ModelSpec::new("models/parakeet-v3").load()?)?;
+//! let listener = tokio::net::TcpListener::bind("127.0.0.1:8000").await?;
+//! TranscriptionServer::new("parakeet", model).serve(listener).await?;
+//! // client: OpenAI(base_url="http://127.0.0.1:8000/v1", api_key="unused")
+//! ```
+
+use std::sync::Arc;
+
+use axum::extract::{DefaultBodyLimit, Multipart, State};
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::{Json, Router};
+use serde_json::{json, Value};
+
+use crate::transcriber::{EnergyAdaptiveChunked, EnergyAdaptiveConfig, Transcriber};
+use crate::{
+    audio, subtitle, AsyncSpeechModel, TimestampGranularity, TranscribeError, TranscribeOptions,
+    TranscriptionResult, TranscriptionSegment,
+};
+
+const SAMPLE_RATE: f32 = 16000.0;
+
+/// Limits of a [`TranscriptionServer`].
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    /// Largest accepted request body. Default: 25 MiB, OpenAI's limit.
+    pub max_upload_bytes: usize,
+    /// Uploads longer than this many seconds are transcribed in chunks.
+    /// Default: 30.
+    pub chunk_threshold_secs: f32,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            max_upload_bytes: 25 * 1024 * 1024,
+            chunk_threshold_secs: 30.0,
+        }
+    }
+}
+
+type TranscriberFactory =
+    dyn Fn(&TranscribeOptions) -> Result<Box<dyn Transcriber>, TranscribeError> + Send + Sync;
+
+/// OpenAI-compatible server for one or more local models.
+pub struct TranscriptionServer {
+    models: Vec<(String, Arc<AsyncSpeechModel>)>,
+    config: ServerConfig,
+    transcriber: Arc<TranscriberFactory>,
+}
+
+impl TranscriptionServer {
+    /// Serve `model` as `name`. Requests naming an unknown model (such as
+    /// the SDKs' `whisper-1`) go to the first model.
+    pub fn new(name: impl Into<String>, model: AsyncSpeechModel) -> Self {
+        Self {
+            models: vec![(name.into(), Arc::new(model))],
+            config: ServerConfig::default(),
+            transcriber: Arc::new(|options| {
+                Ok(Box::new(EnergyAdaptiveChunked::new(
+                    EnergyAdaptiveConfig::default(),
+                    options.clone(),
+                )))
+            }),
+        }
+    }
+
+    /// Also serve `model` as `name`.
+    pub fn with_model(mut self, name: impl Into<String>, model: AsyncSpeechModel) -> Self {
+        self.models.push((name.into(), Arc::new(model)));
+        self
+    }
+
+    pub fn with_config(mut self, config: ServerConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Build the [`Transcriber`] for long uploads from the request's options,
+    /// e.g. a [`VadChunked`](crate::transcriber::VadChunked).
+    pub fn with_transcriber(
+        mut self,
+        factory: impl Fn(&TranscribeOptions) -> Result<Box<dyn Transcriber>, TranscribeError>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.transcriber = Arc::new(factory);
+        self
+    }
+
+    /// The routes, for mounting in a larger axum app.
+    pub fn router(self) -> Router {
+        let limit = self.config.max_upload_bytes;
+        Router::new()
+            .route("/v1/audio/transcriptions", post(transcriptions))
+            .route("/v1/audio/translations", post(translations))
+            .route("/v1/models", get(models))
+            .layer(DefaultBodyLimit::max(limit))
+            .with_state(Arc::new(self))
+    }
+
+    /// Serve on `listener` until the process exits.
+    pub async fn serve(self, listener: tokio::net::TcpListener) -> std::io::Result<()> {
+        axum::serve(listener, self.router()).await
+    }
+
+    fn model(&self, name: Option<&str>) -> (&str, &AsyncSpeechModel) {
+        let (name, model) = name
+            .and_then(|name| self.models.iter().find(|(n, _)| n == name))
+            .unwrap_or(&self.models[0]);
+        (name, model)
+    }
+
+    async fn transcribe(
+        &self,
+        model: &AsyncSpeechModel,
+        samples: Vec<f32>,
+        options: TranscribeOptions,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        if samples.len() as f32 / SAMPLE_RATE > self.config.chunk_threshold_secs {
+            let transcriber = (self.transcriber)(&options)?;
+            model.transcribe_chunked(samples, transcriber).await
+        } else {
+            model.transcribe(samples, options).await
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ResponseFormat {
+    Json,
+    Text,
+    Srt,
+    Vtt,
+    VerboseJson,
+}
+
+/// The multipart form of a transcription or translation request.
+#[derive(Default)]
+struct AudioRequest {
+    file: Option<Vec<u8>>,
+    model: Option<String>,
+    language: Option<String>,
+    response_format: Option<String>,
+    timestamp_granularities: Vec<String>,
+}
+
+impl AudioRequest {
+    async fn read(mut multipart: Multipart) -> Result<Self, ApiError> {
+        let mut request = Self::default();
+        while let Some(field) = multipart.next_field().await.map_err(multipart_error)? {
+            let name = field.name().unwrap_or_default().to_string();
+            if name == "file" {
+                request.file = Some(field.bytes().await.map_err(multipart_error)?.to_vec());
+                continue;
+            }
+            let value = field.text().await.map_err(multipart_error)?;
+            match name.as_str() {
+                "model" => request.model = Some(value),
+                "language" if !value.is_empty() => request.language = Some(value),
+                "response_format" => request.response_format = Some(value),
+                "timestamp_granularities[]" | "timestamp_granularities" => {
+                    request.timestamp_granularities.push(value)
+                }
+                "prompt" | "temperature" => log::debug!("ignoring `{name}`"),
+                _ => {}
+            }
+        }
+        Ok(request)
+    }
+
+    fn response_format(&self) -> Result<ResponseFormat, ApiError> {
+        match self.response_format.as_deref().unwrap_or("json") {
+            "json" => Ok(ResponseFormat::Json),
+            "text" => Ok(ResponseFormat::Text),
+            "srt" => Ok(ResponseFormat::Srt),
+            "vtt" => Ok(ResponseFormat::Vtt),
+            "verbose_json" => Ok(ResponseFormat::VerboseJson),
+            other => Err(ApiError::invalid(
+                format!("unsupported response_format `{other}`"),
+                "response_format",
+            )),
+        }
+    }
+
+    /// Whether word and segment timestamps were asked for.
+    fn granularities(&self) -> Result<(bool, bool), ApiError> {
+        let mut words = false;
+        let mut segments = false;
+        for g in &self.timestamp_granularities {
+            match g.as_str() {
+                "word" => words = true,
+                "segment" => segments = true,
+                other => {
+                    return Err(ApiError::invalid(
+                        format!("unsupported timestamp granularity `{other}`"),
+                        "timestamp_granularities",
+                    ))
+                }
+            }
+        }
+        Ok((words, segments || !words))
+    }
+}
+
+async fn transcriptions(
+    State(server): State<Arc<TranscriptionServer>>,
+    multipart: Multipart,
+) -> Result<Response, ApiError> {
+    handle(&server, multipart, false).await
+}
+
+async fn translations(
+    State(server): State<Arc<TranscriptionServer>>,
+    multipart: Multipart,
+) -> Result<Response, ApiError> {
+    handle(&server, multipart, true).await
+}
+
+async fn models(State(server): State<Arc<TranscriptionServer>>) -> Json<Value> {
+    let data: Vec<Value> = server
+        .models
+        .iter()
+        .map(|(name, _)| json!({ "id": name, "object": "model", "created": 0, "owned_by": "transcribe-rs" }))
+        .collect();
+    Json(json!({ "object": "list", "data": data }))
+}
+
+async fn handle(
+    server: &TranscriptionServer,
+    multipart: Multipart,
+    translate: bool,
+) -> Result<Response, ApiError> {
+    let request = AudioRequest::read(multipart).await?;
+    let format = request.response_format()?;
+    let (words, segments) = request.granularities()?;
+    let file = request
+        .file
+        .as_deref()
+        .ok_or_else(|| ApiError::invalid("`file` is required".to_string(), "file"))?;
+
+    let (name, model) = server.model(request.model.as_deref());
+    let capabilities = model.capabilities();
+    // Models without word timings return segments whatever the
+    // granularity, so `words` is left out for them.
+    let words = words && capabilities.supports_word_timestamps;
+    if translate && !capabilities.supports_translation {
+        return Err(ApiError::invalid(
+            format!("model `{name}` does not support translation"),
+            "model",
+        ));
+    }
+
+    let samples = audio::decode_wav(file).map_err(|e| {
+        ApiError::invalid(format!("{e}; upload a 16 kHz mono 16-bit WAV file"), "file")
+    })?;
+    let duration = samples.len() as f32 / SAMPLE_RATE;
+
+    let options = TranscribeOptions {
+        language: if translate {
+            None
+        } else {
+            request.language.clone()
+        },
+        translate,
+        timestamp_granularity: Some(if words {
+            TimestampGranularity::Word
+        } else {
+            TimestampGranularity::Segment
+        }),
+        ..Default::default()
+    };
+    let result = server.transcribe(model, samples, options).await?;
+    let text = result.text.trim();
+
+    let response = match format {
+        ResponseFormat::Json => Json(json!({ "text": text })).into_response(),
+        ResponseFormat::Text => plain("text/plain; charset=utf-8", format!("{text}\n")),
+        ResponseFormat::Srt => plain(
+            "text/plain; charset=utf-8",
+            subtitle::to_srt(&result, duration),
+        ),
+        ResponseFormat::Vtt => plain(
+            "text/vtt; charset=utf-8",
+            subtitle::to_vtt(&result, duration),
+        ),
+        ResponseFormat::VerboseJson => {
+            let language = match (translate, &request.language, capabilities.languages) {
+                (true, _, _) => "en",
+                (false, Some(language), _) => language.as_str(),
+                (false, None, [only]) => only,
+                _ => "unknown",
+            };
+            let mut body = json!({
+                "task": if translate { "translate" } else { "transcribe" },
+                "language": language,
+                "duration": duration,
+                "text": text,
+            });
+            if words {
+                body["words"] = word_objects(&result);
+            }
+            if segments {
+                // Engines return one granularity per call; rebuild
+                // sentences from the words rather than running twice.
+                let sentences;
+                let segmented = if words {
+                    sentences = sentences_from_words(&result);
+                    &sentences
+                } else {
+                    &result
+                };
+                body["segments"] = segment_objects(segmented);
+            }
+            Json(body).into_response()
+        }
+    };
+    Ok(response)
+}
+
+fn plain(content_type: &'static str, body: String) -> Response {
+    ([(header::CONTENT_TYPE, content_type)], body).into_response()
+}
+
+fn word_objects(result: &TranscriptionResult) -> Value {
+    let words = result
+        .segments
+        .iter()
+        .flatten()
+        .map(|s| json!({ "word": s.text.trim(), "start": s.start, "end": s.end }));
+    Value::Array(words.collect())
+}
+
+/// Group word segments into sentences, ending one after each word with
+/// sentence punctuation.
+fn sentences_from_words(result: &TranscriptionResult) -> TranscriptionResult {
+    let mut sentences: Vec<TranscriptionSegment> = Vec::new();
+    let mut open = false;
+    for word in result.segments.iter().flatten() {
+        let text = word.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        match sentences.last_mut() {
+            Some(sentence) if open => {
+                sentence.text.push(' ');
+                sentence.text.push_str(text);
+                sentence.end = word.end;
+            }
+            _ => sentences.push(TranscriptionSegment {
+                start: word.start,
+                end: word.end,
+                text: text.to_string(),
+            }),
+        }
+        open = !text.ends_with(['.', '?', '!']);
+    }
+    TranscriptionResult {
+        text: result.text.clone(),
+        segments: Some(sentences),
+    }
+}
+
+/// Segments in OpenAI's shape; fields the engines don't produce are zero.
+fn segment_objects(result: &TranscriptionResult) -> Value {
+    let segments = result.segments.iter().flatten().enumerate().map(|(id, s)| {
+        json!({
+            "id": id,
+            "seek": 0,
+            "start": s.start,
+            "end": s.end,
+            "text": s.text,
+            "tokens": [],
+            "temperature": 0.0,
+            "avg_logprob": 0.0,
+            "compression_ratio": 0.0,
+            "no_speech_prob": 0.0,
+        })
+    });
+    Value::Array(segments.collect())
+}
+
+/// An error in OpenAI's JSON shape.
+#[derive(Debug)]
+struct ApiError {
+    status: StatusCode,
+    message: String,
+    param: Option<&'static str>,
+}
+
+impl ApiError {
+    fn invalid(message: String, param: &'static str) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            message,
+            param: Some(param),
+        }
+    }
+}
+
+fn multipart_error(e: axum::extract::multipart::MultipartError) -> ApiError {
+    ApiError {
+        status: e.status(),
+        message: e.body_text(),
+        param: None,
+    }
+}
+
+impl From<TranscribeError> for ApiError {
+    fn from(e: TranscribeError) -> Self {
+        let status = match e {
+            TranscribeError::Audio(_) | TranscribeError::Config(_) => StatusCode::BAD_REQUEST,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+        Self {
+            status,
+            message: e.to_string(),
+            param: None,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let kind = if self.status.is_client_error() {
+            "invalid_request_error"
+        } else {
+            "server_error"
+        };
+        if self.status.is_server_error() {
+            log::error!("{}", self.message);
+        }
+        let body = json!({
+            "error": {
+                "message": self.message,
+                "type": kind,
+                "param": self.param,
+                "code": null,
+            }
+        });
+        (self.status, Json(body)).into_response()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcriber::test_helpers::MockModel;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+
+    /// POST a multipart form and return the raw HTTP response.
+    fn post(addr: std::net::SocketAddr, path: &str, fields: &[(&str, &[u8])]) -> String {
+        let mut body = Vec::new();
+        for (name, value) in fields {
+            let filename = if *name == "file" {
+                "; filename=\"a.wav\""
+            } else {
+                ""
+            };
+            write!(
+                body,
+                "--XYZ\r\nContent-Disposition: form-data; name=\"{name}\"{filename}\r\n\r\n"
+            )
+            .unwrap();
+            body.extend_from_slice(value);
+            body.extend_from_slice(b"\r\n");
+        }
+        body.extend_from_slice(b"--XYZ--\r\n");
+
+        let mut stream = TcpStream::connect(addr).unwrap();
+        write!(
+            stream,
+            "POST {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\
+             Content-Type: multipart/form-data; boundary=XYZ\r\nContent-Length: {}\r\n\r\n",
+            body.len()
+        )
+        .unwrap();
+        stream.write_all(&body).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn sentences_from_words_splits_after_punctuation() {
+        let word = |text: &str, start: f32| TranscriptionSegment {
+            start,
+            end: start + 0.5,
+            text: text.into(),
+        };
+        let result = TranscriptionResult {
+            text: "Hi there. Bye".into(),
+            segments: Some(vec![
+                word(" Hi", 0.0),
+                word("there.", 0.5),
+                word("Bye", 1.5),
+            ]),
+        };
+        let sentences = sentences_from_words(&result).segments.unwrap();
+        let sentences: Vec<_> = sentences
+            .iter()
+            .map(|s| (s.text.as_str(), s.start, s.end))
+            .collect();
+        assert_eq!(sentences, [("Hi there.", 0.0, 1.0), ("Bye", 1.5, 2.0)]);
+    }
+
+    /// Returns one segment per word when asked for word timings.
+    struct WordModel;
+
+    impl crate::SpeechModel for WordModel {
+        fn capabilities(&self) -> crate::ModelCapabilities {
+            crate::ModelCapabilities {
+                supports_word_timestamps: true,
+                ..MockModel.capabilities()
+            }
+        }
+
+        fn transcribe_raw(
+            &mut self,
+            _samples: &[f32],
+            options: &TranscribeOptions,
+        ) -> Result<TranscriptionResult, TranscribeError> {
+            assert_eq!(
+                options.timestamp_granularity,
+                Some(TimestampGranularity::Word)
+            );
+            let word = |text: &str, start: f32| TranscriptionSegment {
+                start,
+                end: start + 0.05,
+                text: text.into(),
+            };
+            Ok(TranscriptionResult {
+                text: "Hi there.".into(),
+                segments: Some(vec![word("Hi", 0.0), word("there.", 0.05)]),
+            })
+        }
+    }
+
+    #[test]
+    fn includes_words_for_models_with_word_timings() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let server =
+            TranscriptionServer::new("words", AsyncSpeechModel::new(Box::new(WordModel)).unwrap());
+        let listener = runtime
+            .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+            .unwrap();
+        let addr = listener.local_addr().unwrap();
+        runtime.spawn(server.serve(listener));
+
+        let response = post(
+            addr,
+            "/v1/audio/transcriptions",
+            &[
+                ("file", &audio::encode_wav(&[0.0; 1600]).unwrap()),
+                ("response_format", b"verbose_json"),
+                ("timestamp_granularities[]", b"word"),
+                ("timestamp_granularities[]", b"segment"),
+            ],
+        );
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.contains(r#""words":[{"#), "{response}");
+        assert!(response.contains(r#""word":"there.""#), "{response}");
+        assert!(response.contains(r#""text":"Hi there.""#), "{response}");
+    }
+
+    #[test]
+    fn serves_openai_audio_api() {
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let server =
+            TranscriptionServer::new("mock", AsyncSpeechModel::new(Box::new(MockModel)).unwrap())
+                .with_config(ServerConfig {
+                    chunk_threshold_secs: 2.0,
+                    ..Default::default()
+                })
+                .with_transcriber(|options| {
+                    let config = EnergyAdaptiveConfig {
+                        target_chunk_secs: 1.0,
+                        search_window_secs: 0.0,
+                        ..Default::default()
+                    };
+                    Ok(Box::new(EnergyAdaptiveChunked::new(
+                        config,
+                        options.clone(),
+                    )))
+                });
+        let listener = runtime
+            .block_on(tokio::net::TcpListener::bind("127.0.0.1:0"))
+            .unwrap();
+        let addr = listener.local_addr().unwrap();
+        runtime.spawn(server.serve(listener));
+
+        let short = audio::encode_wav(&[0.0; 1600]).unwrap();
+        let response = post(
+            addr,
+            "/v1/audio/transcriptions",
+            &[
+                ("model", b"whisper-1"),
+                ("file", &short),
+                ("response_format", b"verbose_json"),
+                ("timestamp_granularities[]", b"word"),
+            ],
+        );
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(response.contains(r#""text":"chunk_1600""#), "{response}");
+        // The mock has no word timings, so no `words`.
+        assert!(!response.contains("words"), "{response}");
+        assert!(!response.contains("segments"), "{response}");
+
+        let response = post(
+            addr,
+            "/v1/audio/transcriptions",
+            &[
+                ("file", &short),
+                ("response_format", b"verbose_json"),
+                ("timestamp_granularities[]", b"word"),
+                ("timestamp_granularities[]", b"segment"),
+            ],
+        );
+        assert!(response.starts_with("HTTP/1.1 200"), "{response}");
+        assert!(!response.contains("words"), "{response}");
+        assert!(response.contains(r#""segments":[{"#), "{response}");
+
+        let long = audio::encode_wav(&[0.0; 40000]).unwrap();
+        let response = post(
+            addr,
+            "/v1/audio/transcriptions",
+            &[("file", &long), ("response_format", b"text")],
+        );
+        assert!(
+            response.ends_with("\r\n\r\nchunk_16000 chunk_16000 chunk_8000\n"),
+            "{response}"
+        );
+
+        let response = post(
+            addr,
+            "/v1/audio/transcriptions",
+            &[("file", &short), ("response_format", b"docx")],
+        );
+        assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+        assert!(
+            response.contains(r#""param":"response_format""#),
+            "{response}"
+        );
+
+        let response = post(addr, "/v1/audio/translations", &[("file", &short)]);
+        assert!(response.starts_with("HTTP/1.1 400"), "{response}");
+        assert!(
+            response.contains("does not support translation"),
+            "{response}"
+        );
+    }
+}
@@ -0,0 +1,318 @@
+//! Engine-independent stream bookkeeping shared by all
+//! [`StreamingSpeechModel`](super::StreamingSpeechModel) implementations.
+//!
+//! [`StreamDriver`] gates audio into utterances, schedules hypothesis
+//! updates, applies [`LocalAgreement`] and accumulates final results. The
+//! engine only supplies an [`UtteranceDecoder`].
+
+use crate::transcriber::{merge_sequential_with_separator, rms_energy, SAMPLE_RATE};
+use crate::{TranscribeError, TranscriptionResult};
+
+use super::{LocalAgreement, StreamEvent, StreamOptions};
+
+/// Frame size for energy-based onset/endpoint detection (30ms at 16kHz).
+const ENERGY_FRAME_SIZE: usize = 480;
+
+/// Engine side of a stream: decodes audio of the current utterance.
+pub(crate) trait UtteranceDecoder {
+    /// Append audio to the current utterance.
+    fn append(&mut self, samples: &[f32]) -> Result<(), TranscribeError>;
+
+    /// Decode the utterance so far. May be called repeatedly as audio grows.
+    fn hypothesis(&mut self) -> Result<String, TranscribeError>;
+
+    /// Decode the utterance as final and reset for the next one.
+    /// Timestamps are relative to the start of the utterance.
+    fn finish_utterance(&mut self) -> Result<TranscriptionResult, TranscribeError>;
+}
+
+/// Output of [`UtteranceTracker::push`].
+#[derive(Debug, PartialEq)]
+pub(crate) enum UtteranceStep {
+    /// Audio belonging to the current utterance.
+    Audio(Vec<f32>),
+    /// The current utterance ended. `start_secs` is its position in the stream.
+    End { start_secs: f32 },
+}
+
+/// Energy-based utterance segmentation of a live stream.
+///
+/// Audio before speech onset is held in a pre-roll window; audio from the
+/// onset on is emitted as [`UtteranceStep::Audio`] until trailing silence
+/// or the maximum utterance length ends it.
+pub(crate) struct UtteranceTracker {
+    threshold_rms: f32,
+    endpoint_samples: Option<usize>,
+    max_samples: usize,
+    pre_roll_samples: usize,
+    pre_roll: Vec<f32>,
+    /// Sub-frame remainder carried to the next `push()`.
+    pending: Vec<f32>,
+    in_utterance: bool,
+    utterance_start_sample: usize,
+    utterance_samples: usize,
+    trailing_silence: usize,
+    elapsed_samples: usize,
+}
+
+impl UtteranceTracker {
+    pub(crate) fn new(options: &StreamOptions) -> Self {
+        Self {
+            threshold_rms: options.silence_threshold_rms,
+            endpoint_samples: options
+                .endpoint_silence_secs
+                .map(|s| (s * SAMPLE_RATE) as usize),
+            max_samples: ((options.max_utterance_secs * SAMPLE_RATE) as usize)
+                .max(ENERGY_FRAME_SIZE),
+            pre_roll_samples: (options.pre_roll_secs * SAMPLE_RATE) as usize,
+            pre_roll: Vec::new(),
+            pending: Vec::new(),
+            in_utterance: false,
+            utterance_start_sample: 0,
+            utterance_samples: 0,
+            trailing_silence: 0,
+            elapsed_samples: 0,
+        }
+    }
+
+    pub(crate) fn push(&mut self, samples: &[f32]) -> Vec<UtteranceStep> {
+        let mut steps = Vec::new();
+        let mut audio: Vec<f32> = Vec::new();
+
+        self.pending.extend_from_slice(samples);
+        let pending = std::mem::take(&mut self.pending);
+        for frame in pending.chunks(ENERGY_FRAME_SIZE) {
+            if frame.len() < ENERGY_FRAME_SIZE {
+                self.pending.extend_from_slice(frame);
+                continue;
+            }
+            self.elapsed_samples += ENERGY_FRAME_SIZE;
+            let is_speech = rms_energy(frame) > self.threshold_rms;
+
+            if !self.in_utterance {
+                if !is_speech {
+                    self.pre_roll.extend_from_slice(frame);
+                    let excess = self.pre_roll.len().saturating_sub(self.pre_roll_samples);
+                    self.pre_roll.drain(..excess);
+                    continue;
+                }
+                self.in_utterance = true;
+                self.trailing_silence = 0;
+                self.utterance_start_sample =
+                    self.elapsed_samples - ENERGY_FRAME_SIZE - self.pre_roll.len();
+                self.utterance_samples = self.pre_roll.len();
+                audio.append(&mut self.pre_roll);
+            }
+
+            audio.extend_from_slice(frame);
+            self.utterance_samples += ENERGY_FRAME_SIZE;
+            if is_speech {
+                self.trailing_silence = 0;
+            } else {
+                self.trailing_silence += ENERGY_FRAME_SIZE;
+            }
+
+            let silence_ended = self
+                .endpoint_samples
+                .is_some_and(|n| self.trailing_silence >= n);
+            if silence_ended || self.utterance_samples >= self.max_samples {
+                if !audio.is_empty() {
+                    steps.push(UtteranceStep::Audio(std::mem::take(&mut audio)));
+                }
+                steps.push(UtteranceStep::End {
+                    start_secs: self.utterance_start_sample as f32 / SAMPLE_RATE,
+                });
+                self.in_utterance = false;
+            }
+        }
+
+        if !audio.is_empty() {
+            steps.push(UtteranceStep::Audio(audio));
+        }
+        steps
+    }
+
+    /// End the stream. Returns the unclassified remainder and the start of
+    /// the current utterance if one is in progress.
+    pub(crate) fn flush(&mut self) -> Option<(Vec<f32>, f32)> {
+        let pending = std::mem::take(&mut self.pending);
+        self.pre_roll.clear();
+        if !self.in_utterance {
+            return None;
+        }
+        self.in_utterance = false;
+        Some((pending, self.utterance_start_sample as f32 / SAMPLE_RATE))
+    }
+}
+
+/// Drives one stream: segmentation, update cadence, commit policy and
+/// session result accumulation.
+pub(crate) struct StreamDriver {
+    tracker: UtteranceTracker,
+    agreement: LocalAgreement,
+    update_interval_samples: usize,
+    samples_since_update: usize,
+    merge_separator: String,
+    results: Vec<TranscriptionResult>,
+}
+
+impl StreamDriver {
+    pub(crate) fn new(options: &StreamOptions) -> Self {
+        Self {
+            tracker: UtteranceTracker::new(options),
+            agreement: LocalAgreement::new(),
+            update_interval_samples: ((options.update_interval_secs * SAMPLE_RATE) as usize).max(1),
+            samples_since_update: 0,
+            merge_separator: options.merge_separator.clone(),
+            results: Vec::new(),
+        }
+    }
+
+    pub(crate) fn push(
+        &mut self,
+        decoder: &mut dyn UtteranceDecoder,
+        samples: &[f32],
+    ) -> Result<Vec<StreamEvent>, TranscribeError> {
+        let mut events = Vec::new();
+        let mut steps = self.tracker.push(samples).into_iter().peekable();
+        while let Some(step) = steps.next() {
+            match step {
+                UtteranceStep::Audio(audio) => {
+                    decoder.append(&audio)?;
+                    self.samples_since_update += audio.len();
+                    // No partial for an utterance that ends in this same push.
+                    let ending = matches!(steps.peek(), Some(UtteranceStep::End { .. }));
+                    if !ending && self.samples_since_update >= self.update_interval_samples {
+                        self.update(decoder, &mut events)?;
+                    }
+                }
+                UtteranceStep::End { start_secs } => {
+                    events.push(self.end_utterance(decoder, start_secs)?);
+                }
+            }
+        }
+        Ok(events)
+    }
+
+    /// Flush the last utterance and return the merged session result.
+    pub(crate) fn finalize(
+        &mut self,
+        decoder: &mut dyn UtteranceDecoder,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        if let Some((remainder, start_secs)) = self.tracker.flush() {
+            if !remainder.is_empty() {
+                decoder.append(&remainder)?;
+            }
+            self.end_utterance(decoder, start_secs)?;
+        }
+        Ok(merge_sequential_with_separator(
+            &self.results,
+            &self.merge_separator,
+        ))
+    }
+
+    fn update(
+        &mut self,
+        decoder: &mut dyn UtteranceDecoder,
+        events: &mut Vec<StreamEvent>,
+    ) -> Result<(), TranscribeError> {
+        self.samples_since_update = 0;
+        let hypothesis = decoder.hypothesis()?;
+        let committed = self.agreement.update(&hypothesis);
+        if !committed.is_empty() {
+            events.push(StreamEvent::Stable {
+                text: committed.join(" "),
+            });
+        }
+        events.push(StreamEvent::Partial {
+            stable: self.agreement.committed().join(" "),
+            unstable: self.agreement.unstable().join(" "),
+        });
+        Ok(())
+    }
+
+    fn end_utterance(
+        &mut self,
+        decoder: &mut dyn UtteranceDecoder,
+        start_secs: f32,
+    ) -> Result<StreamEvent, TranscribeError> {
+        self.agreement.reset();
+        self.samples_since_update = 0;
+        let mut result = decoder.finish_utterance()?;
+        result.offset_timestamps(start_secs);
+        log::debug!(
+            "stream endpoint at {:.2}s: \"{}\"",
+            start_secs,
+            result.text.trim()
+        );
+        self.results.push(result.clone());
+        Ok(StreamEvent::Endpoint { result })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> StreamOptions {
+        StreamOptions {
+            endpoint_silence_secs: Some(0.09), // 3 frames
+            pre_roll_secs: 0.06,               // 2 frames
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tracker_ignores_silence() {
+        let mut t = UtteranceTracker::new(&options());
+        assert!(t.push(&vec![0.0; 480 * 10]).is_empty());
+        assert!(!t.in_utterance);
+        assert!(t.flush().is_none());
+    }
+
+    #[test]
+    fn tracker_includes_pre_roll_and_ends_on_silence() {
+        let mut t = UtteranceTracker::new(&options());
+        let mut audio = vec![0.0f32; 480 * 5];
+        audio.extend(vec![1.0f32; 480 * 4]);
+        audio.extend(vec![0.0f32; 480 * 3]);
+
+        let steps = t.push(&audio);
+        assert_eq!(steps.len(), 2);
+        // 2 pre-roll frames + 4 speech + 3 trailing silence
+        match &steps[0] {
+            UtteranceStep::Audio(a) => assert_eq!(a.len(), 480 * 9),
+            other => panic!("expected audio, got {other:?}"),
+        }
+        match steps[1] {
+            UtteranceStep::End { start_secs } => assert!((start_secs - 0.09).abs() < 1e-4),
+            ref other => panic!("expected end, got {other:?}"),
+        }
+        assert!(!t.in_utterance);
+    }
+
+    #[test]
+    fn tracker_force_ends_at_max_length() {
+        let mut t = UtteranceTracker::new(&StreamOptions {
+            max_utterance_secs: 0.06, // 2 frames
+            ..options()
+        });
+        let steps = t.push(&vec![1.0f32; 480 * 4]);
+        let ends = steps
+            .iter()
+            .filter(|s| matches!(s, UtteranceStep::End { .. }))
+            .count();
+        assert_eq!(ends, 2);
+    }
+
+    #[test]
+    fn tracker_carries_sub_frame_remainder() {
+        let mut t = UtteranceTracker::new(&options());
+        assert!(t.push(&vec![1.0f32; 300]).is_empty());
+        let steps = t.push(&vec![1.0f32; 300]);
+        assert_eq!(steps, vec![UtteranceStep::Audio(vec![1.0; 480])]);
+        let (rest, start) = t.flush().unwrap();
+        assert_eq!(rest.len(), 120);
+        assert_eq!(start, 0.0);
+    }
+}
@@ -0,0 +1,240 @@
+use crate::{
+    ModelCapabilities, SpeechModel, TranscribeError, TranscribeOptions, TranscriptionResult,
+};
+
+use super::driver::{StreamDriver, UtteranceDecoder};
+use super::{no_active_stream, StreamEvent, StreamOptions, StreamingSpeechModel};
+
+/// Streaming emulation for any [`SpeechModel`].
+///
+/// Buffers the audio of the current utterance and re-transcribes the whole
+/// window on every update. Consecutive hypotheses are reconciled with the
+/// [`LocalAgreement`](super::LocalAgreement) policy, so committed text is
+/// stable while the tail keeps refining. The window is bounded by
+/// [`StreamOptions::max_utterance_secs`]; decode cost grows with it, so keep
+/// that limit modest for slow models.
+///
+/// The wrapped model is still usable for whole-buffer transcription through
+/// the [`SpeechModel`] impl, which delegates directly.
+pub struct EmulatedStreaming {
+    window: ReDecodeWindow,
+    driver: Option<StreamDriver>,
+}
+
+struct ReDecodeWindow {
+    model: Box<dyn SpeechModel>,
+    buffer: Vec<f32>,
+    options: TranscribeOptions,
+}
+
+impl EmulatedStreaming {
+    pub fn new(model: Box<dyn SpeechModel>) -> Self {
+        Self {
+            window: ReDecodeWindow {
+                model,
+                buffer: Vec::new(),
+                options: TranscribeOptions::default(),
+            },
+            driver: None,
+        }
+    }
+
+    /// Unwrap the inner model.
+    pub fn into_inner(self) -> Box<dyn SpeechModel> {
+        self.window.model
+    }
+}
+
+impl UtteranceDecoder for ReDecodeWindow {
+    fn append(&mut self, samples: &[f32]) -> Result<(), TranscribeError> {
+        self.buffer.extend_from_slice(samples);
+        Ok(())
+    }
+
+    fn hypothesis(&mut self) -> Result<String, TranscribeError> {
+        Ok(self.model.transcribe(&self.buffer, &self.options)?.text)
+    }
+
+    fn finish_utterance(&mut self) -> Result<TranscriptionResult, TranscribeError> {
+        let samples = std::mem::take(&mut self.buffer);
+        self.model.transcribe(&samples, &self.options)
+    }
+}
+
+impl SpeechModel for EmulatedStreaming {
+    fn capabilities(&self) -> ModelCapabilities {
+        ModelCapabilities {
+            supports_streaming: true,
+            ..self.window.model.capabilities()
+        }
+    }
+
+    fn default_leading_silence_ms(&self) -> u32 {
+        self.window.model.default_leading_silence_ms()
+    }
+
+    fn default_trailing_silence_ms(&self) -> u32 {
+        self.window.model.default_trailing_silence_ms()
+    }
+
+    fn transcribe_raw(
+        &mut self,
+        samples: &[f32],
+        options: &TranscribeOptions,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        self.window.model.transcribe_raw(samples, options)
+    }
+}
+
+impl StreamingSpeechModel for EmulatedStreaming {
+    fn create_stream(&mut self, options: &StreamOptions) -> Result<(), TranscribeError> {
+        self.window.buffer.clear();
+        self.window.options = options.transcribe.clone();
+        self.driver = Some(StreamDriver::new(options));
+        Ok(())
+    }
+
+    fn push_audio(&mut self, samples: &[f32]) -> Result<Vec<StreamEvent>, TranscribeError> {
+        let driver = self.driver.as_mut().ok_or_else(no_active_stream)?;
+        driver.push(&mut self.window, samples)
+    }
+
+    fn finalize(&mut self) -> Result<TranscriptionResult, TranscribeError> {
+        let mut driver = self.driver.take().ok_or_else(no_active_stream)?;
+        let result = driver.finalize(&mut self.window);
+        self.window.buffer.clear();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcriber::test_helpers::MockModel;
+    use crate::TranscriptionSegment;
+
+    /// Mock model that "recognizes" one word per 0.25s of audio, reading
+    /// from a fixed script.
+    struct ScriptModel {
+        script: Vec<&'static str>,
+    }
+
+    impl SpeechModel for ScriptModel {
+        fn capabilities(&self) -> ModelCapabilities {
+            MockModel.capabilities()
+        }
+
+        fn transcribe_raw(
+            &mut self,
+            samples: &[f32],
+            _options: &TranscribeOptions,
+        ) -> Result<TranscriptionResult, TranscribeError> {
+            let n = (samples.len() / 4000).min(self.script.len());
+            let text = self.script[..n].join(" ");
+            Ok(TranscriptionResult {
+                text: text.clone(),
+                segments: Some(vec![TranscriptionSegment {
+                    start: 0.0,
+                    end: samples.len() as f32 / 16000.0,
+                    text,
+                }]),
+            })
+        }
+    }
+
+    fn script_engine() -> EmulatedStreaming {
+        EmulatedStreaming::new(Box::new(ScriptModel {
+            script: vec![
+                "one", "two", "three", "four", "five", "six", "seven", "eight",
+            ],
+        }))
+    }
+
+    #[test]
+    fn push_without_stream_errors() {
+        let mut engine = script_engine();
+        assert!(engine.push_audio(&[0.0; 480]).is_err());
+        assert!(engine.finalize().is_err());
+    }
+
+    #[test]
+    fn emits_partials_and_stable_text() {
+        let mut engine = script_engine();
+        engine
+            .create_stream(&StreamOptions {
+                update_interval_secs: 0.25,
+                ..Default::default()
+            })
+            .unwrap();
+
+        let mut events = Vec::new();
+        for _ in 0..50 {
+            // 50 * 480 samples = 1.5s of speech
+            events.extend(engine.push_audio(&[0.5; 480]).unwrap());
+        }
+
+        let stable: Vec<&str> = events
+            .iter()
+            .filter_map(|e| match e {
+                StreamEvent::Stable { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(!stable.is_empty());
+        assert_eq!(stable[0], "one");
+        assert!(events
+            .iter()
+            .any(|e| matches!(e, StreamEvent::Partial { .. })));
+        assert!(!events
+            .iter()
+            .any(|e| matches!(e, StreamEvent::Endpoint { .. })));
+
+        let result = engine.finalize().unwrap();
+        assert_eq!(result.text, "one two three four five six");
+    }
+
+    #[test]
+    fn silence_triggers_endpoint_with_stream_timestamps() {
+        let mut engine = script_engine();
+        engine
+            .create_stream(&StreamOptions {
+                endpoint_silence_secs: Some(0.3),
+                pre_roll_secs: 0.0,
+                ..Default::default()
+            })
+            .unwrap();
+
+        let mut audio = vec![0.0f32; 16000]; // 1s leading silence
+        audio.extend(vec![0.5f32; 16000]); // 1s speech
+        audio.extend(vec![0.0f32; 8000]); // 0.5s silence
+        let events = engine.push_audio(&audio).unwrap();
+
+        let endpoints: Vec<&TranscriptionResult> = events
+            .iter()
+            .filter_map(|e| match e {
+                StreamEvent::Endpoint { result } => Some(result),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(endpoints.len(), 1);
+        assert_eq!(endpoints[0].text, "one two three four five");
+        let seg = &endpoints[0].segments.as_ref().unwrap()[0];
+        assert!((seg.start - 1.0).abs() < 0.05, "start={}", seg.start);
+
+        // Utterance already ended; finalize has nothing left to flush.
+        let result = engine.finalize().unwrap();
+        assert_eq!(result.text, "one two three four five");
+    }
+
+    #[test]
+    fn object_safe_and_reusable() {
+        let mut engine: Box<dyn StreamingSpeechModel> = Box::new(script_engine());
+        assert!(engine.capabilities().supports_streaming);
+
+        for _ in 0..2 {
+            engine.create_stream(&StreamOptions::default()).unwrap();
+            engine.push_audio(&vec![0.5f32; 8000]).unwrap();
+            assert_eq!(engine.finalize().unwrap().text, "one two");
+        }
+    }
+}
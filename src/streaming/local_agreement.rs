@@ -0,0 +1,125 @@
+/// LocalAgreement-n commit policy for re-decoded hypotheses.
+///
+/// Each new hypothesis of a growing audio window is compared with the
+/// previous one. Words on which consecutive hypotheses agree (the longest
+/// common prefix) are committed and never revised, even if later
+/// hypotheses disagree. This is the LocalAgreement-2 policy from
+/// "Turning Whisper into Real-Time Transcription System" (Macháček et al.).
+///
+/// Words are whitespace-delimited and compared case-insensitively with
+/// surrounding punctuation ignored. For unsegmented scripts (Chinese,
+/// Japanese) the whole hypothesis is one word, so text is only committed
+/// once two hypotheses match exactly.
+#[derive(Debug, Clone, Default)]
+pub struct LocalAgreement {
+    previous: Vec<String>,
+    committed: Vec<String>,
+}
+
+impl LocalAgreement {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed the latest hypothesis. Returns the words committed by this
+    /// update (empty if nothing new was agreed on).
+    pub fn update(&mut self, hypothesis: &str) -> Vec<String> {
+        let words: Vec<String> = hypothesis.split_whitespace().map(str::to_string).collect();
+
+        let agreed = self
+            .previous
+            .iter()
+            .zip(&words)
+            .take_while(|(a, b)| normalize(a) == normalize(b))
+            .count();
+
+        let newly_committed = if agreed > self.committed.len() {
+            words[self.committed.len()..agreed].to_vec()
+        } else {
+            Vec::new()
+        };
+        self.committed.extend(newly_committed.iter().cloned());
+        self.previous = words;
+        newly_committed
+    }
+
+    /// All words committed since the last [`reset()`](LocalAgreement::reset).
+    pub fn committed(&self) -> &[String] {
+        &self.committed
+    }
+
+    /// Words of the latest hypothesis beyond the committed prefix.
+    pub fn unstable(&self) -> &[String] {
+        let start = self.committed.len().min(self.previous.len());
+        &self.previous[start..]
+    }
+
+    /// Forget all hypotheses and committed words.
+    pub fn reset(&mut self) {
+        self.previous.clear();
+        self.committed.clear();
+    }
+}
+
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_hypothesis_commits_nothing() {
+        let mut la = LocalAgreement::new();
+        assert!(la.update("hello world").is_empty());
+        assert!(la.committed().is_empty());
+        assert_eq!(la.unstable(), ["hello", "world"]);
+    }
+
+    #[test]
+    fn commits_common_prefix() {
+        let mut la = LocalAgreement::new();
+        la.update("hello word");
+        let new = la.update("hello world how");
+        assert_eq!(new, ["hello"]);
+        assert_eq!(la.unstable(), ["world", "how"]);
+
+        let new = la.update("hello world how are");
+        assert_eq!(new, ["world", "how"]);
+        assert_eq!(la.committed(), ["hello", "world", "how"]);
+        assert_eq!(la.unstable(), ["are"]);
+    }
+
+    #[test]
+    fn committed_words_are_never_revised() {
+        let mut la = LocalAgreement::new();
+        la.update("one two");
+        la.update("one two three");
+        assert_eq!(la.committed(), ["one", "two"]);
+
+        // A later disagreement doesn't retract committed words.
+        assert!(la.update("won too three").is_empty());
+        assert_eq!(la.committed(), ["one", "two"]);
+        assert_eq!(la.unstable(), ["three"]);
+    }
+
+    #[test]
+    fn ignores_case_and_punctuation() {
+        let mut la = LocalAgreement::new();
+        la.update("Hello, world");
+        assert_eq!(la.update("hello world."), ["hello", "world."]);
+    }
+
+    #[test]
+    fn reset_clears_state() {
+        let mut la = LocalAgreement::new();
+        la.update("a b");
+        la.update("a b");
+        la.reset();
+        assert!(la.committed().is_empty());
+        assert!(la.unstable().is_empty());
+        assert!(la.update("a b").is_empty());
+    }
+}
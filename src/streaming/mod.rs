@@ -0,0 +1,130 @@
+//! Incremental (streaming) transcription.
+//!
+//! The [`StreamingSpeechModel`] trait is the common interface for engines that
+//! can produce hypotheses while audio is still arriving. A live-caption loop
+//! written against it works unchanged with any implementation:
+//!
+//! - [`StreamingModel`](crate::onnx::moonshine::StreamingModel) — native
+//!   incremental Moonshine encoder (requires `onnx` feature)
+//! - [`EmulatedStreaming`] — wraps any [`SpeechModel`] and emulates streaming
+//!   by re-decoding a growing window, committing text with the
+//!   [`LocalAgreement`] policy
+//!
+//! # Example
+//!
+//! ```ignore
+//! use transcribe_rs::streaming::{EmulatedStreaming, StreamEvent, StreamOptions};
+//! use transcribe_rs::StreamingSpeechModel;
+//!
+//! let mut engine: Box<dyn StreamingSpeechModel> =
+//!     Box::new(EmulatedStreaming::new(Box::new(model)));
+//!
+//! engine.create_stream(&StreamOptions::default())?;
+//! for frame in audio_frames {
+//!     for event in engine.push_audio(&frame)? {
+//!         match event {
+//!             StreamEvent::Partial { stable, unstable } => println!("{stable} [{unstable}]"),
+//!             StreamEvent::Stable { text } => println!("committed: {text}"),
+//!             StreamEvent::Endpoint { result } => println!("final: {}", result.text),
+//!         }
+//!     }
+//! }
+//! let session = engine.finalize()?;
+//! ```
+
+pub(crate) mod driver;
+mod emulated;
+mod local_agreement;
+
+pub use emulated::EmulatedStreaming;
+pub use local_agreement::LocalAgreement;
+
+use crate::{SpeechModel, TranscribeError, TranscribeOptions, TranscriptionResult};
+
+/// Options for a streaming session, passed to
+/// [`create_stream()`](StreamingSpeechModel::create_stream).
+#[derive(Debug, Clone)]
+pub struct StreamOptions {
+    /// Options forwarded to every decode of the stream.
+    pub transcribe: TranscribeOptions,
+    /// Seconds of new utterance audio between hypothesis updates. Each
+    /// update may emit [`StreamEvent::Stable`] and [`StreamEvent::Partial`].
+    pub update_interval_secs: f32,
+    /// Seconds of trailing silence that end an utterance and emit
+    /// [`StreamEvent::Endpoint`]. `None` disables silence endpointing
+    /// (utterances then end only at `max_utterance_secs` or `finalize()`).
+    pub endpoint_silence_secs: Option<f32>,
+    /// RMS energy above which a 30ms frame counts as speech for
+    /// utterance onset and endpoint detection.
+    pub silence_threshold_rms: f32,
+    /// Maximum utterance duration in seconds. Longer utterances are
+    /// force-ended so the decode window stays bounded.
+    pub max_utterance_secs: f32,
+    /// Seconds of audio before speech onset kept and included in the
+    /// utterance, so soft word starts are not clipped.
+    pub pre_roll_secs: f32,
+    /// Separator inserted between utterance texts when merging the
+    /// session result in `finalize()`. Use `" "` for most languages,
+    /// `""` for CJK.
+    pub merge_separator: String,
+}
+
+impl Default for StreamOptions {
+    fn default() -> Self {
+        Self {
+            transcribe: TranscribeOptions::default(),
+            update_interval_secs: 0.5,
+            endpoint_silence_secs: Some(1.0),
+            silence_threshold_rms: 0.01,
+            max_utterance_secs: 30.0,
+            pre_roll_secs: 0.3,
+            merge_separator: " ".into(),
+        }
+    }
+}
+
+/// Events produced by [`StreamingSpeechModel::push_audio`].
+#[derive(Debug, Clone)]
+pub enum StreamEvent {
+    /// The current hypothesis for the in-progress utterance. `stable` is
+    /// the committed prefix (never changes), `unstable` is the tail that
+    /// may still be revised by later audio.
+    Partial { stable: String, unstable: String },
+    /// Text newly committed to the in-progress utterance. Concatenating all
+    /// `Stable` events of an utterance yields its committed prefix.
+    Stable { text: String },
+    /// The utterance ended (trailing silence or maximum length). `result`
+    /// is the final decode of the whole utterance, with timestamps relative
+    /// to the start of the stream. It supersedes any partial text.
+    Endpoint { result: TranscriptionResult },
+}
+
+/// Unified interface for incremental speech-to-text.
+///
+/// A model drives one stream at a time: [`create_stream()`](StreamingSpeechModel::create_stream)
+/// starts it, [`push_audio()`](StreamingSpeechModel::push_audio) feeds audio
+/// and returns events, and [`finalize()`](StreamingSpeechModel::finalize)
+/// flushes the last utterance and returns the merged session result. After
+/// `finalize()` returns, a new stream must be created before pushing more
+/// audio.
+///
+/// Every streaming model is also a [`SpeechModel`], so the same instance can
+/// transcribe whole buffers between streams.
+pub trait StreamingSpeechModel: SpeechModel {
+    /// Start a new stream, discarding any stream in progress.
+    fn create_stream(&mut self, options: &StreamOptions) -> Result<(), TranscribeError>;
+
+    /// Push audio samples (16 kHz, mono, f32 in [-1, 1]) into the active
+    /// stream. Any number of samples may be pushed per call.
+    ///
+    /// Returns the events produced while processing these samples, in order.
+    fn push_audio(&mut self, samples: &[f32]) -> Result<Vec<StreamEvent>, TranscribeError>;
+
+    /// Flush the active stream and return the merged result of all its
+    /// utterances. Ends the stream.
+    fn finalize(&mut self) -> Result<TranscriptionResult, TranscribeError>;
+}
+
+pub(crate) fn no_active_stream() -> TranscribeError {
+    TranscribeError::Config("no active stream; call create_stream() first".into())
+}
@@ -0,0 +1,115 @@
+//! SubRip (SRT) and WebVTT rendering of a [`TranscriptionResult`].
+//!
+//! One cue per segment. A result without segments becomes a single cue
+//! spanning the whole audio, so pass its duration.
+
+use crate::{TranscriptionResult, TranscriptionSegment};
+
+/// Render `result` as SRT.
+pub fn to_srt(result: &TranscriptionResult, duration_secs: f32) -> String {
+    cues(result, duration_secs)
+        .iter()
+        .enumerate()
+        .map(|(i, cue)| {
+            format!(
+                "{}\n{} --> {}\n{}\n\n",
+                i + 1,
+                timestamp(cue.start, ','),
+                timestamp(cue.end, ','),
+                cue.text.trim()
+            )
+        })
+        .collect()
+}
+
+/// Render `result` as WebVTT.
+pub fn to_vtt(result: &TranscriptionResult, duration_secs: f32) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for cue in cues(result, duration_secs) {
+        out.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            timestamp(cue.start, '.'),
+            timestamp(cue.end, '.'),
+            cue.text.trim()
+        ));
+    }
+    out
+}
+
+fn cues(result: &TranscriptionResult, duration_secs: f32) -> Vec<TranscriptionSegment> {
+    match &result.segments {
+        Some(segments) if !segments.is_empty() => segments
+            .iter()
+            .filter(|s| !s.text.trim().is_empty())
+            .cloned()
+            .collect(),
+        _ if result.text.trim().is_empty() => Vec::new(),
+        _ => vec![TranscriptionSegment {
+            start: 0.0,
+            end: duration_secs,
+            text: result.text.clone(),
+        }],
+    }
+}
+
+/// `HH:MM:SS{sep}mmm`.
+fn timestamp(secs: f32, sep: char) -> String {
+    let ms = (secs.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        ms / 3_600_000,
+        ms / 60_000 % 60,
+        ms / 1000 % 60,
+        sep,
+        ms % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn renders_cues_per_segment_or_whole_text() {
+        let result = TranscriptionResult {
+            text: "hello world".into(),
+            segments: Some(vec![
+                TranscriptionSegment {
+                    start: 0.5,
+                    end: 1.25,
+                    text: " hello".into(),
+                },
+                TranscriptionSegment {
+                    start: 3661.0,
+                    end: 3662.0,
+                    text: " world".into(),
+                },
+            ]),
+        };
+        assert_eq!(
+            to_srt(&result, 4000.0),
+            "1\n00:00:00,500 --> 00:00:01,250\nhello\n\n\
+             2\n01:01:01,000 --> 01:01:02,000\nworld\n\n"
+        );
+        assert_eq!(
+            to_vtt(&result, 4000.0),
+            "WEBVTT\n\n\
+             00:00:00.500 --> 00:00:01.250\nhello\n\n\
+             01:01:01.000 --> 01:01:02.000\nworld\n\n"
+        );
+
+        let plain = TranscriptionResult {
+            text: "hi".into(),
+            segments: None,
+        };
+        assert_eq!(
+            to_srt(&plain, 2.0),
+            "1\n00:00:00,000 --> 00:00:02,000\nhi\n\n"
+        );
+        let empty = TranscriptionResult {
+            text: String::new(),
+            segments: None,
+        };
+        assert_eq!(to_vtt(&empty, 2.0), "WEBVTT\n\n");
+    }
+}
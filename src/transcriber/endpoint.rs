@@ -0,0 +1,563 @@
+use crate::vad::Vad;
+use crate::{cancel, SpeechModel, TranscribeError, TranscribeOptions, TranscriptionResult};
+
+use super::merge::merge_sequential_with_separator;
+use super::{transcribe_padded, ChunkResult, Transcriber, SAMPLE_RATE};
+
+/// Rules for [`Endpointer`].
+#[derive(Debug, Clone)]
+pub struct EndpointConfig {
+    /// End the utterance after this much continuous silence.
+    pub trailing_silence_secs: f32,
+    /// If set, end the utterance after only this much silence when the
+    /// hypothesis for the utterance so far ends in sentence punctuation
+    /// (see [`sentence_terminators`](Self::sentence_terminators)).
+    pub punctuated_silence_secs: Option<f32>,
+    /// End the utterance once it is this long, even without a pause.
+    pub max_utterance_secs: f32,
+    /// Characters that mark a complete sentence at the end of a hypothesis.
+    pub sentence_terminators: String,
+}
+
+impl Default for EndpointConfig {
+    fn default() -> Self {
+        Self {
+            trailing_silence_secs: 1.0,
+            punctuated_silence_secs: Some(0.3),
+            max_utterance_secs: 15.0,
+            sentence_terminators: ".?!。？！".into(),
+        }
+    }
+}
+
+/// Why an utterance ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointReason {
+    /// Trailing silence reached [`EndpointConfig::trailing_silence_secs`].
+    TrailingSilence,
+    /// The hypothesis ended in sentence punctuation and trailing silence
+    /// reached [`EndpointConfig::punctuated_silence_secs`].
+    Punctuation,
+    /// The utterance reached [`EndpointConfig::max_utterance_secs`].
+    MaxLength,
+}
+
+/// Decides when a speaker has finished an utterance.
+///
+/// Feed it one VAD decision per frame with [`push()`](Endpointer::push).
+/// If the punctuation rule is enabled, supply a hypothesis whenever
+/// [`needs_hypothesis()`](Endpointer::needs_hypothesis) is true (once per
+/// pause). After returning an endpoint, the endpointer is ready for the
+/// next utterance.
+///
+/// Engine-independent: [`EndpointChunked`] combines it with a VAD and a
+/// model, but it can also drive a custom pipeline.
+pub struct Endpointer {
+    config: EndpointConfig,
+    // internal state
+    in_utterance: bool,
+    utterance_samples: usize,
+    trailing_silence_samples: usize,
+    /// Whether the hypothesis for the current pause ends in punctuation.
+    /// `None` until supplied; cleared when speech resumes.
+    punctuated: Option<bool>,
+}
+
+impl Endpointer {
+    pub fn new(config: EndpointConfig) -> Self {
+        Self {
+            config,
+            in_utterance: false,
+            utterance_samples: 0,
+            trailing_silence_samples: 0,
+            punctuated: None,
+        }
+    }
+
+    /// Record one frame. Returns the reason if the utterance ended with it.
+    pub fn push(&mut self, is_speech: bool, num_samples: usize) -> Option<EndpointReason> {
+        if !self.in_utterance {
+            if !is_speech {
+                return None;
+            }
+            self.in_utterance = true;
+        }
+
+        self.utterance_samples += num_samples;
+        if is_speech {
+            self.trailing_silence_samples = 0;
+            self.punctuated = None;
+        } else {
+            self.trailing_silence_samples += num_samples;
+        }
+
+        let silence = self.trailing_silence_samples as f32 / SAMPLE_RATE;
+        let reason =
+            if self.utterance_samples as f32 / SAMPLE_RATE >= self.config.max_utterance_secs {
+                Some(EndpointReason::MaxLength)
+            } else if silence >= self.config.trailing_silence_secs {
+                Some(EndpointReason::TrailingSilence)
+            } else if self.punctuated == Some(true)
+                && self
+                    .config
+                    .punctuated_silence_secs
+                    .is_some_and(|secs| silence >= secs)
+            {
+                Some(EndpointReason::Punctuation)
+            } else {
+                None
+            };
+
+        if reason.is_some() {
+            self.reset();
+        }
+        reason
+    }
+
+    /// Whether a hypothesis for the current pause would be used: the
+    /// punctuation rule is enabled, the utterance is in trailing silence,
+    /// and no hypothesis has been supplied since speech stopped.
+    pub fn needs_hypothesis(&self) -> bool {
+        self.config.punctuated_silence_secs.is_some()
+            && self.in_utterance
+            && self.trailing_silence_samples > 0
+            && self.punctuated.is_none()
+    }
+
+    /// Supply the hypothesis for the utterance so far.
+    pub fn set_hypothesis(&mut self, text: &str) {
+        let last = text.trim_end().chars().last();
+        self.punctuated = Some(last.is_some_and(|c| self.config.sentence_terminators.contains(c)));
+    }
+
+    /// Whether an utterance is in progress.
+    pub fn in_utterance(&self) -> bool {
+        self.in_utterance
+    }
+
+    /// Seconds of silence since the last speech frame.
+    pub fn trailing_silence_secs(&self) -> f32 {
+        self.trailing_silence_samples as f32 / SAMPLE_RATE
+    }
+
+    /// Abandon the current utterance.
+    pub fn reset(&mut self) {
+        self.in_utterance = false;
+        self.utterance_samples = 0;
+        self.trailing_silence_samples = 0;
+        self.punctuated = None;
+    }
+}
+
+/// A finished utterance from [`EndpointChunked::feed_with_endpoints`].
+#[derive(Debug, Clone)]
+pub struct Endpoint {
+    pub result: TranscriptionResult,
+    pub reason: EndpointReason,
+}
+
+/// Configuration for [`EndpointChunked`].
+pub struct EndpointChunkedConfig {
+    /// Endpointing rules.
+    pub endpoint: EndpointConfig,
+    /// Seconds of silence to prepend and append to each utterance before
+    /// transcription.
+    pub padding_secs: f32,
+    /// Separator inserted between utterance texts when merging.
+    /// Use `" "` for most languages, `""` for CJK.
+    pub merge_separator: String,
+}
+
+impl Default for EndpointChunkedConfig {
+    fn default() -> Self {
+        Self {
+            endpoint: EndpointConfig::default(),
+            padding_secs: 0.0,
+            merge_separator: " ".into(),
+        }
+    }
+}
+
+/// Utterance-level transcription for voice assistants.
+///
+/// Buffers audio from speech onset until an [`Endpointer`] decides the
+/// utterance is over, then transcribes it as one chunk. When the
+/// punctuation rule is enabled, the utterance is transcribed once at the
+/// start of each pause; if it ends in sentence punctuation the endpoint
+/// fires after the shorter punctuated silence, and the hypothesis is reused
+/// as the final result.
+///
+/// [`feed()`](Transcriber::feed) returns one result per utterance,
+/// [`feed_with_endpoints()`](EndpointChunked::feed_with_endpoints) adds the
+/// reason, and [`feed_with_interim()`](Transcriber::feed_with_interim)
+/// returns the pause hypotheses as interim results.
+///
+/// ```ignore
+/// let mut t = EndpointChunked::new(Box::new(vad), EndpointChunkedConfig::default(), options);
+/// for frame in mic_frames {
+///     for endpoint in t.feed_with_endpoints(&mut model, &frame)? {
+///         respond_to(&endpoint.result.text);
+///     }
+/// }
+/// ```
+pub struct EndpointChunked {
+    vad: Box<dyn Vad>,
+    endpointer: Endpointer,
+    config: EndpointChunkedConfig,
+    options: TranscribeOptions,
+    // internal state
+    buffer: Vec<f32>,
+    pending: Vec<f32>,
+    elapsed_samples: usize,
+    utterance_start_sample: usize,
+    /// Hypothesis of the current pause; cleared when speech resumes.
+    hypothesis: Option<TranscriptionResult>,
+    results: Vec<TranscriptionResult>,
+}
+
+/// Output of `EndpointChunked::feed_inner`.
+enum Event {
+    Hypothesis(TranscriptionResult),
+    Endpoint(Endpoint),
+}
+
+impl EndpointChunked {
+    pub fn new(
+        vad: Box<dyn Vad>,
+        config: EndpointChunkedConfig,
+        options: TranscribeOptions,
+    ) -> Self {
+        Self {
+            vad,
+            endpointer: Endpointer::new(config.endpoint.clone()),
+            config,
+            options,
+            buffer: Vec::new(),
+            pending: Vec::new(),
+            elapsed_samples: 0,
+            utterance_start_sample: 0,
+            hypothesis: None,
+            results: Vec::new(),
+        }
+    }
+
+    /// Like [`feed()`](Transcriber::feed), but also reports why each
+    /// utterance ended.
+    pub fn feed_with_endpoints(
+        &mut self,
+        model: &mut dyn SpeechModel,
+        samples: &[f32],
+    ) -> Result<Vec<Endpoint>, TranscribeError> {
+        Ok(self
+            .feed_inner(model, samples)?
+            .into_iter()
+            .filter_map(|event| match event {
+                Event::Endpoint(endpoint) => Some(endpoint),
+                Event::Hypothesis(_) => None,
+            })
+            .collect())
+    }
+
+    fn transcribe_buffer(
+        &mut self,
+        model: &mut dyn SpeechModel,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        let start_secs = self.utterance_start_sample as f32 / SAMPLE_RATE;
+        log::debug!(
+            "utterance: start={:.2}s duration={:.2}s",
+            start_secs,
+            self.buffer.len() as f32 / SAMPLE_RATE
+        );
+        transcribe_padded(
+            model,
+            &self.buffer,
+            self.config.padding_secs,
+            0.0,
+            self.utterance_start_sample,
+            &self.options,
+        )
+    }
+
+    fn end_utterance(
+        &mut self,
+        model: &mut dyn SpeechModel,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        // Reuse the pause hypothesis if only silence followed it.
+        let result = match self.hypothesis.take() {
+            Some(result) => result,
+            None => self.transcribe_buffer(model)?,
+        };
+        self.buffer.clear();
+        log::info!("  -> \"{}\"", result.text.trim());
+        self.results.push(result.clone());
+        Ok(result)
+    }
+
+    fn feed_inner(
+        &mut self,
+        model: &mut dyn SpeechModel,
+        samples: &[f32],
+    ) -> Result<Vec<Event>, TranscribeError> {
+        let frame_size = self.vad.frame_size();
+        let mut events = Vec::new();
+
+        self.pending.extend_from_slice(samples);
+        let pending = std::mem::take(&mut self.pending);
+        for frame in pending.chunks(frame_size) {
+            if frame.len() < frame_size {
+                self.pending.extend_from_slice(frame);
+                continue;
+            }
+
+            cancel::check(self.options.cancel.as_ref())?;
+            let is_speech = self.vad.is_speech(frame)?;
+            self.elapsed_samples += frame_size;
+
+            if !self.endpointer.in_utterance() {
+                if !is_speech {
+                    continue;
+                }
+                let prefill = self.vad.drain_prefill();
+                self.utterance_start_sample =
+                    (self.elapsed_samples - frame_size).saturating_sub(prefill.len());
+                self.buffer.extend_from_slice(&prefill);
+            }
+            self.buffer.extend_from_slice(frame);
+            if is_speech {
+                self.hypothesis = None;
+            }
+
+            if let Some(reason) = self.endpointer.push(is_speech, frame_size) {
+                log::info!(
+                    "endpoint at {:.2}s ({:?})",
+                    self.elapsed_samples as f32 / SAMPLE_RATE,
+                    reason
+                );
+                let result = self.end_utterance(model)?;
+                events.push(Event::Endpoint(Endpoint { result, reason }));
+            } else if self.endpointer.needs_hypothesis() {
+                let result = self.transcribe_buffer(model)?;
+                self.endpointer.set_hypothesis(&result.text);
+                self.hypothesis = Some(result.clone());
+                events.push(Event::Hypothesis(result));
+            }
+        }
+        Ok(events)
+    }
+
+    fn finish_inner(
+        &mut self,
+        model: &mut dyn SpeechModel,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        if self.endpointer.in_utterance() {
+            self.buffer.extend(std::mem::take(&mut self.pending));
+            self.end_utterance(model)?;
+        }
+        Ok(merge_sequential_with_separator(
+            &self.results,
+            &self.config.merge_separator,
+        ))
+    }
+
+    fn reset_state(&mut self) {
+        self.endpointer.reset();
+        self.vad.reset();
+        self.buffer.clear();
+        self.pending.clear();
+        self.elapsed_samples = 0;
+        self.utterance_start_sample = 0;
+        self.hypothesis = None;
+        self.results.clear();
+    }
+}
+
+impl Transcriber for EndpointChunked {
+    fn feed(
+        &mut self,
+        model: &mut dyn SpeechModel,
+        samples: &[f32],
+    ) -> Result<Vec<TranscriptionResult>, TranscribeError> {
+        Ok(self
+            .feed_with_endpoints(model, samples)?
+            .into_iter()
+            .map(|endpoint| endpoint.result)
+            .collect())
+    }
+
+    fn feed_with_interim(
+        &mut self,
+        model: &mut dyn SpeechModel,
+        samples: &[f32],
+    ) -> Result<Vec<ChunkResult>, TranscribeError> {
+        Ok(self
+            .feed_inner(model, samples)?
+            .into_iter()
+            .map(|event| match event {
+                Event::Hypothesis(result) => ChunkResult::Interim(result),
+                Event::Endpoint(endpoint) => ChunkResult::Final(endpoint.result),
+            })
+            .collect())
+    }
+
+    fn finish(
+        &mut self,
+        model: &mut dyn SpeechModel,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        let result = self.finish_inner(model);
+        self.reset_state();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcriber::test_helpers::{make_silence, make_speech, MockModel};
+    use crate::vad::EnergyVad;
+    use crate::ModelCapabilities;
+
+    /// Model that answers with a fixed text and counts calls.
+    struct FixedModel {
+        text: &'static str,
+        calls: usize,
+    }
+
+    impl SpeechModel for FixedModel {
+        fn capabilities(&self) -> ModelCapabilities {
+            MockModel.capabilities()
+        }
+
+        fn transcribe_raw(
+            &mut self,
+            _samples: &[f32],
+            _options: &TranscribeOptions,
+        ) -> Result<TranscriptionResult, TranscribeError> {
+            self.calls += 1;
+            Ok(TranscriptionResult {
+                text: self.text.into(),
+                segments: None,
+            })
+        }
+    }
+
+    fn config(punctuated: Option<f32>) -> EndpointChunkedConfig {
+        EndpointChunkedConfig {
+            endpoint: EndpointConfig {
+                trailing_silence_secs: 0.6, // 20 frames
+                punctuated_silence_secs: punctuated,
+                max_utterance_secs: 3.0,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn chunked(punctuated: Option<f32>) -> EndpointChunked {
+        EndpointChunked::new(
+            Box::new(EnergyVad::new(480, 0.01)),
+            config(punctuated),
+            TranscribeOptions::default(),
+        )
+    }
+
+    #[test]
+    fn endpointer_rules() {
+        let mut e = Endpointer::new(config(Some(0.15)).endpoint);
+        assert_eq!(e.push(false, 480), None);
+        assert!(!e.in_utterance());
+
+        assert_eq!(e.push(true, 4800), None);
+        assert!(!e.needs_hypothesis());
+        assert_eq!(e.push(false, 480), None);
+        assert!(e.needs_hypothesis());
+
+        // Unpunctuated hypothesis: wait for full trailing silence
+        e.set_hypothesis("turn on the");
+        assert_eq!(e.push(false, 4800), None);
+        assert_eq!(e.push(false, 4800), Some(EndpointReason::TrailingSilence));
+        assert!(!e.in_utterance());
+
+        // Punctuated hypothesis: shorter silence suffices
+        e.push(true, 4800);
+        e.push(false, 480);
+        e.set_hypothesis("Turn on the lights. ");
+        assert_eq!(e.push(false, 2000), Some(EndpointReason::Punctuation));
+
+        // Max length without pauses
+        assert_eq!(e.push(true, 16000 * 2), None);
+        assert_eq!(e.push(true, 16000), Some(EndpointReason::MaxLength));
+    }
+
+    #[test]
+    fn endpoint_after_trailing_silence() {
+        let mut t = chunked(None);
+        let mut model = MockModel;
+
+        let mut audio = make_silence(480, 5);
+        audio.extend(make_speech(480, 10));
+        audio.extend(make_silence(480, 19));
+        assert!(t
+            .feed_with_endpoints(&mut model, &audio)
+            .unwrap()
+            .is_empty());
+
+        let endpoints = t
+            .feed_with_endpoints(&mut model, &make_silence(480, 1))
+            .unwrap();
+        assert_eq!(endpoints.len(), 1);
+        assert_eq!(endpoints[0].reason, EndpointReason::TrailingSilence);
+        // 10 speech + 20 silence frames
+        assert_eq!(endpoints[0].result.text, "chunk_14400");
+        let seg = &endpoints[0].result.segments.as_ref().unwrap()[0];
+        assert!((seg.start - 0.15).abs() < 1e-3);
+    }
+
+    #[test]
+    fn punctuation_shortens_endpoint_and_reuses_hypothesis() {
+        let mut t = chunked(Some(0.15));
+        let mut model = FixedModel {
+            text: "Lights on.",
+            calls: 0,
+        };
+
+        let mut audio = make_speech(480, 10);
+        audio.extend(make_silence(480, 5));
+        let chunks = t.feed_with_interim(&mut model, &audio).unwrap();
+
+        assert_eq!(chunks.len(), 2);
+        assert!(!chunks[0].is_final());
+        assert!(chunks[1].is_final());
+        assert_eq!(model.calls, 1);
+
+        let result = t.finish(&mut model).unwrap();
+        assert_eq!(result.text, "Lights on.");
+    }
+
+    #[test]
+    fn unpunctuated_hypothesis_waits_for_silence() {
+        let mut t = chunked(Some(0.15));
+        let mut model = FixedModel {
+            text: "turn on the",
+            calls: 0,
+        };
+
+        let mut audio = make_speech(480, 10);
+        audio.extend(make_silence(480, 10));
+        assert!(t.feed(&mut model, &audio).unwrap().is_empty());
+
+        let results = t.feed(&mut model, &make_silence(480, 10)).unwrap();
+        assert_eq!(results.len(), 1);
+        // Hypothesis reused: only silence followed it
+        assert_eq!(model.calls, 1);
+    }
+
+    #[test]
+    fn finish_flushes_open_utterance() {
+        let mut t = chunked(None);
+        let mut model = MockModel;
+        t.feed(&mut model, &make_speech(480, 10)).unwrap();
+        let result = t.finish(&mut model).unwrap();
+        assert_eq!(result.text, "chunk_4800");
+        assert_eq!(t.finish(&mut model).unwrap().text, "");
+    }
+}
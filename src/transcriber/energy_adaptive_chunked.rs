@@ -1,7 +1,8 @@
 use crate::{SpeechModel, TranscribeError, TranscribeOptions, TranscriptionResult};
 
 use super::merge::merge_sequential_with_separator;
-use super::{rms_energy, transcribe_padded, Transcriber, SAMPLE_RATE};
+use super::observer::EventSink;
+use super::{rms_energy, ChunkSink, PlannedChunk, Transcriber, TranscriberObserver, SAMPLE_RATE};
 
 /// Configuration for [`EnergyAdaptiveChunked`].
 pub struct EnergyAdaptiveConfig {
@@ -51,9 +52,13 @@ impl Default for EnergyAdaptiveConfig {
 /// Good default for file transcription when you don't have or want a
 /// neural VAD model. Avoids splitting mid-word by finding natural
 /// pause points via energy analysis.
+///
+/// Reports chunk and progress events to an observer set with
+/// [`with_observer()`](EnergyAdaptiveChunked::with_observer).
 pub struct EnergyAdaptiveChunked {
     config: EnergyAdaptiveConfig,
     options: TranscribeOptions,
+    events: EventSink,
     // internal state
     buffer: Vec<f32>,
     elapsed_samples: usize,
@@ -66,6 +71,7 @@ impl EnergyAdaptiveChunked {
         Self {
             config,
             options,
+            events: EventSink::default(),
             buffer: Vec::new(),
             elapsed_samples: 0,
             chunk_index: 0,
@@ -73,6 +79,12 @@ impl EnergyAdaptiveChunked {
         }
     }
 
+    /// Report [`TranscriberEvent`](super::TranscriberEvent)s to `observer`.
+    pub fn with_observer(mut self, observer: impl TranscriberObserver + 'static) -> Self {
+        self.events.set_observer(Box::new(observer));
+        self
+    }
+
     /// Find the best split point in the buffer, searching around
     /// `target_samples` for the minimum-energy frame.
     fn find_split_point(&self, target_samples: usize) -> usize {
@@ -107,12 +119,22 @@ impl EnergyAdaptiveChunked {
         best_offset
     }
 
+    /// Transcribe a chunk, or record it when planning (returning `None`).
     fn transcribe_chunk(
         &mut self,
-        model: &mut dyn SpeechModel,
+        sink: &mut ChunkSink,
         chunk: &[f32],
         chunk_start_samples: usize,
-    ) -> Result<TranscriptionResult, TranscribeError> {
+    ) -> Result<Option<TranscriptionResult>, TranscribeError> {
+        if let ChunkSink::Plan { .. } = sink {
+            return sink.chunk(
+                chunk,
+                self.config.padding_secs,
+                self.config.min_chunk_secs,
+                chunk_start_samples,
+                &self.options,
+            );
+        }
         let chunk_start_secs = chunk_start_samples as f32 / SAMPLE_RATE;
 
         log::info!(
@@ -124,33 +146,66 @@ impl EnergyAdaptiveChunked {
             self.config.padding_secs * 1000.0,
         );
 
+        let index = self.chunk_index;
         self.chunk_index += 1;
+        self.events
+            .chunk_started(index, chunk_start_secs, chunk.len());
 
-        let result = transcribe_padded(
-            model,
+        let Some(result) = sink.chunk(
             chunk,
             self.config.padding_secs,
             self.config.min_chunk_secs,
-            chunk_start_secs,
+            chunk_start_samples,
             &self.options,
-        )?;
+        )?
+        else {
+            return Ok(None);
+        };
 
         log::info!("  -> \"{}\"", result.text.trim());
+        self.events
+            .chunk_transcribed(index, &result, chunk_start_samples + chunk.len());
 
         self.results.push(result.clone());
-        Ok(result)
+        Ok(Some(result))
+    }
+
+    fn feed_inner(
+        &mut self,
+        sink: &mut ChunkSink,
+        samples: &[f32],
+    ) -> Result<Vec<TranscriptionResult>, TranscribeError> {
+        self.buffer.extend_from_slice(samples);
+        self.elapsed_samples += samples.len();
+
+        let target_samples = (self.config.target_chunk_secs * SAMPLE_RATE) as usize;
+        let search_samples = (self.config.search_window_secs * SAMPLE_RATE) as usize;
+        // We need at least target + search_window of audio to find the best
+        // split point (the optimal point could be past the target).
+        let min_buffer_for_split = target_samples + search_samples;
+
+        let mut new_results = Vec::new();
+
+        while self.buffer.len() >= min_buffer_for_split {
+            let split_at = self.find_split_point(target_samples);
+            let chunk: Vec<f32> = self.buffer.drain(..split_at).collect();
+            let chunk_start = self.elapsed_samples - self.buffer.len() - chunk.len();
+            new_results.extend(self.transcribe_chunk(sink, &chunk, chunk_start)?);
+        }
+
+        Ok(new_results)
     }
 
     fn finish_inner(
         &mut self,
-        model: &mut dyn SpeechModel,
+        sink: &mut ChunkSink,
     ) -> Result<TranscriptionResult, TranscribeError> {
         if !self.buffer.is_empty() {
             let chunk = std::mem::take(&mut self.buffer);
             let chunk_secs = chunk.len() as f32 / SAMPLE_RATE;
             if chunk_secs >= self.config.min_chunk_secs {
                 let chunk_start = self.elapsed_samples - chunk.len();
-                self.transcribe_chunk(model, &chunk, chunk_start)?;
+                self.transcribe_chunk(sink, &chunk, chunk_start)?;
             } else {
                 log::debug!(
                     "skipping short remainder ({:.2}s < min {:.2}s)",
@@ -170,6 +225,7 @@ impl EnergyAdaptiveChunked {
         self.results.clear();
         self.elapsed_samples = 0;
         self.chunk_index = 0;
+        self.events.set_total_samples(None);
     }
 }
 
@@ -179,35 +235,50 @@ impl Transcriber for EnergyAdaptiveChunked {
         model: &mut dyn SpeechModel,
         samples: &[f32],
     ) -> Result<Vec<TranscriptionResult>, TranscribeError> {
-        self.buffer.extend_from_slice(samples);
-        self.elapsed_samples += samples.len();
-
-        let target_samples = (self.config.target_chunk_secs * SAMPLE_RATE) as usize;
-        let search_samples = (self.config.search_window_secs * SAMPLE_RATE) as usize;
-        // We need at least target + search_window of audio to find the best
-        // split point (the optimal point could be past the target).
-        let min_buffer_for_split = target_samples + search_samples;
-
-        let mut new_results = Vec::new();
-
-        while self.buffer.len() >= min_buffer_for_split {
-            let split_at = self.find_split_point(target_samples);
-            let chunk: Vec<f32> = self.buffer.drain(..split_at).collect();
-            let chunk_start = self.elapsed_samples - self.buffer.len() - chunk.len();
-            new_results.push(self.transcribe_chunk(model, &chunk, chunk_start)?);
-        }
-
-        Ok(new_results)
+        self.feed_inner(&mut ChunkSink::Model(model), samples)
     }
 
     fn finish(
         &mut self,
         model: &mut dyn SpeechModel,
     ) -> Result<TranscriptionResult, TranscribeError> {
-        let result = self.finish_inner(model);
+        let result = self.finish_inner(&mut ChunkSink::Model(model));
+        if let Ok(result) = &result {
+            self.events.finished(self.elapsed_samples, result);
+        }
         self.reset_state();
         result
     }
+
+    fn transcribe(
+        &mut self,
+        model: &mut dyn SpeechModel,
+        samples: &[f32],
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        self.events
+            .set_total_samples(Some(self.elapsed_samples + samples.len()));
+        if let Err(e) = self.feed(model, samples) {
+            // Drop the failed session's audio and results (and its total),
+            // like a failed finish() does.
+            self.reset_state();
+            return Err(e);
+        }
+        self.finish(model)
+    }
+
+    fn plan<'a>(&mut self, samples: &'a [f32]) -> Result<Vec<PlannedChunk<'a>>, TranscribeError> {
+        self.reset_state();
+        let mut chunks = Vec::new();
+        let mut sink = ChunkSink::Plan {
+            input: samples,
+            chunks: &mut chunks,
+        };
+        let planned = self
+            .feed_inner(&mut sink, samples)
+            .and_then(|_| self.finish_inner(&mut sink));
+        self.reset_state();
+        planned.map(|_| chunks)
+    }
 }
 
 #[cfg(test)]
@@ -249,7 +320,7 @@ mod tests {
             .unwrap();
         let chunk_secs = chunk_samples as f32 / SAMPLE_RATE;
         assert!(
-            chunk_secs >= 2.5 && chunk_secs <= 3.5,
+            (2.5..=3.5).contains(&chunk_secs),
             "split at {chunk_secs:.2}s, expected within search window of 3.0s"
         );
         // Should be closer to 2.8 than to 3.0
@@ -439,4 +510,82 @@ mod tests {
         let result = t.transcribe(&mut model, &audio).unwrap();
         assert!(!result.text.is_empty());
     }
+
+    #[test]
+    fn energy_adaptive_reports_progress() {
+        use crate::transcriber::TranscriberEvent;
+
+        let config = EnergyAdaptiveConfig {
+            target_chunk_secs: 1.0,
+            search_window_secs: 0.0,
+            ..Default::default()
+        };
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut t =
+            EnergyAdaptiveChunked::new(config, TranscribeOptions::default()).with_observer(tx);
+        t.transcribe(&mut MockModel, &vec![0.5f32; 16000 * 2])
+            .unwrap();
+
+        let events: Vec<TranscriberEvent> = rx.try_iter().collect();
+        let kinds: Vec<&str> = events
+            .iter()
+            .map(|e| match e {
+                TranscriberEvent::ChunkStarted { .. } => "started",
+                TranscriberEvent::ChunkTranscribed { .. } => "transcribed",
+                TranscriberEvent::Progress { .. } => "progress",
+                TranscriberEvent::Finished { .. } => "finished",
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                "started",
+                "transcribed",
+                "progress",
+                "started",
+                "transcribed",
+                "progress",
+                "progress",
+                "finished"
+            ]
+        );
+        assert_eq!(events[2].fraction(), Some(0.5));
+        assert_eq!(events[6].fraction(), Some(1.0));
+
+        // Streaming sessions have no known total
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut t = EnergyAdaptiveChunked::new(EnergyAdaptiveConfig::default(), Default::default())
+            .with_observer(tx);
+        t.feed(&mut MockModel, &[0.5; 1600]).unwrap();
+        t.finish(&mut MockModel).unwrap();
+        assert!(rx.try_iter().all(|e| e.fraction().is_none()));
+    }
+
+    #[test]
+    fn energy_adaptive_failed_transcribe_forgets_total() {
+        use crate::transcriber::test_helpers::FailOnNthModel;
+
+        let config = EnergyAdaptiveConfig {
+            target_chunk_secs: 1.0,
+            search_window_secs: 0.0,
+            ..Default::default()
+        };
+        let (tx, rx) = std::sync::mpsc::channel();
+        let mut t =
+            EnergyAdaptiveChunked::new(config, TranscribeOptions::default()).with_observer(tx);
+        assert!(t
+            .transcribe(&mut FailOnNthModel::new(2), &vec![0.5f32; 16000 * 3])
+            .is_err());
+        rx.try_iter().for_each(drop);
+
+        t.feed(&mut MockModel, &[0.5; 1600]).unwrap();
+        let result = t.finish(&mut MockModel).unwrap();
+        let events: Vec<_> = rx.try_iter().collect();
+        assert!(!events.is_empty());
+        assert!(events.iter().all(|e| e.fraction().is_none()));
+
+        // Nothing of the failed session is carried over.
+        assert_eq!(result.text, "chunk_1600");
+        assert_eq!(result.segments.unwrap()[0].start, 0.0);
+    }
 }
@@ -6,6 +6,16 @@
 //!
 //! - [`VadChunked`] — splits audio on speech/silence boundaries using a [`Vad`](crate::vad::Vad)
 //! - [`EnergyAdaptiveChunked`] — fixed-duration chunks with energy-based split point search
+//! - [`OverlapChunked`] — fixed-size overlapping windows with transcript deduplication
+//! - [`EndpointChunked`] — one chunk per utterance, ended by an [`Endpointer`]
+//!   (trailing silence, punctuation, max length)
+//!
+//! [`ParallelChunked`] splits a file once with one of these strategies and
+//! transcribes the chunks concurrently over a pool of models.
+//!
+//! [`VadChunked`] and [`EnergyAdaptiveChunked`] can report chunk and
+//! progress [`TranscriberEvent`]s to a [`TranscriberObserver`] (a closure or
+//! an `mpsc::Sender`), e.g. to drive a progress bar for long files.
 //!
 //! The model is borrowed per-call (`&mut dyn SpeechModel`), never owned.
 //! This works naturally with `Arc<Mutex<Box<dyn SpeechModel>>>` — lock for
@@ -33,15 +43,39 @@
 //! }
 //! let final_result = t.finish(&mut model)?;
 //! ```
+//!
+//! **Live audio with interim (partial) results:**
+//! ```ignore
+//! let config = VadChunkedConfig { interim_interval_secs: Some(0.5), ..Default::default() };
+//! let mut t = VadChunked::new(vad, config, options);
+//! for frame in audio_frames {
+//!     for chunk in t.feed_with_interim(&mut model, &frame)? {
+//!         match chunk {
+//!             ChunkResult::Interim(r) => show_partial(&r.text),
+//!             ChunkResult::Final(r) => commit(&r.text),
+//!         }
+//!     }
+//! }
+//! ```
 
+mod endpoint;
 mod energy_adaptive_chunked;
 mod merge;
+mod observer;
+mod overlap_chunked;
+mod parallel;
 #[cfg(test)]
 pub(crate) mod test_helpers;
 mod vad_chunked;
 
+pub use endpoint::{
+    Endpoint, EndpointChunked, EndpointChunkedConfig, EndpointConfig, EndpointReason, Endpointer,
+};
 pub use energy_adaptive_chunked::{EnergyAdaptiveChunked, EnergyAdaptiveConfig};
 pub use merge::{merge_sequential, merge_sequential_with_separator, DEFAULT_MERGE_SEPARATOR};
+pub use observer::{TranscriberEvent, TranscriberObserver};
+pub use overlap_chunked::{OverlapChunked, OverlapChunkedConfig};
+pub use parallel::{ParallelChunked, ParallelConfig};
 pub use vad_chunked::{VadChunked, VadChunkedConfig};
 
 /// Expected sample rate for all transcription audio.
@@ -55,52 +89,192 @@ pub(crate) fn rms_energy(frame: &[f32]) -> f32 {
     (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt()
 }
 
+use std::borrow::Cow;
 use std::path::Path;
 
-use crate::{audio, SpeechModel, TranscribeError, TranscribeOptions, TranscriptionResult};
+use crate::{audio, cancel, SpeechModel, TranscribeError, TranscribeOptions, TranscriptionResult};
 
 /// Transcribe a chunk with optional silence padding and timestamp adjustment.
 ///
 /// Sets leading/trailing silence on [`TranscribeOptions`] and delegates padding
 /// and leading-silence timestamp correction to [`SpeechModel::transcribe`].
 /// Enforces `min_duration_secs` via zero-padding of the content, then offsets
-/// segment timestamps by `chunk_start_secs`.
+/// segment timestamps by the chunk's position, `chunk_start_sample`.
+///
+/// Fails with [`TranscribeError::Cancelled`] if `options.cancel` was
+/// cancelled, so every chunking strategy stops between chunks.
 pub(crate) fn transcribe_padded(
     model: &mut dyn SpeechModel,
     samples: &[f32],
     padding_secs: f32,
     min_duration_secs: f32,
-    chunk_start_secs: f32,
+    chunk_start_sample: usize,
     options: &TranscribeOptions,
 ) -> Result<TranscriptionResult, TranscribeError> {
-    let padding_ms = (padding_secs * 1000.0) as u32;
+    PlannedChunk::new(
+        samples,
+        padding_secs,
+        min_duration_secs,
+        chunk_start_sample,
+        options,
+    )
+    .transcribe(model)
+}
 
-    // The trait will prepend + append padding. Ensure the total
-    // (padding + content + padding) meets the minimum duration.
-    let pad_total = 2 * padding_ms as usize * audio::SAMPLES_PER_MS;
-    let min_total = (min_duration_secs * SAMPLE_RATE) as usize;
-    let min_content = min_total.saturating_sub(pad_total);
+/// A chunk exactly as a strategy would pass it to the model, from
+/// [`Transcriber::plan`].
+#[derive(Debug, Clone)]
+pub struct PlannedChunk<'a> {
+    /// Position of the chunk's first sample in the planned audio.
+    pub start_sample: usize,
+    /// Chunk audio, zero-padded to the strategy's minimum duration.
+    /// Borrowed from the planned audio when it is one contiguous run of it.
+    pub samples: Cow<'a, [f32]>,
+    /// Options to transcribe the chunk with, including the strategy's
+    /// silence padding.
+    pub options: TranscribeOptions,
+}
 
-    let mut content = samples.to_vec();
-    if content.len() < min_content {
-        content.resize(min_content, 0.0);
+impl<'a> PlannedChunk<'a> {
+    /// Apply `padding_secs` of silence padding and zero-pad the content so
+    /// the padded chunk lasts at least `min_duration_secs`.
+    fn new(
+        samples: &'a [f32],
+        padding_secs: f32,
+        min_duration_secs: f32,
+        start_sample: usize,
+        options: &TranscribeOptions,
+    ) -> Self {
+        let padding_ms = (padding_secs * 1000.0) as u32;
+
+        // The trait will prepend + append padding. Ensure the total
+        // (padding + content + padding) meets the minimum duration.
+        let pad_total = 2 * padding_ms as usize * audio::SAMPLES_PER_MS;
+        let min_total = (min_duration_secs * SAMPLE_RATE) as usize;
+        let min_content = min_total.saturating_sub(pad_total);
+
+        let samples = if samples.len() < min_content {
+            let mut content = samples.to_vec();
+            content.resize(min_content, 0.0);
+            Cow::Owned(content)
+        } else {
+            Cow::Borrowed(samples)
+        };
+
+        // Override any user-specified padding — chunked transcription manages
+        // its own padding to ensure consistent overlap between chunks.
+        let mut options = options.clone();
+        options.leading_silence_ms = Some(padding_ms);
+        options.trailing_silence_ms = Some(padding_ms);
+
+        Self {
+            start_sample,
+            samples,
+            options,
+        }
     }
 
-    // Override any user-specified padding — chunked transcription manages
-    // its own padding to ensure consistent overlap between chunks.
-    let mut opts = options.clone();
-    opts.leading_silence_ms = Some(padding_ms);
-    opts.trailing_silence_ms = Some(padding_ms);
+    /// Transcribe the chunk with `model`, with timestamps offset to the
+    /// chunk's position.
+    ///
+    /// Fails with [`TranscribeError::Cancelled`] if the chunk's options were
+    /// cancelled.
+    pub fn transcribe(
+        &self,
+        model: &mut dyn SpeechModel,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        cancel::check(self.options.cancel.as_ref())?;
+
+        let mut result = model.transcribe(&self.samples, &self.options)?;
 
-    let mut result = model.transcribe(&content, &opts)?;
+        // The trait already subtracted leading silence from timestamps.
+        // Offset by this chunk's position in the overall audio.
+        let start_secs = self.start_sample as f32 / SAMPLE_RATE;
+        if start_secs > 0.0 {
+            result.offset_timestamps(start_secs);
+        }
 
-    // The trait already subtracted leading silence from timestamps.
-    // Offset by this chunk's position in the overall audio.
-    if chunk_start_secs > 0.0 {
-        result.offset_timestamps(chunk_start_secs);
+        Ok(result)
     }
+}
 
-    Ok(result)
+/// Where a strategy sends its chunks: to a model, or into a plan.
+pub(crate) enum ChunkSink<'s, 'a> {
+    Model(&'s mut dyn SpeechModel),
+    Plan {
+        /// The audio being planned, for borrowing chunks from.
+        input: &'a [f32],
+        chunks: &'s mut Vec<PlannedChunk<'a>>,
+    },
+}
+
+impl ChunkSink<'_, '_> {
+    /// Like [`transcribe_padded`], but when planning, record the chunk and
+    /// return `None`.
+    pub(crate) fn chunk(
+        &mut self,
+        samples: &[f32],
+        padding_secs: f32,
+        min_duration_secs: f32,
+        chunk_start_sample: usize,
+        options: &TranscribeOptions,
+    ) -> Result<Option<TranscriptionResult>, TranscribeError> {
+        let chunk = PlannedChunk::new(
+            samples,
+            padding_secs,
+            min_duration_secs,
+            chunk_start_sample,
+            options,
+        );
+        match self {
+            ChunkSink::Model(model) => chunk.transcribe(&mut **model).map(Some),
+            ChunkSink::Plan { input, chunks } => {
+                let range = chunk_start_sample..chunk_start_sample + samples.len();
+                let samples = match chunk.samples {
+                    Cow::Borrowed(samples) if input.get(range.clone()) == Some(samples) => {
+                        Cow::Borrowed(&input[range])
+                    }
+                    samples => Cow::Owned(samples.into_owned()),
+                };
+                chunks.push(PlannedChunk {
+                    start_sample: chunk_start_sample,
+                    samples,
+                    options: chunk.options,
+                });
+                Ok(None)
+            }
+        }
+    }
+}
+
+/// A result from [`Transcriber::feed_with_interim`].
+#[derive(Debug, Clone)]
+pub enum ChunkResult {
+    /// Provisional transcription of a chunk that is still growing.
+    Interim(TranscriptionResult),
+    /// Transcription of a completed chunk, as returned by [`Transcriber::feed`].
+    Final(TranscriptionResult),
+}
+
+impl ChunkResult {
+    /// Whether this is a final result.
+    pub fn is_final(&self) -> bool {
+        matches!(self, ChunkResult::Final(_))
+    }
+
+    /// The transcription, interim or final.
+    pub fn result(&self) -> &TranscriptionResult {
+        match self {
+            ChunkResult::Interim(r) | ChunkResult::Final(r) => r,
+        }
+    }
+
+    /// Consume and return the transcription, interim or final.
+    pub fn into_result(self) -> TranscriptionResult {
+        match self {
+            ChunkResult::Interim(r) | ChunkResult::Final(r) => r,
+        }
+    }
 }
 
 /// A chunked transcription strategy.
@@ -130,6 +304,28 @@ pub trait Transcriber: Send {
         samples: &[f32],
     ) -> Result<Vec<TranscriptionResult>, TranscribeError>;
 
+    /// Like [`feed()`](Transcriber::feed), but also returns interim results
+    /// for audio that is still being buffered, interleaved with final
+    /// results in the order they were produced.
+    ///
+    /// Interim results are re-transcriptions of an in-progress chunk and are
+    /// superseded by later interim results and by the chunk's final result.
+    /// They are not included in [`finish()`](Transcriber::finish). The final
+    /// results are exactly those [`feed()`](Transcriber::feed) would return.
+    ///
+    /// The default implementation produces no interim results.
+    fn feed_with_interim(
+        &mut self,
+        model: &mut dyn SpeechModel,
+        samples: &[f32],
+    ) -> Result<Vec<ChunkResult>, TranscribeError> {
+        Ok(self
+            .feed(model, samples)?
+            .into_iter()
+            .map(ChunkResult::Final)
+            .collect())
+    }
+
     /// Transcribe any remaining buffered audio and return the merged
     /// result of the entire session. Resets internal state for reuse.
     fn finish(
@@ -151,6 +347,21 @@ pub trait Transcriber: Send {
         self.finish(model)
     }
 
+    /// Split `samples` into the chunks [`transcribe()`](Transcriber::transcribe)
+    /// would pass to the model, without transcribing them or reporting
+    /// events. The transcriber is reset before and after planning.
+    ///
+    /// Used by [`ParallelChunked`]. Strategies whose chunks can't be
+    /// transcribed independently and merged in order (such as
+    /// [`OverlapChunked`]) keep the default, which fails with
+    /// [`TranscribeError::Config`].
+    fn plan<'a>(&mut self, samples: &'a [f32]) -> Result<Vec<PlannedChunk<'a>>, TranscribeError> {
+        let _ = samples;
+        Err(TranscribeError::Config(
+            "this transcriber cannot plan its chunks ahead of transcription".into(),
+        ))
+    }
+
     /// Convenience: load a WAV file, feed, and finish.
     fn transcribe_file(
         &mut self,
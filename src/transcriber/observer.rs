@@ -0,0 +1,152 @@
+use std::sync::mpsc::Sender;
+
+use crate::TranscriptionResult;
+
+use super::SAMPLE_RATE;
+
+/// Something that happened during a transcription session.
+///
+/// Emitted in this order per chunk: [`ChunkStarted`](Self::ChunkStarted),
+/// [`ChunkTranscribed`](Self::ChunkTranscribed), then
+/// [`Progress`](Self::Progress). [`Finished`](Self::Finished) is emitted
+/// once by [`finish()`](super::Transcriber::finish).
+#[derive(Debug, Clone)]
+pub enum TranscriberEvent {
+    /// A chunk is about to be sent to the model.
+    ChunkStarted {
+        /// Zero-based chunk index within the session.
+        index: usize,
+        /// Chunk start time in seconds.
+        start_secs: f32,
+        /// Chunk duration in seconds.
+        duration_secs: f32,
+    },
+    /// A chunk was transcribed. Timestamps are absolute.
+    ChunkTranscribed {
+        index: usize,
+        result: TranscriptionResult,
+    },
+    /// Audio up to `processed_secs` is done.
+    Progress {
+        processed_secs: f32,
+        /// Total duration of the audio, known when it was passed in one
+        /// call ([`transcribe()`](super::Transcriber::transcribe),
+        /// [`transcribe_file()`](super::Transcriber::transcribe_file)),
+        /// `None` for streaming feeds.
+        total_secs: Option<f32>,
+    },
+    /// The session finished with this merged result.
+    Finished { result: TranscriptionResult },
+}
+
+impl TranscriberEvent {
+    /// Fraction of the audio processed (0.0–1.0) for
+    /// [`Progress`](Self::Progress) events with a known total.
+    pub fn fraction(&self) -> Option<f32> {
+        match self {
+            TranscriberEvent::Progress {
+                processed_secs,
+                total_secs: Some(total),
+            } if *total > 0.0 => Some((processed_secs / total).min(1.0)),
+            _ => None,
+        }
+    }
+}
+
+/// Receives [`TranscriberEvent`]s from a transcriber.
+///
+/// Implemented for closures and for `std::sync::mpsc::Sender`, so events
+/// can be handled inline or forwarded to another thread (e.g. a UI):
+///
+/// ```ignore
+/// let (tx, rx) = std::sync::mpsc::channel();
+/// let mut t = VadChunked::new(vad, config, options).with_observer(tx);
+/// std::thread::spawn(move || t.transcribe_file(&mut model, &path));
+/// for event in rx {
+///     if let Some(fraction) = event.fraction() {
+///         progress_bar.set(fraction);
+///     }
+/// }
+/// ```
+///
+/// Observers run on the transcribing thread between chunks; keep them cheap.
+pub trait TranscriberObserver: Send {
+    fn on_event(&mut self, event: &TranscriberEvent);
+}
+
+impl<F: FnMut(&TranscriberEvent) + Send> TranscriberObserver for F {
+    fn on_event(&mut self, event: &TranscriberEvent) {
+        self(event)
+    }
+}
+
+impl TranscriberObserver for Sender<TranscriberEvent> {
+    /// Forwards a clone of the event. A dropped receiver is ignored.
+    fn on_event(&mut self, event: &TranscriberEvent) {
+        let _ = self.send(event.clone());
+    }
+}
+
+/// Optional observer plus the session's known total length, shared by the
+/// transcribers that report events.
+#[derive(Default)]
+pub(crate) struct EventSink {
+    observer: Option<Box<dyn TranscriberObserver>>,
+    total_samples: Option<usize>,
+}
+
+impl EventSink {
+    pub(crate) fn set_observer(&mut self, observer: Box<dyn TranscriberObserver>) {
+        self.observer = Some(observer);
+    }
+
+    /// Set the expected session length, or `None` for streaming.
+    pub(crate) fn set_total_samples(&mut self, total: Option<usize>) {
+        self.total_samples = total;
+    }
+
+    pub(crate) fn emit(&mut self, event: impl FnOnce() -> TranscriberEvent) {
+        if let Some(observer) = &mut self.observer {
+            observer.on_event(&event());
+        }
+    }
+
+    pub(crate) fn chunk_started(&mut self, index: usize, start_secs: f32, samples: usize) {
+        self.emit(|| TranscriberEvent::ChunkStarted {
+            index,
+            start_secs,
+            duration_secs: samples as f32 / SAMPLE_RATE,
+        });
+    }
+
+    /// Emit `ChunkTranscribed` followed by `Progress`.
+    pub(crate) fn chunk_transcribed(
+        &mut self,
+        index: usize,
+        result: &TranscriptionResult,
+        processed_samples: usize,
+    ) {
+        self.emit(|| TranscriberEvent::ChunkTranscribed {
+            index,
+            result: result.clone(),
+        });
+        self.progress(processed_samples);
+    }
+
+    pub(crate) fn progress(&mut self, processed_samples: usize) {
+        let total_secs = self.total_samples.map(|t| t as f32 / SAMPLE_RATE);
+        self.emit(|| TranscriberEvent::Progress {
+            processed_secs: processed_samples as f32 / SAMPLE_RATE,
+            total_secs,
+        });
+    }
+
+    /// Emit final `Progress` and `Finished`, and forget the session length.
+    pub(crate) fn finished(&mut self, processed_samples: usize, result: &TranscriptionResult) {
+        self.progress(processed_samples);
+        self.emit(|| TranscriberEvent::Finished {
+            result: result.clone(),
+        });
+        self.total_samples = None;
+    }
+}
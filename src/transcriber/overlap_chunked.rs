@@ -0,0 +1,569 @@
+use crate::{
+    SpeechModel, TranscribeError, TranscribeOptions, TranscriptionResult, TranscriptionSegment,
+};
+
+use super::merge::merge_sequential_with_separator;
+use super::{transcribe_padded, Transcriber, SAMPLE_RATE};
+
+/// Configuration for [`OverlapChunked`].
+pub struct OverlapChunkedConfig {
+    /// Duration of each transcribed window in seconds (e.g. 30.0).
+    pub chunk_secs: f32,
+    /// Seconds of audio shared between consecutive windows (e.g. 2.0).
+    /// Words in this region are transcribed twice and reconciled, so a
+    /// word cut at one window's edge is heard whole by the other. Clamped
+    /// to half of `chunk_secs`.
+    pub overlap_secs: f32,
+    /// Seconds of silence to prepend and append to each window before
+    /// transcription.
+    pub padding_secs: f32,
+    /// Minimum window duration in seconds. Shorter windows (typically the
+    /// last one) are zero-padded before transcription.
+    pub min_chunk_secs: f32,
+    /// Separator inserted between chunk texts when merging.
+    /// Use `" "` for most languages, `""` for CJK.
+    pub merge_separator: String,
+}
+
+impl Default for OverlapChunkedConfig {
+    fn default() -> Self {
+        Self {
+            chunk_secs: 30.0,
+            overlap_secs: 2.0,
+            padding_secs: 0.0,
+            min_chunk_secs: 0.0,
+            merge_separator: " ".into(),
+        }
+    }
+}
+
+/// Fixed-size overlapping windows with transcript deduplication.
+///
+/// Each window starts `chunk_secs - overlap_secs` after the previous one, so
+/// consecutive windows share `overlap_secs` of audio. The duplicated words
+/// are reconciled by aligning the tail of one window with the head of the
+/// next (longest common subsequence over normalized words, restricted to
+/// words whose timestamps fall near the overlap). The seam is placed at the
+/// middle of the alignment, where both windows had the most context. If
+/// nothing aligns, the seam falls at the temporal midpoint of the overlap.
+///
+/// Word times come from the engine's segments, interpolated within each
+/// segment by character length; engines without timestamps get times
+/// interpolated across the window. Alignment works on whitespace-delimited
+/// words, so it is intended for languages that separate words with spaces.
+///
+/// Because the tail of a window can still be replaced by the next window,
+/// each window's result is returned by [`feed()`](Transcriber::feed) one
+/// window late (once its successor has been reconciled). Returned results
+/// never overlap, and their segments keep the engine's granularity except
+/// where a segment was cut at a seam.
+pub struct OverlapChunked {
+    config: OverlapChunkedConfig,
+    options: TranscribeOptions,
+    chunk_samples: usize,
+    overlap_samples: usize,
+    // internal state
+    buffer: Vec<f32>,
+    /// Sample offset of `buffer[0]` in the session.
+    buffer_start: usize,
+    /// Whether any window has been transcribed this session.
+    transcribed_any: bool,
+    /// Words of the latest window not yet returned.
+    pending: Vec<Word>,
+    /// Whether the engine reported segments for the pending words.
+    pending_timed: bool,
+    chunk_index: usize,
+    results: Vec<TranscriptionResult>,
+}
+
+/// A word with (possibly interpolated) session-relative timing.
+#[derive(Debug, Clone)]
+struct Word {
+    text: String,
+    start: f32,
+    end: f32,
+    /// Identifies the engine segment the word came from, so words can be
+    /// regrouped into segments: `(chunk_index, segment_index)`.
+    source: (usize, usize),
+}
+
+impl OverlapChunked {
+    pub fn new(config: OverlapChunkedConfig, options: TranscribeOptions) -> Self {
+        let chunk_samples = ((config.chunk_secs * SAMPLE_RATE) as usize).max(1);
+        let mut overlap_samples = (config.overlap_secs * SAMPLE_RATE) as usize;
+        if overlap_samples > chunk_samples / 2 {
+            log::warn!(
+                "overlap_secs={:.2} exceeds half of chunk_secs={:.2}; clamping",
+                config.overlap_secs,
+                config.chunk_secs
+            );
+            overlap_samples = chunk_samples / 2;
+        }
+        Self {
+            config,
+            options,
+            chunk_samples,
+            overlap_samples,
+            buffer: Vec::new(),
+            buffer_start: 0,
+            transcribed_any: false,
+            pending: Vec::new(),
+            pending_timed: false,
+            chunk_index: 0,
+            results: Vec::new(),
+        }
+    }
+
+    /// Transcribe `buffer[..len]` as one window and reconcile it with the
+    /// pending words. Returns the result finalized by this window, if any.
+    fn transcribe_window(
+        &mut self,
+        model: &mut dyn SpeechModel,
+        len: usize,
+    ) -> Result<Option<TranscriptionResult>, TranscribeError> {
+        let start_secs = self.buffer_start as f32 / SAMPLE_RATE;
+        let duration_secs = len as f32 / SAMPLE_RATE;
+
+        log::info!(
+            "chunk {}: start={:.2}s duration={:.2}s samples={} overlap={:.2}s",
+            self.chunk_index,
+            start_secs,
+            duration_secs,
+            len,
+            self.overlap_samples as f32 / SAMPLE_RATE,
+        );
+
+        let result = transcribe_padded(
+            model,
+            &self.buffer[..len],
+            self.config.padding_secs,
+            self.config.min_chunk_secs,
+            self.buffer_start,
+            &self.options,
+        )?;
+
+        log::info!("  -> \"{}\"", result.text.trim());
+
+        let timed = result.segments.is_some();
+        let words = split_words(&result, self.chunk_index, start_secs, duration_secs);
+        self.chunk_index += 1;
+
+        let finalized = if self.transcribed_any {
+            let overlap_end = start_secs + self.overlap_samples as f32 / SAMPLE_RATE;
+            let (keep, skip) = reconcile(&self.pending, &words, start_secs, overlap_end);
+            log::debug!(
+                "overlap {:.2}-{:.2}s: kept {} of {} previous words, skipped {} new words",
+                start_secs,
+                overlap_end,
+                keep,
+                self.pending.len(),
+                skip
+            );
+            let prev: Vec<Word> = self.pending.drain(..keep).collect();
+            let prev_timed = self.pending_timed;
+            self.pending = words.into_iter().skip(skip).collect();
+            Some(self.emit(&prev, prev_timed))
+        } else {
+            self.pending = words;
+            None
+        };
+        self.pending_timed = timed;
+        self.transcribed_any = true;
+        Ok(finalized)
+    }
+
+    /// Record and return the result for a finalized run of words.
+    fn emit(&mut self, words: &[Word], timed: bool) -> TranscriptionResult {
+        let result = words_to_result(words, timed);
+        self.results.push(result.clone());
+        result
+    }
+
+    fn finish_inner(
+        &mut self,
+        model: &mut dyn SpeechModel,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        // Everything up to `overlap_samples` of the buffer was already
+        // covered by the previous window; only transcribe if there's more.
+        let has_new_audio = if self.transcribed_any {
+            self.buffer.len() > self.overlap_samples
+        } else {
+            !self.buffer.is_empty()
+        };
+        if has_new_audio {
+            self.transcribe_window(model, self.buffer.len())?;
+        }
+        if !self.pending.is_empty() {
+            let pending = std::mem::take(&mut self.pending);
+            self.emit(&pending, self.pending_timed);
+        }
+        Ok(merge_sequential_with_separator(
+            &self.results,
+            &self.config.merge_separator,
+        ))
+    }
+
+    fn reset_state(&mut self) {
+        self.buffer.clear();
+        self.buffer_start = 0;
+        self.transcribed_any = false;
+        self.pending.clear();
+        self.pending_timed = false;
+        self.chunk_index = 0;
+        self.results.clear();
+    }
+}
+
+impl Transcriber for OverlapChunked {
+    fn feed(
+        &mut self,
+        model: &mut dyn SpeechModel,
+        samples: &[f32],
+    ) -> Result<Vec<TranscriptionResult>, TranscribeError> {
+        self.buffer.extend_from_slice(samples);
+
+        let stride = self.chunk_samples - self.overlap_samples;
+        let mut new_results = Vec::new();
+        while self.buffer.len() >= self.chunk_samples {
+            if let Some(result) = self.transcribe_window(model, self.chunk_samples)? {
+                new_results.push(result);
+            }
+            self.buffer.drain(..stride);
+            self.buffer_start += stride;
+        }
+        Ok(new_results)
+    }
+
+    fn finish(
+        &mut self,
+        model: &mut dyn SpeechModel,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        let result = self.finish_inner(model);
+        self.reset_state();
+        result
+    }
+}
+
+/// Split a window result into words with session-relative times.
+fn split_words(
+    result: &TranscriptionResult,
+    chunk_index: usize,
+    window_start: f32,
+    window_secs: f32,
+) -> Vec<Word> {
+    match &result.segments {
+        Some(segments) => segments
+            .iter()
+            .enumerate()
+            .flat_map(|(i, seg)| interpolate_words(&seg.text, seg.start, seg.end, (chunk_index, i)))
+            .collect(),
+        None => interpolate_words(
+            &result.text,
+            window_start,
+            window_start + window_secs,
+            (chunk_index, 0),
+        ),
+    }
+}
+
+/// Spread the words of `text` over `[start, end]` proportionally to their
+/// character length.
+fn interpolate_words(text: &str, start: f32, end: f32, source: (usize, usize)) -> Vec<Word> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let total_chars: usize = words.iter().map(|w| w.chars().count()).sum();
+    if total_chars == 0 {
+        return Vec::new();
+    }
+    let span = (end - start).max(0.0);
+    let mut chars_before = 0;
+    words
+        .into_iter()
+        .map(|w| {
+            let n = w.chars().count();
+            let w_start = start + span * chars_before as f32 / total_chars as f32;
+            chars_before += n;
+            let w_end = start + span * chars_before as f32 / total_chars as f32;
+            Word {
+                text: w.to_string(),
+                start: w_start,
+                end: w_end,
+                source,
+            }
+        })
+        .collect()
+}
+
+/// Slack around the overlap region when choosing alignment candidates,
+/// to tolerate timestamp imprecision.
+const ALIGN_SLACK_SECS: f32 = 0.5;
+
+/// Decide where to join `prev` (the earlier window) and `next`.
+///
+/// Returns `(keep, skip)`: keep `prev[..keep]` and `next[skip..]`.
+fn reconcile(prev: &[Word], next: &[Word], overlap_start: f32, overlap_end: f32) -> (usize, usize) {
+    let tail_start = prev
+        .iter()
+        .position(|w| w.end > overlap_start - ALIGN_SLACK_SECS)
+        .unwrap_or(prev.len());
+    let head_end = next
+        .iter()
+        .position(|w| w.start >= overlap_end + ALIGN_SLACK_SECS)
+        .unwrap_or(next.len());
+
+    let tail: Vec<String> = prev[tail_start..]
+        .iter()
+        .map(|w| normalize(&w.text))
+        .collect();
+    let head: Vec<String> = next[..head_end]
+        .iter()
+        .map(|w| normalize(&w.text))
+        .collect();
+    let pairs = lcs_pairs(&tail, &head);
+
+    if let Some(&(i, j)) = pairs.get(pairs.len() / 2) {
+        return (tail_start + i + 1, j + 1);
+    }
+
+    // Nothing aligned: cut both windows at the middle of the overlap.
+    let mid = (overlap_start + overlap_end) / 2.0;
+    let center = |w: &Word| (w.start + w.end) / 2.0;
+    let keep = prev.iter().take_while(|w| center(w) < mid).count();
+    let skip = next.iter().take_while(|w| center(w) < mid).count();
+    (keep, skip)
+}
+
+fn normalize(word: &str) -> String {
+    word.trim_matches(|c: char| !c.is_alphanumeric())
+        .to_lowercase()
+}
+
+/// Longest common subsequence of `a` and `b`, as matched index pairs in
+/// increasing order. Empty words never match.
+fn lcs_pairs(a: &[String], b: &[String]) -> Vec<(usize, usize)> {
+    let (n, m) = (a.len(), b.len());
+    let mut dp = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            dp[i][j] = if !a[i].is_empty() && a[i] == b[j] {
+                dp[i + 1][j + 1] + 1
+            } else {
+                dp[i + 1][j].max(dp[i][j + 1])
+            };
+        }
+    }
+
+    let mut pairs = Vec::with_capacity(dp[0][0]);
+    let (mut i, mut j) = (0, 0);
+    while i < n && j < m {
+        if !a[i].is_empty() && a[i] == b[j] {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if dp[i + 1][j] >= dp[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+/// Rebuild a result from words, regrouping consecutive words that came
+/// from the same engine segment.
+fn words_to_result(words: &[Word], timed: bool) -> TranscriptionResult {
+    let text = words
+        .iter()
+        .map(|w| w.text.as_str())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let segments = timed.then(|| {
+        let mut segments: Vec<TranscriptionSegment> = Vec::new();
+        let mut last_source = None;
+        for w in words {
+            match segments.last_mut() {
+                Some(seg) if last_source == Some(w.source) => {
+                    seg.end = w.end;
+                    seg.text.push(' ');
+                    seg.text.push_str(&w.text);
+                }
+                _ => segments.push(TranscriptionSegment {
+                    start: w.start,
+                    end: w.end,
+                    text: w.text.clone(),
+                }),
+            }
+            last_source = Some(w.source);
+        }
+        segments
+    });
+
+    TranscriptionResult { text, segments }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcriber::test_helpers::MockModel;
+    use crate::ModelCapabilities;
+
+    /// Mock model over a spoken script: each word occupies 0.5s of session
+    /// time. A window hears every word that overlaps it, and emits a
+    /// truncated "fragment" for words cut by the window edges.
+    struct ScriptModel {
+        words: Vec<&'static str>,
+        /// Session sample offset of the next window, set by the test.
+        window_starts: Vec<usize>,
+        calls: usize,
+        with_segments: bool,
+    }
+
+    impl SpeechModel for ScriptModel {
+        fn capabilities(&self) -> ModelCapabilities {
+            MockModel.capabilities()
+        }
+
+        fn transcribe_raw(
+            &mut self,
+            samples: &[f32],
+            _options: &TranscribeOptions,
+        ) -> Result<TranscriptionResult, TranscribeError> {
+            let start = self.window_starts[self.calls] as f32 / SAMPLE_RATE;
+            self.calls += 1;
+            let end = start + samples.len() as f32 / SAMPLE_RATE;
+
+            let mut segments = Vec::new();
+            for (i, w) in self.words.iter().enumerate() {
+                let (ws, we) = (i as f32 * 0.5, (i + 1) as f32 * 0.5);
+                if we <= start || ws >= end {
+                    continue;
+                }
+                let text = if ws < start || we > end {
+                    format!("{}-", &w[..1]) // cut word
+                } else {
+                    w.to_string()
+                };
+                segments.push(TranscriptionSegment {
+                    start: ws.max(start) - start,
+                    end: we.min(end) - start,
+                    text,
+                });
+            }
+            let text = segments
+                .iter()
+                .map(|s| s.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            Ok(TranscriptionResult {
+                text,
+                segments: self.with_segments.then_some(segments),
+            })
+        }
+    }
+
+    const SCRIPT: [&str; 12] = [
+        "alpha", "bravo", "charlie", "delta", "echo", "foxtrot", "golf", "hotel", "india",
+        "juliet", "kilo", "lima",
+    ];
+
+    fn script_model(with_segments: bool) -> ScriptModel {
+        ScriptModel {
+            words: SCRIPT.to_vec(),
+            // 2.25s windows with 0.75s overlap -> stride 1.5s
+            window_starts: (0..10).map(|k| k * 24000).collect(),
+            calls: 0,
+            with_segments,
+        }
+    }
+
+    fn config() -> OverlapChunkedConfig {
+        OverlapChunkedConfig {
+            chunk_secs: 2.25,
+            overlap_secs: 0.75,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn lcs_finds_common_subsequence() {
+        let a: Vec<String> = ["a", "b", "c", "d"].iter().map(|s| s.to_string()).collect();
+        let b: Vec<String> = ["b", "x", "d"].iter().map(|s| s.to_string()).collect();
+        assert_eq!(lcs_pairs(&a, &b), vec![(1, 0), (3, 2)]);
+        assert!(lcs_pairs(&a, &[]).is_empty());
+    }
+
+    #[test]
+    fn overlap_deduplicates_and_repairs_cut_words() {
+        let mut t = OverlapChunked::new(config(), TranscribeOptions::default());
+        let mut model = script_model(true);
+
+        // 6s of audio = 12 words
+        let result = t.transcribe(&mut model, &vec![0.5f32; 96000]).unwrap();
+        assert_eq!(result.text, SCRIPT.join(" "));
+
+        let segs = result.segments.unwrap();
+        assert_eq!(segs.len(), SCRIPT.len());
+        for (i, seg) in segs.iter().enumerate() {
+            assert_eq!(seg.text, SCRIPT[i]);
+            assert!((seg.start - i as f32 * 0.5).abs() < 0.01, "{seg:?}");
+        }
+    }
+
+    #[test]
+    fn overlap_without_timestamps() {
+        let mut t = OverlapChunked::new(config(), TranscribeOptions::default());
+        let mut model = script_model(false);
+
+        let result = t.transcribe(&mut model, &vec![0.5f32; 96000]).unwrap();
+        assert_eq!(result.text, SCRIPT.join(" "));
+        assert!(result.segments.is_none());
+    }
+
+    #[test]
+    fn overlap_feed_results_are_one_window_late() {
+        let mut t = OverlapChunked::new(config(), TranscribeOptions::default());
+        let mut model = script_model(true);
+
+        // First full window: nothing final yet
+        assert!(t.feed(&mut model, &vec![0.5f32; 36000]).unwrap().is_empty());
+        // Second window finalizes the first
+        let results = t.feed(&mut model, &vec![0.5f32; 24000]).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].text, "alpha bravo charlie delta");
+    }
+
+    #[test]
+    fn overlap_skips_trailing_overlap_only_remainder() {
+        let mut t = OverlapChunked::new(config(), TranscribeOptions::default());
+        let mut model = MockModel;
+
+        // Exactly one window: the remainder after the stride is overlap
+        // audio already covered, so finish() must not re-transcribe it.
+        let result = t.transcribe(&mut model, &vec![0.5f32; 36000]).unwrap();
+        assert_eq!(result.text, "chunk_36000");
+    }
+
+    #[test]
+    fn overlap_short_input_single_window() {
+        let mut t = OverlapChunked::new(config(), TranscribeOptions::default());
+        let mut model = MockModel;
+        let result = t.transcribe(&mut model, &vec![0.5f32; 8000]).unwrap();
+        assert_eq!(result.text, "chunk_8000");
+
+        let result = t.transcribe(&mut model, &[]).unwrap();
+        assert_eq!(result.text, "");
+    }
+
+    #[test]
+    fn overlap_clamped_to_half_chunk() {
+        let t = OverlapChunked::new(
+            OverlapChunkedConfig {
+                chunk_secs: 1.0,
+                overlap_secs: 5.0,
+                ..Default::default()
+            },
+            TranscribeOptions::default(),
+        );
+        assert_eq!(t.overlap_samples, 8000);
+    }
+}
@@ -0,0 +1,295 @@
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::{audio, SpeechModel, TranscribeError, TranscriptionResult};
+
+use super::merge::merge_sequential_with_separator;
+use super::{PlannedChunk, Transcriber, SAMPLE_RATE};
+
+/// Configuration for [`ParallelChunked`].
+pub struct ParallelConfig {
+    /// Separator inserted between chunk texts when merging.
+    /// Use `" "` for most languages, `""` for CJK.
+    pub merge_separator: String,
+}
+
+impl Default for ParallelConfig {
+    fn default() -> Self {
+        Self {
+            merge_separator: " ".into(),
+        }
+    }
+}
+
+/// Parallel file transcription over a pool of models.
+///
+/// Splits the audio once using a serial chunking strategy (e.g.
+/// [`VadChunked`](super::VadChunked) or
+/// [`EnergyAdaptiveChunked`](super::EnergyAdaptiveChunked)), then transcribes
+/// the chunks on one worker thread per model and merges the results in
+/// chunk order.
+///
+/// The split comes from [`Transcriber::plan`], so the chunk boundaries,
+/// padding and minimum durations are exactly those of serial transcription.
+/// Strategies that can't plan their chunks (such as
+/// [`OverlapChunked`](super::OverlapChunked), which reconciles overlapping
+/// transcripts) are rejected with [`TranscribeError::Config`].
+///
+/// Each model in the pool should be an independent instance. For ONNX
+/// engines, loading each instance with a small intra-op thread count (e.g.
+/// one) usually gives the best throughput on many-core machines.
+///
+/// ```ignore
+/// let splitter = EnergyAdaptiveChunked::new(EnergyAdaptiveConfig::default(), options);
+/// let mut models: Vec<Box<dyn SpeechModel>> = (0..8).map(|_| load_model()).collect();
+/// let mut parallel = ParallelChunked::new(Box::new(splitter), ParallelConfig::default());
+/// let result = parallel.transcribe_file(&mut models, &path)?;
+/// ```
+pub struct ParallelChunked {
+    splitter: Box<dyn Transcriber>,
+    config: ParallelConfig,
+}
+
+impl ParallelChunked {
+    pub fn new(splitter: Box<dyn Transcriber>, config: ParallelConfig) -> Self {
+        Self { splitter, config }
+    }
+
+    /// Transcribe `samples` using every model in `models` concurrently.
+    ///
+    /// On failure, returns the error of the earliest failing chunk; workers
+    /// stop picking up new chunks once any chunk has failed.
+    pub fn transcribe(
+        &mut self,
+        models: &mut [Box<dyn SpeechModel>],
+        samples: &[f32],
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        if models.is_empty() {
+            return Err(TranscribeError::Config(
+                "parallel transcription needs at least one model".into(),
+            ));
+        }
+
+        let chunks = self.splitter.plan(samples)?;
+        log::info!(
+            "parallel: {} chunks across {} models",
+            chunks.len(),
+            models.len()
+        );
+
+        let next = AtomicUsize::new(0);
+        let failed = AtomicBool::new(false);
+        let mut slots: Vec<Option<Result<TranscriptionResult, TranscribeError>>> =
+            (0..chunks.len()).map(|_| None).collect();
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = models
+                .iter_mut()
+                .map(|model| {
+                    let (chunks, next, failed) = (&chunks, &next, &failed);
+                    scope.spawn(move || run_worker(model.as_mut(), chunks, next, failed))
+                })
+                .collect();
+
+            for handle in handles {
+                match handle.join() {
+                    Ok(done) => {
+                        for (index, result) in done {
+                            slots[index] = Some(result);
+                        }
+                    }
+                    Err(_) => {
+                        failed.store(true, Ordering::Relaxed);
+                        log::error!("parallel: worker thread panicked");
+                    }
+                }
+            }
+        });
+
+        let mut results = Vec::with_capacity(chunks.len());
+        for (index, slot) in slots.into_iter().enumerate() {
+            match slot {
+                Some(Ok(result)) => results.push(result),
+                Some(Err(e)) => return Err(e),
+                None => {
+                    return Err(TranscribeError::Inference(format!(
+                        "chunk {index} was not transcribed (worker panicked)"
+                    )))
+                }
+            }
+        }
+
+        Ok(merge_sequential_with_separator(
+            &results,
+            &self.config.merge_separator,
+        ))
+    }
+
+    /// Convenience: load a WAV file and transcribe it in parallel.
+    pub fn transcribe_file(
+        &mut self,
+        models: &mut [Box<dyn SpeechModel>],
+        path: &Path,
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        let samples = audio::read_wav_samples(path)?;
+        self.transcribe(models, &samples)
+    }
+}
+
+/// Transcribe chunks until the queue is exhausted or a chunk fails.
+fn run_worker(
+    model: &mut dyn SpeechModel,
+    chunks: &[PlannedChunk],
+    next: &AtomicUsize,
+    failed: &AtomicBool,
+) -> Vec<(usize, Result<TranscriptionResult, TranscribeError>)> {
+    let mut done = Vec::new();
+    while !failed.load(Ordering::Relaxed) {
+        let index = next.fetch_add(1, Ordering::Relaxed);
+        let Some(chunk) = chunks.get(index) else {
+            break;
+        };
+        log::debug!(
+            "parallel: chunk {} start={:.2}s duration={:.2}s",
+            index,
+            chunk.start_sample as f32 / SAMPLE_RATE,
+            chunk.samples.len() as f32 / SAMPLE_RATE
+        );
+
+        let result = chunk.transcribe(model);
+        if result.is_err() {
+            failed.store(true, Ordering::Relaxed);
+        }
+        done.push((index, result));
+    }
+    done
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcriber::test_helpers::{make_silence, make_speech, FailOnNthModel, MockModel};
+    use crate::transcriber::{
+        EnergyAdaptiveChunked, EnergyAdaptiveConfig, OverlapChunked, OverlapChunkedConfig,
+        VadChunked, VadChunkedConfig,
+    };
+    use crate::vad::EnergyVad;
+    use crate::TranscribeOptions;
+
+    fn energy_splitter() -> Box<dyn Transcriber> {
+        Box::new(EnergyAdaptiveChunked::new(
+            EnergyAdaptiveConfig {
+                target_chunk_secs: 1.0,
+                search_window_secs: 0.0,
+                ..Default::default()
+            },
+            TranscribeOptions::default(),
+        ))
+    }
+
+    fn pool(n: usize) -> Vec<Box<dyn SpeechModel>> {
+        (0..n)
+            .map(|_| Box::new(MockModel) as Box<dyn SpeechModel>)
+            .collect()
+    }
+
+    #[test]
+    fn parallel_matches_serial() {
+        let audio: Vec<f32> = (0..16000 * 5 + 4000)
+            .map(|i| (i as f32 * 0.01).sin())
+            .collect();
+
+        let serial = energy_splitter()
+            .transcribe(&mut MockModel, &audio)
+            .unwrap();
+        let mut parallel = ParallelChunked::new(energy_splitter(), ParallelConfig::default());
+        let result = parallel.transcribe(&mut pool(3), &audio).unwrap();
+
+        assert_eq!(result.text, serial.text);
+        let (a, b) = (result.segments.unwrap(), serial.segments.unwrap());
+        assert_eq!(a.len(), 6);
+        for (x, y) in a.iter().zip(&b) {
+            assert_eq!(x.text, y.text);
+            assert!((x.start - y.start).abs() < 1e-4);
+            assert!((x.end - y.end).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn parallel_vad_offsets_by_region_start() {
+        let splitter = VadChunked::new(
+            Box::new(EnergyVad::new(480, 0.01)),
+            VadChunkedConfig::default(),
+            TranscribeOptions::default(),
+        );
+        let mut parallel = ParallelChunked::new(Box::new(splitter), ParallelConfig::default());
+
+        let mut audio = make_silence(480, 50);
+        audio.extend(make_speech(480, 20));
+        audio.extend(make_silence(480, 50));
+        let result = parallel.transcribe(&mut pool(2), &audio).unwrap();
+
+        let segs = result.segments.unwrap();
+        assert_eq!(segs.len(), 1);
+        assert!(segs[0].start > 1.0, "segment start {}", segs[0].start);
+    }
+
+    #[test]
+    fn parallel_reports_chunk_error() {
+        let mut parallel = ParallelChunked::new(energy_splitter(), ParallelConfig::default());
+        let mut models: Vec<Box<dyn SpeechModel>> = vec![Box::new(FailOnNthModel::new(1))];
+        let err = parallel
+            .transcribe(&mut models, &vec![0.5f32; 16000 * 3])
+            .unwrap_err();
+        assert!(matches!(err, TranscribeError::Inference(_)));
+    }
+
+    #[test]
+    fn parallel_plans_borrowed_chunks_and_rejects_overlap() {
+        let audio: Vec<f32> = (0..16000 * 3).map(|i| (i as f32 * 0.01).sin()).collect();
+        let chunks = energy_splitter().plan(&audio).unwrap();
+        let starts: Vec<_> = chunks.iter().map(|c| c.start_sample).collect();
+        assert_eq!(starts, [0, 16000, 32000]);
+        assert!(chunks
+            .iter()
+            .all(|c| matches!(c.samples, std::borrow::Cow::Borrowed(_))));
+
+        let overlap = OverlapChunked::new(
+            OverlapChunkedConfig {
+                chunk_secs: 1.0,
+                overlap_secs: 0.5,
+                ..Default::default()
+            },
+            TranscribeOptions::default(),
+        );
+        let mut parallel = ParallelChunked::new(Box::new(overlap), ParallelConfig::default());
+        let err = parallel.transcribe(&mut pool(2), &audio).unwrap_err();
+        assert!(matches!(err, TranscribeError::Config(_)));
+    }
+
+    #[test]
+    fn parallel_planning_reports_no_events() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let splitter = EnergyAdaptiveChunked::new(
+            EnergyAdaptiveConfig {
+                target_chunk_secs: 1.0,
+                search_window_secs: 0.0,
+                ..Default::default()
+            },
+            TranscribeOptions::default(),
+        )
+        .with_observer(tx);
+        let mut parallel = ParallelChunked::new(Box::new(splitter), ParallelConfig::default());
+        parallel
+            .transcribe(&mut pool(2), &vec![0.5f32; 16000 * 3])
+            .unwrap();
+        assert_eq!(rx.try_iter().count(), 0);
+    }
+
+    #[test]
+    fn parallel_requires_models() {
+        let mut parallel = ParallelChunked::new(energy_splitter(), ParallelConfig::default());
+        let err = parallel.transcribe(&mut [], &[0.0; 100]).unwrap_err();
+        assert!(matches!(err, TranscribeError::Config(_)));
+    }
+}
@@ -15,6 +15,7 @@ impl SpeechModel for MockModel {
             sample_rate: 16000,
             languages: &[],
             supports_timestamps: false,
+            supports_word_timestamps: false,
             supports_translation: false,
             supports_streaming: false,
         }
@@ -59,6 +60,7 @@ impl SpeechModel for FailOnNthModel {
             sample_rate: 16000,
             languages: &[],
             supports_timestamps: false,
+            supports_word_timestamps: false,
             supports_translation: false,
             supports_streaming: false,
         }
@@ -1,8 +1,12 @@
 use crate::vad::Vad;
-use crate::{SpeechModel, TranscribeError, TranscribeOptions, TranscriptionResult};
+use crate::{cancel, SpeechModel, TranscribeError, TranscribeOptions, TranscriptionResult};
 
 use super::merge::merge_sequential_with_separator;
-use super::{rms_energy, transcribe_padded, Transcriber, SAMPLE_RATE};
+use super::observer::EventSink;
+use super::{
+    rms_energy, transcribe_padded, ChunkResult, ChunkSink, PlannedChunk, Transcriber,
+    TranscriberObserver, SAMPLE_RATE,
+};
 
 /// Configuration for [`VadChunked`].
 pub struct VadChunkedConfig {
@@ -26,6 +30,12 @@ pub struct VadChunkedConfig {
     /// Separator inserted between chunk texts when merging.
     /// Use `" "` for most languages, `""` for CJK.
     pub merge_separator: String,
+    /// If set, [`feed_with_interim()`](Transcriber::feed_with_interim)
+    /// re-transcribes the in-progress speech buffer every time this many
+    /// seconds of new speech have been buffered, and returns the result as
+    /// [`ChunkResult::Interim`]. `None` disables interim results.
+    /// [`feed()`](Transcriber::feed) never produces interim results.
+    pub interim_interval_secs: Option<f32>,
 }
 
 impl Default for VadChunkedConfig {
@@ -36,6 +46,7 @@ impl Default for VadChunkedConfig {
             padding_secs: 0.0,
             smart_split_search_secs: None,
             merge_separator: " ".into(),
+            interim_interval_secs: None,
         }
     }
 }
@@ -48,10 +59,14 @@ impl Default for VadChunkedConfig {
 ///
 /// Works for both live audio (small per-frame feeds) and file
 /// transcription (one large feed).
+///
+/// Reports chunk and progress events to an observer set with
+/// [`with_observer()`](VadChunked::with_observer).
 pub struct VadChunked {
     vad: Box<dyn Vad>,
     config: VadChunkedConfig,
     options: TranscribeOptions,
+    events: EventSink,
     // internal state
     speech_buffer: Vec<f32>,
     /// Accumulates sub-frame remainders across `feed()` calls so they
@@ -64,6 +79,8 @@ pub struct VadChunked {
     /// buffer. Used for accurate timestamp calculation, especially when
     /// short speech carries forward across silence gaps.
     speech_start_sample: Option<usize>,
+    /// Speech samples buffered since the last interim transcription.
+    samples_since_interim: usize,
     chunk_index: usize,
     results: Vec<TranscriptionResult>,
 }
@@ -74,25 +91,33 @@ impl VadChunked {
             vad,
             config,
             options,
+            events: EventSink::default(),
             speech_buffer: Vec::new(),
             pending: Vec::new(),
             in_speech: false,
             elapsed_samples: 0,
             speech_start_sample: None,
+            samples_since_interim: 0,
             chunk_index: 0,
             results: Vec::new(),
         }
     }
 
+    /// Report [`TranscriberEvent`](super::TranscriberEvent)s to `observer`.
+    pub fn with_observer(mut self, observer: impl TranscriberObserver + 'static) -> Self {
+        self.events.set_observer(Box::new(observer));
+        self
+    }
+
     /// When force-splitting, scan backward over `search_secs` of the
     /// speech buffer to find the frame with the lowest RMS energy. Splits
     /// the buffer there, transcribes the first part, and keeps the
     /// remainder for the next chunk.
     fn smart_split_buffer(
         &mut self,
-        model: &mut dyn SpeechModel,
+        sink: &mut ChunkSink,
         search_secs: f32,
-    ) -> Result<TranscriptionResult, TranscribeError> {
+    ) -> Result<Option<TranscriptionResult>, TranscribeError> {
         let frame_size = self.vad.frame_size();
         let search_samples = (search_secs * SAMPLE_RATE) as usize;
         let buf_len = self.speech_buffer.len();
@@ -126,11 +151,10 @@ impl VadChunked {
 
         // Drain the chunk; remainder stays in speech_buffer.
         let chunk: Vec<f32> = self.speech_buffer.drain(..best_offset).collect();
-        let chunk_start_secs = self.speech_start_sample.unwrap_or_else(|| {
+        let chunk_start_sample = self.speech_start_sample.unwrap_or_else(|| {
             self.elapsed_samples
                 .saturating_sub(self.speech_buffer.len() + chunk.len())
-        }) as f32
-            / SAMPLE_RATE;
+        });
 
         // Update speech_start_sample for the remainder
         if self.speech_buffer.is_empty() {
@@ -139,33 +163,70 @@ impl VadChunked {
             self.speech_start_sample = self.speech_start_sample.map(|s| s + best_offset);
         }
 
-        self.transcribe_chunk(model, chunk, chunk_start_secs)
+        self.transcribe_chunk(sink, chunk, chunk_start_sample)
     }
 
-    /// Take the entire speech buffer and transcribe it as one chunk.
-    fn flush_speech_buffer(
+    /// Transcribe the in-progress speech buffer without consuming it.
+    /// The result is not recorded in the session results.
+    fn transcribe_interim(
         &mut self,
         model: &mut dyn SpeechModel,
     ) -> Result<TranscriptionResult, TranscribeError> {
+        self.samples_since_interim = 0;
+        let chunk_start_sample = self.speech_start_sample.unwrap_or_else(|| {
+            self.elapsed_samples
+                .saturating_sub(self.speech_buffer.len())
+        });
+
+        log::debug!(
+            "interim: start={:.2}s buffered={:.2}s",
+            chunk_start_sample as f32 / SAMPLE_RATE,
+            self.speech_buffer.len() as f32 / SAMPLE_RATE,
+        );
+
+        transcribe_padded(
+            model,
+            &self.speech_buffer,
+            self.config.padding_secs,
+            self.config.min_chunk_secs,
+            chunk_start_sample,
+            &self.options,
+        )
+    }
+
+    /// Take the entire speech buffer and transcribe it as one chunk.
+    fn flush_speech_buffer(
+        &mut self,
+        sink: &mut ChunkSink,
+    ) -> Result<Option<TranscriptionResult>, TranscribeError> {
         let samples = std::mem::take(&mut self.speech_buffer);
-        let chunk_start_secs = self
+        let chunk_start_sample = self
             .speech_start_sample
-            .unwrap_or_else(|| self.elapsed_samples.saturating_sub(samples.len()))
-            as f32
-            / SAMPLE_RATE;
+            .unwrap_or_else(|| self.elapsed_samples.saturating_sub(samples.len()));
         self.speech_start_sample = None;
-        self.transcribe_chunk(model, samples, chunk_start_secs)
+        self.transcribe_chunk(sink, samples, chunk_start_sample)
     }
 
     /// Transcribe a chunk of audio samples at a given position. Does not
     /// touch `self.speech_buffer` or `self.speech_start_sample` — callers
-    /// manage buffer state before calling this.
+    /// manage buffer state before calling this. When planning, records the
+    /// chunk and returns `None`.
     fn transcribe_chunk(
         &mut self,
-        model: &mut dyn SpeechModel,
+        sink: &mut ChunkSink,
         samples: Vec<f32>,
-        chunk_start_secs: f32,
-    ) -> Result<TranscriptionResult, TranscribeError> {
+        chunk_start_sample: usize,
+    ) -> Result<Option<TranscriptionResult>, TranscribeError> {
+        if let ChunkSink::Plan { .. } = sink {
+            return sink.chunk(
+                &samples,
+                self.config.padding_secs,
+                self.config.min_chunk_secs,
+                chunk_start_sample,
+                &self.options,
+            );
+        }
+        let chunk_start_secs = chunk_start_sample as f32 / SAMPLE_RATE;
         log::info!(
             "chunk {}: start={:.2}s duration={:.2}s samples={} padding={:.0}ms",
             self.chunk_index,
@@ -175,26 +236,34 @@ impl VadChunked {
             self.config.padding_secs * 1000.0,
         );
 
+        let index = self.chunk_index;
         self.chunk_index += 1;
+        self.samples_since_interim = 0;
+        self.events
+            .chunk_started(index, chunk_start_secs, samples.len());
 
-        let result = transcribe_padded(
-            model,
+        let Some(result) = sink.chunk(
             &samples,
             self.config.padding_secs,
             self.config.min_chunk_secs,
-            chunk_start_secs,
+            chunk_start_sample,
             &self.options,
-        )?;
+        )?
+        else {
+            return Ok(None);
+        };
 
         log::info!("  -> \"{}\"", result.text.trim());
+        self.events
+            .chunk_transcribed(index, &result, self.elapsed_samples);
 
         self.results.push(result.clone());
-        Ok(result)
+        Ok(Some(result))
     }
 
     fn finish_inner(
         &mut self,
-        model: &mut dyn SpeechModel,
+        sink: &mut ChunkSink,
     ) -> Result<TranscriptionResult, TranscribeError> {
         // Flush any pending sub-frame samples into the speech buffer.
         // Pending holds at most frame_size-1 samples (~29ms at 480/16kHz) that
@@ -215,7 +284,7 @@ impl VadChunked {
                 "finish: transcribing remaining buffer ({:.2}s)",
                 self.speech_buffer.len() as f32 / SAMPLE_RATE
             );
-            self.flush_speech_buffer(model)?;
+            self.flush_speech_buffer(sink)?;
         }
         log::info!("session complete: {} chunks transcribed", self.chunk_index);
         Ok(merge_sequential_with_separator(
@@ -231,18 +300,27 @@ impl VadChunked {
         self.in_speech = false;
         self.elapsed_samples = 0;
         self.speech_start_sample = None;
+        self.samples_since_interim = 0;
         self.chunk_index = 0;
         self.vad.reset();
+        self.events.set_total_samples(None);
     }
-}
 
-impl Transcriber for VadChunked {
-    fn feed(
+    /// Shared implementation of `feed()` and `feed_with_interim()`.
+    /// Interim transcriptions only run when `interim` is set and chunks go
+    /// to a model.
+    fn feed_inner(
         &mut self,
-        model: &mut dyn SpeechModel,
+        sink: &mut ChunkSink,
         samples: &[f32],
-    ) -> Result<Vec<TranscriptionResult>, TranscribeError> {
+        interim: bool,
+    ) -> Result<Vec<ChunkResult>, TranscribeError> {
         let frame_size = self.vad.frame_size();
+        let interim_samples = self
+            .config
+            .interim_interval_secs
+            .filter(|_| interim)
+            .map(|secs| ((secs * SAMPLE_RATE) as usize).max(1));
         let mut new_results = Vec::new();
 
         // Combine pending sub-frame samples from previous call
@@ -262,6 +340,7 @@ impl Transcriber for VadChunked {
                 continue;
             }
 
+            cancel::check(self.options.cancel.as_ref())?;
             let is_speech = self.vad.is_speech(frame)?;
             self.elapsed_samples += frame_size;
 
@@ -274,8 +353,10 @@ impl Transcriber for VadChunked {
                             Some((self.elapsed_samples - frame_size).saturating_sub(prefill.len()));
                     }
                     self.speech_buffer.extend_from_slice(&prefill);
+                    self.samples_since_interim += prefill.len();
                 }
                 self.speech_buffer.extend_from_slice(frame);
+                self.samples_since_interim += frame_size;
                 self.in_speech = true;
 
                 // Force-split if exceeding max duration
@@ -287,11 +368,15 @@ impl Transcriber for VadChunked {
                         self.config.max_chunk_secs
                     );
                     let result = if let Some(search_secs) = self.config.smart_split_search_secs {
-                        self.smart_split_buffer(model, search_secs)?
+                        self.smart_split_buffer(sink, search_secs)?
                     } else {
-                        self.flush_speech_buffer(model)?
+                        self.flush_speech_buffer(sink)?
                     };
-                    new_results.push(result);
+                    new_results.extend(result.map(ChunkResult::Final));
+                } else if interim_samples.is_some_and(|n| self.samples_since_interim >= n) {
+                    if let ChunkSink::Model(model) = sink {
+                        new_results.push(ChunkResult::Interim(self.transcribe_interim(*model)?));
+                    }
                 }
             } else if self.in_speech {
                 // Speech -> silence transition: transcribe the chunk
@@ -304,7 +389,8 @@ impl Transcriber for VadChunked {
                             self.elapsed_samples as f32 / SAMPLE_RATE,
                             chunk_secs
                         );
-                        new_results.push(self.flush_speech_buffer(model)?);
+                        let result = self.flush_speech_buffer(sink)?;
+                        new_results.extend(result.map(ChunkResult::Final));
                     } else {
                         // Keep short speech in buffer so it merges with the
                         // next speech region (don't lose brief utterances)
@@ -320,15 +406,70 @@ impl Transcriber for VadChunked {
 
         Ok(new_results)
     }
+}
+
+impl Transcriber for VadChunked {
+    fn feed(
+        &mut self,
+        model: &mut dyn SpeechModel,
+        samples: &[f32],
+    ) -> Result<Vec<TranscriptionResult>, TranscribeError> {
+        Ok(self
+            .feed_inner(&mut ChunkSink::Model(model), samples, false)?
+            .into_iter()
+            .map(ChunkResult::into_result)
+            .collect())
+    }
+
+    fn feed_with_interim(
+        &mut self,
+        model: &mut dyn SpeechModel,
+        samples: &[f32],
+    ) -> Result<Vec<ChunkResult>, TranscribeError> {
+        self.feed_inner(&mut ChunkSink::Model(model), samples, true)
+    }
 
     fn finish(
         &mut self,
         model: &mut dyn SpeechModel,
     ) -> Result<TranscriptionResult, TranscribeError> {
-        let result = self.finish_inner(model);
+        let result = self.finish_inner(&mut ChunkSink::Model(model));
+        if let Ok(result) = &result {
+            self.events.finished(self.elapsed_samples, result);
+        }
         self.reset_state();
         result
     }
+
+    fn transcribe(
+        &mut self,
+        model: &mut dyn SpeechModel,
+        samples: &[f32],
+    ) -> Result<TranscriptionResult, TranscribeError> {
+        self.events
+            .set_total_samples(Some(self.elapsed_samples + samples.len()));
+        if let Err(e) = self.feed(model, samples) {
+            // Drop the failed session's audio and results (and its total),
+            // like a failed finish() does.
+            self.reset_state();
+            return Err(e);
+        }
+        self.finish(model)
+    }
+
+    fn plan<'a>(&mut self, samples: &'a [f32]) -> Result<Vec<PlannedChunk<'a>>, TranscribeError> {
+        self.reset_state();
+        let mut chunks = Vec::new();
+        let mut sink = ChunkSink::Plan {
+            input: samples,
+            chunks: &mut chunks,
+        };
+        let planned = self
+            .feed_inner(&mut sink, samples, false)
+            .and_then(|_| self.finish_inner(&mut sink));
+        self.reset_state();
+        planned.map(|_| chunks)
+    }
 }
 
 #[cfg(test)]
@@ -732,6 +873,79 @@ mod tests {
         assert_eq!(results[0].text, "chunk_2400");
     }
 
+    #[test]
+    fn vad_chunked_interim_results_during_speech() {
+        let vad = EnergyVad::new(480, 0.01);
+        let config = VadChunkedConfig {
+            min_chunk_secs: 0.0,
+            interim_interval_secs: Some(0.09), // every 3 frames
+            ..Default::default()
+        };
+        let mut t = VadChunked::new(Box::new(vad), config, TranscribeOptions::default());
+        let mut model = MockModel;
+
+        let mut interim = Vec::new();
+        for _ in 0..10 {
+            for r in t
+                .feed_with_interim(&mut model, &make_speech(480, 1))
+                .unwrap()
+            {
+                assert!(!r.is_final());
+                interim.push(r.into_result().text);
+            }
+        }
+        // Interim results re-transcribe the growing buffer
+        assert_eq!(interim, ["chunk_1440", "chunk_2880", "chunk_4320"]);
+
+        let results = t
+            .feed_with_interim(&mut model, &make_silence(480, 5))
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_final());
+        assert_eq!(results[0].result().text, "chunk_4800");
+
+        // Interim results are not part of the session result
+        let final_result = t.finish(&mut model).unwrap();
+        assert_eq!(final_result.text, "chunk_4800");
+    }
+
+    #[test]
+    fn vad_chunked_feed_ignores_interim_interval() {
+        let vad = EnergyVad::new(480, 0.01);
+        let config = VadChunkedConfig {
+            min_chunk_secs: 0.0,
+            interim_interval_secs: Some(0.03),
+            ..Default::default()
+        };
+        let mut t = VadChunked::new(Box::new(vad), config, TranscribeOptions::default());
+        let mut model = MockModel;
+
+        let results = t.feed(&mut model, &make_speech(480, 10)).unwrap();
+        assert!(results.is_empty());
+        let results = t.feed(&mut model, &make_silence(480, 5)).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].text, "chunk_4800");
+    }
+
+    #[test]
+    fn vad_chunked_interim_timestamps_offset() {
+        let vad = EnergyVad::new(480, 0.01);
+        let config = VadChunkedConfig {
+            min_chunk_secs: 0.0,
+            interim_interval_secs: Some(0.3),
+            ..Default::default()
+        };
+        let mut t = VadChunked::new(Box::new(vad), config, TranscribeOptions::default());
+        let mut model = MockModel;
+
+        let mut audio = make_silence(480, 34);
+        audio.extend(make_speech(480, 10));
+        let results = t.feed_with_interim(&mut model, &audio).unwrap();
+        assert_eq!(results.len(), 1);
+        let segs = results[0].result().segments.as_ref().unwrap();
+        assert!(segs[0].start > 0.9, "got {}", segs[0].start);
+    }
+
     #[test]
     fn vad_chunked_reusable_after_error() {
         let vad = EnergyVad::new(480, 0.01);
@@ -750,5 +964,60 @@ mod tests {
         let mut model = MockModel;
         let result = t.transcribe(&mut model, &speech).unwrap();
         assert_eq!(result.text, "chunk_4800");
+
+        // A transcribe() that fails while feeding leaves nothing behind.
+        let mut audio = make_speech(480, 10);
+        audio.extend(make_silence(480, 10));
+        audio.extend(make_speech(480, 10));
+        audio.extend(make_silence(480, 10));
+        assert!(t.transcribe(&mut FailOnNthModel::new(2), &audio).is_err());
+        let result = t.transcribe(&mut model, &speech).unwrap();
+        assert_eq!(result.text, "chunk_4800");
+        assert_eq!(result.segments.unwrap()[0].start, 0.0);
+    }
+
+    #[test]
+    fn vad_chunked_reports_chunk_events() {
+        use crate::transcriber::TranscriberEvent;
+        use std::sync::{Arc, Mutex};
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&events);
+        let config = VadChunkedConfig {
+            min_chunk_secs: 0.0,
+            ..Default::default()
+        };
+        let mut t = VadChunked::new(
+            Box::new(EnergyVad::new(480, 0.01)),
+            config,
+            TranscribeOptions::default(),
+        )
+        .with_observer(move |e: &TranscriberEvent| sink.lock().unwrap().push(e.clone()));
+
+        let mut audio = make_silence(480, 10);
+        audio.extend(make_speech(480, 10));
+        audio.extend(make_silence(480, 10));
+        t.transcribe(&mut MockModel, &audio).unwrap();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 5);
+        match &events[0] {
+            TranscriberEvent::ChunkStarted {
+                index,
+                start_secs,
+                duration_secs,
+            } => {
+                assert_eq!(*index, 0);
+                assert!((start_secs - 0.3).abs() < 1e-3);
+                assert!((duration_secs - 0.3).abs() < 1e-3);
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+        assert!(
+            matches!(&events[1], TranscriberEvent::ChunkTranscribed { result, .. } if result.text == "chunk_4800")
+        );
+        assert!(events[2].fraction().unwrap() < 1.0);
+        assert_eq!(events[3].fraction(), Some(1.0));
+        assert!(matches!(&events[4], TranscriberEvent::Finished { .. }));
     }
 }
@@ -0,0 +1,306 @@
+use std::collections::VecDeque;
+use std::f32::consts::PI;
+
+use crate::transcriber::SAMPLE_RATE;
+use crate::TranscribeError;
+
+use super::Vad;
+
+/// Configuration for [`AdaptiveEnergyVad`].
+#[derive(Debug, Clone)]
+pub struct AdaptiveEnergyConfig {
+    /// Number of samples per frame. Default: 480 (30ms at 16kHz).
+    pub frame_size: usize,
+    /// A frame is speech when its energy exceeds the noise floor by at
+    /// least this many decibels.
+    pub snr_margin_db: f32,
+    /// Length of the minimum-statistics window in seconds. The noise floor
+    /// is the lowest smoothed frame energy over this window, so it follows
+    /// gain changes within about this long. Must exceed the longest
+    /// stretch of speech without a pause.
+    pub noise_window_secs: f32,
+    /// Lower bound for the noise floor in dBFS, so digital silence does
+    /// not make every faint sound count as speech.
+    pub min_noise_floor_db: f32,
+    /// If set, frames whose spectral flatness (0 = tonal, 1 = white noise)
+    /// exceeds this are rejected as noise. Typical: 0.5.
+    pub max_spectral_flatness: Option<f32>,
+    /// If set, frames whose zero-crossing rate (fraction of adjacent sample
+    /// pairs that change sign) exceeds this are rejected as noise.
+    /// Typical: 0.35.
+    pub max_zero_crossing_rate: Option<f32>,
+}
+
+impl Default for AdaptiveEnergyConfig {
+    fn default() -> Self {
+        Self {
+            frame_size: 480,
+            snr_margin_db: 9.0,
+            noise_window_secs: 3.0,
+            min_noise_floor_db: -70.0,
+            max_spectral_flatness: None,
+            max_zero_crossing_rate: None,
+        }
+    }
+}
+
+/// Energy VAD with an adaptive noise floor. Zero dependencies.
+///
+/// Unlike [`EnergyVad`](super::EnergyVad), the threshold is relative: the
+/// noise floor is tracked with minimum statistics (the minimum of smoothed
+/// frame energy over a sliding window) and a frame is speech when it is
+/// [`snr_margin_db`](AdaptiveEnergyConfig::snr_margin_db) above that floor.
+/// This keeps working when recording gain or background level changes.
+///
+/// Spectral flatness and zero-crossing rate can optionally veto loud but
+/// noise-like frames.
+///
+/// The floor starts at the first frame's energy, so speech at the very
+/// start of a stream is only detected once a pause has established the
+/// floor.
+pub struct AdaptiveEnergyVad {
+    config: AdaptiveEnergyConfig,
+    window_frames: usize,
+    // internal state
+    smoothed_db: Option<f32>,
+    history: VecDeque<f32>,
+}
+
+/// Smoothing factor of the frame energy EMA used for floor tracking.
+const ENERGY_SMOOTHING: f32 = 0.7;
+
+/// Decibels of SNR excess per unit of logit in `speech_probability()`.
+const PROBABILITY_SCALE_DB: f32 = 2.0;
+
+impl AdaptiveEnergyVad {
+    pub fn new(config: AdaptiveEnergyConfig) -> Self {
+        let window_frames =
+            ((config.noise_window_secs * SAMPLE_RATE) as usize / config.frame_size.max(1)).max(1);
+        Self {
+            config,
+            window_frames,
+            smoothed_db: None,
+            history: VecDeque::new(),
+        }
+    }
+
+    /// Current noise floor estimate in dBFS, or `None` before the first frame.
+    pub fn noise_floor_db(&self) -> Option<f32> {
+        self.history
+            .iter()
+            .copied()
+            .reduce(f32::min)
+            .map(|floor| floor.max(self.config.min_noise_floor_db))
+    }
+
+    /// Update the floor with `frame` and return its SNR in dB, or `None`
+    /// if a spectral feature vetoed it.
+    fn evaluate(&mut self, frame: &[f32]) -> Result<Option<f32>, TranscribeError> {
+        if frame.len() != self.config.frame_size {
+            return Err(TranscribeError::Audio(format!(
+                "expected {} samples, got {}",
+                self.config.frame_size,
+                frame.len()
+            )));
+        }
+
+        let energy_db = energy_db(frame);
+        let smoothed = match self.smoothed_db {
+            Some(prev) => ENERGY_SMOOTHING * prev + (1.0 - ENERGY_SMOOTHING) * energy_db,
+            None => energy_db,
+        };
+        self.smoothed_db = Some(smoothed);
+        self.history.push_back(smoothed);
+        while self.history.len() > self.window_frames {
+            self.history.pop_front();
+        }
+
+        let floor = self.noise_floor_db().unwrap_or(energy_db);
+        let snr = energy_db - floor;
+
+        if let Some(max) = self.config.max_zero_crossing_rate {
+            if zero_crossing_rate(frame) > max {
+                return Ok(None);
+            }
+        }
+        if let Some(max) = self.config.max_spectral_flatness {
+            if snr > self.config.snr_margin_db && spectral_flatness(frame) > max {
+                return Ok(None);
+            }
+        }
+        Ok(Some(snr))
+    }
+}
+
+impl Vad for AdaptiveEnergyVad {
+    fn frame_size(&self) -> usize {
+        self.config.frame_size
+    }
+
+    fn is_speech(&mut self, frame: &[f32]) -> Result<bool, TranscribeError> {
+        Ok(self
+            .evaluate(frame)?
+            .is_some_and(|snr| snr > self.config.snr_margin_db))
+    }
+
+    /// Logistic function of the SNR excess over the margin: 0.5 at the
+    /// margin, and `0.0` for frames vetoed by a spectral feature.
+    fn speech_probability(&mut self, frame: &[f32]) -> Result<f32, TranscribeError> {
+        Ok(match self.evaluate(frame)? {
+            Some(snr) => {
+                let x = (snr - self.config.snr_margin_db) / PROBABILITY_SCALE_DB;
+                1.0 / (1.0 + (-x).exp())
+            }
+            None => 0.0,
+        })
+    }
+
+    fn reset(&mut self) {
+        self.smoothed_db = None;
+        self.history.clear();
+    }
+}
+
+fn energy_db(frame: &[f32]) -> f32 {
+    let mean_square = frame.iter().map(|s| s * s).sum::<f32>() / frame.len().max(1) as f32;
+    10.0 * (mean_square + 1e-10).log10()
+}
+
+fn zero_crossing_rate(frame: &[f32]) -> f32 {
+    if frame.len() < 2 {
+        return 0.0;
+    }
+    let crossings = frame
+        .windows(2)
+        .filter(|w| (w[0] >= 0.0) != (w[1] >= 0.0))
+        .count();
+    crossings as f32 / (frame.len() - 1) as f32
+}
+
+/// Spectral flatness (Wiener entropy) of the frame's power spectrum:
+/// geometric mean over arithmetic mean, in `[0, 1]`.
+///
+/// Uses a direct DFT, which is fast enough for VAD-sized frames and keeps
+/// this module dependency-free.
+fn spectral_flatness(frame: &[f32]) -> f32 {
+    let n = frame.len();
+    let bins = n / 2;
+    if bins < 2 {
+        return 0.0;
+    }
+
+    let mut log_sum = 0.0f64;
+    let mut sum = 0.0f64;
+    for k in 1..=bins {
+        let (mut re, mut im) = (0.0f32, 0.0f32);
+        let step = 2.0 * PI * k as f32 / n as f32;
+        for (t, &x) in frame.iter().enumerate() {
+            let phase = step * t as f32;
+            re += x * phase.cos();
+            im -= x * phase.sin();
+        }
+        let power = (re * re + im * im) as f64 + 1e-12;
+        log_sum += power.ln();
+        sum += power;
+    }
+    let geometric = (log_sum / bins as f64).exp();
+    let arithmetic = sum / bins as f64;
+    (geometric / arithmetic) as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(amplitude: f32, frames: usize) -> Vec<f32> {
+        (0..480 * frames)
+            .map(|i| amplitude * (2.0 * PI * 200.0 * i as f32 / SAMPLE_RATE).sin())
+            .collect()
+    }
+
+    /// Deterministic white-ish noise.
+    fn noise(amplitude: f32, frames: usize) -> Vec<f32> {
+        let mut state = 0x1234_5678u32;
+        (0..480 * frames)
+            .map(|_| {
+                state = state.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                amplitude * ((state >> 8) as f32 / (1u32 << 24) as f32 * 2.0 - 1.0)
+            })
+            .collect()
+    }
+
+    fn classify(vad: &mut AdaptiveEnergyVad, audio: &[f32]) -> Vec<bool> {
+        audio
+            .chunks_exact(480)
+            .map(|f| vad.is_speech(f).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn adaptive_follows_gain_changes() {
+        let mut vad = AdaptiveEnergyVad::new(AdaptiveEnergyConfig {
+            noise_window_secs: 1.0,
+            ..Default::default()
+        });
+
+        // Quiet noise floor, then speech 20 dB above it
+        assert!(!classify(&mut vad, &noise(0.001, 30)).contains(&true));
+        assert!(classify(&mut vad, &tone(0.05, 10)).iter().all(|&s| s));
+
+        // Gain x20: the old threshold would call the noise speech. After
+        // one noise window the floor has adapted.
+        let loud = classify(&mut vad, &noise(0.02, 60));
+        assert!(!loud[40..].contains(&true), "{loud:?}");
+        assert!(classify(&mut vad, &tone(1.0, 10)).iter().all(|&s| s));
+    }
+
+    #[test]
+    fn adaptive_floor_respects_minimum() {
+        let mut vad = AdaptiveEnergyVad::new(AdaptiveEnergyConfig::default());
+        classify(&mut vad, &vec![0.0; 480 * 5]);
+        assert_eq!(vad.noise_floor_db(), Some(-70.0));
+        // -46 dBFS is 24 dB above the clamped floor
+        assert!(classify(&mut vad, &tone(0.007, 1))[0]);
+        vad.reset();
+        assert_eq!(vad.noise_floor_db(), None);
+    }
+
+    #[test]
+    fn spectral_features_veto_noise() {
+        let config = AdaptiveEnergyConfig {
+            max_spectral_flatness: Some(0.5),
+            max_zero_crossing_rate: Some(0.35),
+            ..Default::default()
+        };
+        let mut vad = AdaptiveEnergyVad::new(config);
+        classify(&mut vad, &noise(0.001, 20));
+
+        // Loud broadband noise is rejected; a loud tone is not
+        assert!(!classify(&mut vad, &noise(0.3, 3)).contains(&true));
+        assert!(classify(&mut vad, &tone(0.3, 3)).iter().all(|&s| s));
+
+        assert!(spectral_flatness(&noise(1.0, 1)) > 0.4);
+        assert!(spectral_flatness(&tone(1.0, 1)) < 0.1);
+        let alternating: Vec<f32> = (0..480)
+            .map(|i| if i % 2 == 0 { 0.5 } else { -0.5 })
+            .collect();
+        assert_eq!(zero_crossing_rate(&alternating), 1.0);
+    }
+
+    #[test]
+    fn probability_is_half_at_margin() {
+        let mut vad = AdaptiveEnergyVad::new(AdaptiveEnergyConfig::default());
+        classify(&mut vad, &noise(0.001, 20));
+
+        // A tone's mean square is A²/2, so this frame sits exactly
+        // `snr_margin_db` above the floor.
+        let floor = vad.noise_floor_db().unwrap();
+        let amplitude = 2f32.sqrt() * 10f32.powf((floor + 9.0) / 20.0);
+        let p = vad.speech_probability(&tone(amplitude, 1)).unwrap();
+        assert!((p - 0.5).abs() < 1e-3, "{p}");
+
+        assert!(vad.speech_probability(&tone(0.5, 1)).unwrap() > 0.99);
+        assert!(vad.speech_probability(&noise(0.001, 1)).unwrap() < 0.1);
+        assert!(vad.speech_probability(&[0.0; 100]).is_err());
+    }
+}
@@ -0,0 +1,402 @@
+use crate::TranscribeError;
+
+use super::Vad;
+
+/// Check that every VAD is non-empty and shares one frame size.
+fn common_frame_size(vads: &[Box<dyn Vad>], what: &str) -> Result<usize, TranscribeError> {
+    let Some(first) = vads.first() else {
+        return Err(TranscribeError::Config(format!(
+            "{what} needs at least one VAD"
+        )));
+    };
+    let frame_size = first.frame_size();
+    if let Some(other) = vads.iter().find(|v| v.frame_size() != frame_size) {
+        return Err(TranscribeError::Config(format!(
+            "{what}: frame sizes differ ({frame_size} vs {})",
+            other.frame_size()
+        )));
+    }
+    Ok(frame_size)
+}
+
+/// Evaluate every VAD on the frame (so stateful VADs never miss a frame)
+/// and count the positive votes.
+fn count_votes(vads: &mut [Box<dyn Vad>], frame: &[f32]) -> Result<usize, TranscribeError> {
+    let mut votes = 0;
+    for vad in vads {
+        if vad.is_speech(frame)? {
+            votes += 1;
+        }
+    }
+    Ok(votes)
+}
+
+fn probabilities(vads: &mut [Box<dyn Vad>], frame: &[f32]) -> Result<Vec<f32>, TranscribeError> {
+    vads.iter_mut()
+        .map(|vad| vad.speech_probability(frame))
+        .collect()
+}
+
+/// Speech only when every inner VAD agrees.
+///
+/// All inner VADs see every frame, so stateful detectors (e.g. Silero)
+/// keep consistent state. [`speech_probability()`](Vad::speech_probability)
+/// is the minimum of the inner probabilities.
+///
+/// ```ignore
+/// let vad = AndVad::new(vec![
+///     Box::new(EnergyVad::new(480, 0.005)),
+///     Box::new(SileroVad::new("silero_vad_v4.onnx", 0.3)?),
+/// ])?;
+/// ```
+pub struct AndVad {
+    vads: Vec<Box<dyn Vad>>,
+    frame_size: usize,
+}
+
+impl AndVad {
+    /// Combine VADs that share a frame size.
+    pub fn new(vads: Vec<Box<dyn Vad>>) -> Result<Self, TranscribeError> {
+        let frame_size = common_frame_size(&vads, "AndVad")?;
+        Ok(Self { vads, frame_size })
+    }
+}
+
+impl Vad for AndVad {
+    fn frame_size(&self) -> usize {
+        self.frame_size
+    }
+
+    fn is_speech(&mut self, frame: &[f32]) -> Result<bool, TranscribeError> {
+        Ok(count_votes(&mut self.vads, frame)? == self.vads.len())
+    }
+
+    fn speech_probability(&mut self, frame: &[f32]) -> Result<f32, TranscribeError> {
+        Ok(probabilities(&mut self.vads, frame)?
+            .into_iter()
+            .fold(1.0, f32::min))
+    }
+
+    fn reset(&mut self) {
+        self.vads.iter_mut().for_each(|v| v.reset());
+    }
+}
+
+/// Speech when any inner VAD detects it.
+///
+/// All inner VADs see every frame. [`speech_probability()`](Vad::speech_probability)
+/// is the maximum of the inner probabilities.
+pub struct OrVad {
+    vads: Vec<Box<dyn Vad>>,
+    frame_size: usize,
+}
+
+impl OrVad {
+    /// Combine VADs that share a frame size.
+    pub fn new(vads: Vec<Box<dyn Vad>>) -> Result<Self, TranscribeError> {
+        let frame_size = common_frame_size(&vads, "OrVad")?;
+        Ok(Self { vads, frame_size })
+    }
+}
+
+impl Vad for OrVad {
+    fn frame_size(&self) -> usize {
+        self.frame_size
+    }
+
+    fn is_speech(&mut self, frame: &[f32]) -> Result<bool, TranscribeError> {
+        Ok(count_votes(&mut self.vads, frame)? > 0)
+    }
+
+    fn speech_probability(&mut self, frame: &[f32]) -> Result<f32, TranscribeError> {
+        Ok(probabilities(&mut self.vads, frame)?
+            .into_iter()
+            .fold(0.0, f32::max))
+    }
+
+    fn reset(&mut self) {
+        self.vads.iter_mut().for_each(|v| v.reset());
+    }
+}
+
+/// Runs an expensive VAD only while a cheap gate VAD fires.
+///
+/// Frames the gate rejects are non-speech without consulting the inner
+/// VAD. After the gate stops firing, the inner VAD keeps running for
+/// `hangover_frames` frames so quiet speech endings are still judged by
+/// it; once the gate has been closed longer than that, the inner VAD is
+/// reset, since its recurrent state no longer reflects recent audio.
+///
+/// ```ignore
+/// // Skip Silero on silent audio
+/// let vad = GatedVad::new(
+///     Box::new(EnergyVad::new(480, 0.002)),
+///     Box::new(SileroVad::new("silero_vad_v4.onnx", 0.3)?),
+///     10,
+/// )?;
+/// ```
+pub struct GatedVad {
+    gate: Box<dyn Vad>,
+    inner: Box<dyn Vad>,
+    hangover_frames: usize,
+    // internal state
+    /// Frames since the gate last fired; `None` while the inner VAD is idle.
+    frames_since_gate: Option<usize>,
+}
+
+impl GatedVad {
+    /// Gate `inner` behind `gate`. Both must share a frame size.
+    pub fn new(
+        gate: Box<dyn Vad>,
+        inner: Box<dyn Vad>,
+        hangover_frames: usize,
+    ) -> Result<Self, TranscribeError> {
+        if gate.frame_size() != inner.frame_size() {
+            return Err(TranscribeError::Config(format!(
+                "GatedVad: frame sizes differ ({} vs {})",
+                gate.frame_size(),
+                inner.frame_size()
+            )));
+        }
+        Ok(Self {
+            gate,
+            inner,
+            hangover_frames,
+            frames_since_gate: None,
+        })
+    }
+
+    /// Whether the inner VAD ran on the last frame.
+    pub fn inner_active(&self) -> bool {
+        self.frames_since_gate.is_some()
+    }
+
+    /// Feed the gate; returns whether the inner VAD should see this frame.
+    fn open(&mut self, frame: &[f32]) -> Result<bool, TranscribeError> {
+        if self.gate.is_speech(frame)? {
+            self.frames_since_gate = Some(0);
+            return Ok(true);
+        }
+        match self.frames_since_gate {
+            Some(n) if n < self.hangover_frames => {
+                self.frames_since_gate = Some(n + 1);
+                Ok(true)
+            }
+            Some(_) => {
+                self.frames_since_gate = None;
+                self.inner.reset();
+                Ok(false)
+            }
+            None => Ok(false),
+        }
+    }
+}
+
+impl Vad for GatedVad {
+    fn frame_size(&self) -> usize {
+        self.inner.frame_size()
+    }
+
+    fn is_speech(&mut self, frame: &[f32]) -> Result<bool, TranscribeError> {
+        if !self.open(frame)? {
+            return Ok(false);
+        }
+        self.inner.is_speech(frame)
+    }
+
+    fn speech_probability(&mut self, frame: &[f32]) -> Result<f32, TranscribeError> {
+        if !self.open(frame)? {
+            return Ok(0.0);
+        }
+        self.inner.speech_probability(frame)
+    }
+
+    fn reset(&mut self) {
+        self.frames_since_gate = None;
+        self.gate.reset();
+        self.inner.reset();
+    }
+}
+
+/// One VAD per channel of multichannel audio.
+///
+/// As a [`Vad`], it takes interleaved frames of `frame_size × channels`
+/// samples and reports speech when any channel is active;
+/// [`active_channels()`](MultichannelVad::active_channels) tells which.
+/// [`process()`](MultichannelVad::process) accepts planar audio instead.
+pub struct MultichannelVad {
+    vads: Vec<Box<dyn Vad>>,
+    frame_size: usize,
+    // internal state
+    active: Vec<bool>,
+    scratch: Vec<f32>,
+}
+
+impl MultichannelVad {
+    /// Create from one VAD per channel, all sharing a frame size.
+    pub fn new(vads: Vec<Box<dyn Vad>>) -> Result<Self, TranscribeError> {
+        let frame_size = common_frame_size(&vads, "MultichannelVad")?;
+        let channels = vads.len();
+        Ok(Self {
+            vads,
+            frame_size,
+            active: vec![false; channels],
+            scratch: Vec::with_capacity(frame_size),
+        })
+    }
+
+    /// Number of channels.
+    pub fn channels(&self) -> usize {
+        self.vads.len()
+    }
+
+    /// Per-channel speech decisions for the last frame.
+    pub fn active_channels(&self) -> &[bool] {
+        &self.active
+    }
+
+    /// Classify one planar frame per channel. Returns per-channel decisions.
+    pub fn process(&mut self, frames: &[&[f32]]) -> Result<&[bool], TranscribeError> {
+        if frames.len() != self.vads.len() {
+            return Err(TranscribeError::Audio(format!(
+                "expected {} channels, got {}",
+                self.vads.len(),
+                frames.len()
+            )));
+        }
+        for ((vad, frame), active) in self.vads.iter_mut().zip(frames).zip(&mut self.active) {
+            *active = vad.is_speech(frame)?;
+        }
+        Ok(&self.active)
+    }
+}
+
+impl Vad for MultichannelVad {
+    fn frame_size(&self) -> usize {
+        self.frame_size * self.vads.len()
+    }
+
+    fn is_speech(&mut self, frame: &[f32]) -> Result<bool, TranscribeError> {
+        let channels = self.vads.len();
+        if frame.len() != self.frame_size * channels {
+            return Err(TranscribeError::Audio(format!(
+                "expected {} interleaved samples, got {}",
+                self.frame_size * channels,
+                frame.len()
+            )));
+        }
+        for ch in 0..channels {
+            self.scratch.clear();
+            self.scratch
+                .extend(frame.iter().skip(ch).step_by(channels).copied());
+            self.active[ch] = self.vads[ch].is_speech(&self.scratch)?;
+        }
+        Ok(self.active.contains(&true))
+    }
+
+    fn reset(&mut self) {
+        self.vads.iter_mut().for_each(|v| v.reset());
+        self.active.fill(false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::vad::EnergyVad;
+
+    /// Energy VAD that counts how often it runs.
+    struct CountingVad {
+        inner: EnergyVad,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl Vad for CountingVad {
+        fn frame_size(&self) -> usize {
+            self.inner.frame_size()
+        }
+
+        fn is_speech(&mut self, frame: &[f32]) -> Result<bool, TranscribeError> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            self.inner.is_speech(frame)
+        }
+
+        fn reset(&mut self) {}
+    }
+
+    fn energy(threshold: f32) -> Box<dyn Vad> {
+        Box::new(EnergyVad::new(480, threshold))
+    }
+
+    #[test]
+    fn and_or_voting() {
+        let mut and = AndVad::new(vec![energy(0.1), energy(0.5)]).unwrap();
+        let mut or = OrVad::new(vec![energy(0.1), energy(0.5)]).unwrap();
+        let medium = vec![0.3f32; 480];
+        let loud = vec![0.9f32; 480];
+
+        assert!(!and.is_speech(&medium).unwrap());
+        assert!(or.is_speech(&medium).unwrap());
+        assert!(and.is_speech(&loud).unwrap());
+        assert_eq!(and.speech_probability(&medium).unwrap(), 0.0);
+        assert_eq!(or.speech_probability(&medium).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn combinators_validate_frame_sizes() {
+        assert!(AndVad::new(vec![]).is_err());
+        assert!(OrVad::new(vec![energy(0.1), Box::new(EnergyVad::new(512, 0.1))]).is_err());
+        assert!(GatedVad::new(energy(0.1), Box::new(EnergyVad::new(512, 0.1)), 0).is_err());
+    }
+
+    #[test]
+    fn gated_skips_inner_on_silence() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let inner = CountingVad {
+            inner: EnergyVad::new(480, 0.5),
+            calls: calls.clone(),
+        };
+        let mut vad = GatedVad::new(energy(0.05), Box::new(inner), 2).unwrap();
+        let silence = vec![0.0f32; 480];
+        let quiet = vec![0.1f32; 480];
+        let loud = vec![0.9f32; 480];
+
+        for _ in 0..10 {
+            assert!(!vad.is_speech(&silence).unwrap());
+        }
+        assert_eq!(calls.load(Ordering::Relaxed), 0);
+
+        // Gate open but inner says no
+        assert!(!vad.is_speech(&quiet).unwrap());
+        assert!(vad.is_speech(&loud).unwrap());
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+
+        // Two hangover frames still reach the inner VAD, then it idles
+        for _ in 0..5 {
+            vad.is_speech(&silence).unwrap();
+        }
+        assert_eq!(calls.load(Ordering::Relaxed), 4);
+        assert!(!vad.inner_active());
+    }
+
+    #[test]
+    fn multichannel_reports_active_channel() {
+        let mut vad = MultichannelVad::new(vec![energy(0.1), energy(0.1)]).unwrap();
+        assert_eq!(vad.frame_size(), 960);
+
+        // Interleaved: channel 0 silent, channel 1 loud
+        let frame: Vec<f32> = (0..960)
+            .map(|i| if i % 2 == 1 { 0.5 } else { 0.0 })
+            .collect();
+        assert!(vad.is_speech(&frame).unwrap());
+        assert_eq!(vad.active_channels(), &[false, true]);
+
+        let silent = vec![0.0f32; 480];
+        let loud = vec![0.5f32; 480];
+        assert_eq!(vad.process(&[&loud, &silent]).unwrap(), &[true, false]);
+        assert!(vad.process(&[&loud]).is_err());
+    }
+}
@@ -4,8 +4,16 @@
 //! along with built-in implementations:
 //!
 //! - [`EnergyVad`] — simple RMS energy threshold (pure Rust, no dependencies)
+//! - [`AdaptiveEnergyVad`] — energy relative to a tracked noise floor (pure Rust)
 //! - [`SmoothedVad`] — wraps any `Vad` with onset detection and hangover
-//! - [`SileroVad`] — Silero ONNX model (requires `vad-silero` feature)
+//! - [`SileroVad`] — Silero v4/v5 ONNX model (requires `vad-silero` feature)
+//!
+//! Combinators build on these: [`AndVad`] / [`OrVad`] for ensembles,
+//! [`GatedVad`] to run an expensive VAD only behind a cheap gate, and
+//! [`MultichannelVad`] for per-channel detection.
+//!
+//! [`segment()`] turns any `Vad` into a list of [`SpeechRegion`]s for offline
+//! use (trimming, analytics) without transcribing anything.
 //!
 //! # Recommended Parameters
 //!
@@ -17,10 +25,16 @@
 //! | Onset frames | 2 | 60ms consecutive speech to trigger |
 //! | Frame size | 480 | 30ms at 16kHz |
 
+mod adaptive;
+mod combinators;
+mod segment;
 #[cfg(feature = "vad-silero")]
 mod silero;
+pub use adaptive::{AdaptiveEnergyConfig, AdaptiveEnergyVad};
+pub use combinators::{AndVad, GatedVad, MultichannelVad, OrVad};
+pub use segment::{segment, SegmentConfig, SpeechRegion};
 #[cfg(feature = "vad-silero")]
-pub use silero::SileroVad;
+pub use silero::{SileroVad, SileroVersion};
 
 use std::collections::VecDeque;
 
@@ -34,7 +48,7 @@ use crate::TranscribeError;
 pub trait Vad: Send {
     /// Number of samples required per frame.
     ///
-    /// Silero: 480 (30ms at 16kHz). EnergyVad: configurable.
+    /// Silero v4: 480 (30ms at 16kHz). Silero v5: 512. EnergyVad: configurable.
     fn frame_size(&self) -> usize;
 
     /// Returns `true` if the frame contains speech.
@@ -42,6 +56,19 @@ pub trait Vad: Send {
     /// The frame must be exactly [`frame_size()`](Vad::frame_size) samples.
     fn is_speech(&mut self, frame: &[f32]) -> Result<bool, TranscribeError>;
 
+    /// Probability (0.0–1.0) that the frame contains speech.
+    ///
+    /// Advances internal state exactly like [`is_speech()`](Vad::is_speech),
+    /// so call one or the other for each frame, not both. Useful for
+    /// threshold tuning, visualization, or custom smoothing.
+    ///
+    /// The default implementation maps [`is_speech()`](Vad::is_speech) to
+    /// `1.0` or `0.0`. Models with a native probability (e.g. Silero)
+    /// override it.
+    fn speech_probability(&mut self, frame: &[f32]) -> Result<f32, TranscribeError> {
+        Ok(if self.is_speech(frame)? { 1.0 } else { 0.0 })
+    }
+
     /// Drain any buffered prefill audio and return it as a flat sample vector.
     ///
     /// Call this immediately after [`is_speech()`](Vad::is_speech) returns `true`
@@ -62,7 +89,8 @@ pub trait Vad: Send {
 ///
 /// Classifies a frame as speech if its RMS energy exceeds the configured
 /// threshold. Suitable for clean audio with minimal background noise.
-/// Use [`SileroVad`] for noisy environments.
+/// Use [`AdaptiveEnergyVad`] when gain or background level varies, or
+/// [`SileroVad`] for noisy environments.
 pub struct EnergyVad {
     frame_size: usize,
     threshold_rms: f32,
@@ -110,6 +138,11 @@ impl Vad for EnergyVad {
 /// - **Prefill buffer**: maintains a ring buffer of recent frames (up to
 ///   `prefill_frames + 1`) for use by higher-level chunking logic.
 ///
+/// - **Hysteresis** (optional): with [`with_hysteresis()`](SmoothedVad::with_hysteresis),
+///   frames are classified from the inner VAD's
+///   [`speech_probability()`](Vad::speech_probability) against a higher
+///   threshold to enter speech and a lower one to stay in it.
+///
 /// The `is_speech()` method returns the smoothed speech/non-speech decision.
 /// Use [`in_speech()`](SmoothedVad::in_speech) to query the current state
 /// without feeding a new frame.
@@ -118,6 +151,8 @@ pub struct SmoothedVad {
     onset_frames: usize,
     hangover_frames: usize,
     prefill_frames: usize,
+    /// `(onset_threshold, offset_threshold)` applied to the inner probability.
+    hysteresis: Option<(f32, f32)>,
     // internal state
     frame_buffer: VecDeque<Vec<f32>>,
     hangover_counter: usize,
@@ -146,6 +181,7 @@ impl SmoothedVad {
             onset_frames,
             hangover_frames,
             prefill_frames,
+            hysteresis: None,
             frame_buffer: VecDeque::new(),
             hangover_counter: 0,
             onset_counter: 0,
@@ -154,6 +190,20 @@ impl SmoothedVad {
         }
     }
 
+    /// Classify frames by the inner VAD's speech probability with hysteresis.
+    ///
+    /// Outside speech, a frame counts as voiced when its probability is at
+    /// least `onset_threshold`; inside speech, when it is at least
+    /// `offset_threshold`. Use a lower offset than onset threshold (e.g. 0.5
+    /// and 0.35) to avoid flapping around a single threshold.
+    ///
+    /// Without hysteresis, the inner VAD's own [`is_speech()`](Vad::is_speech)
+    /// decision is used.
+    pub fn with_hysteresis(mut self, onset_threshold: f32, offset_threshold: f32) -> Self {
+        self.hysteresis = Some((onset_threshold, offset_threshold));
+        self
+    }
+
     /// Whether currently in a speech region (after onset, before hangover expires).
     pub fn in_speech(&self) -> bool {
         self.in_speech
@@ -186,7 +236,13 @@ impl Vad for SmoothedVad {
             }
         }
 
-        let voice = self.inner.is_speech(frame)?;
+        let voice = match self.hysteresis {
+            Some((onset, offset)) => {
+                let threshold = if self.in_speech { offset } else { onset };
+                self.inner.speech_probability(frame)? >= threshold
+            }
+            None => self.inner.is_speech(frame)?,
+        };
 
         match (self.in_speech, voice) {
             (false, true) => {
@@ -464,4 +520,47 @@ mod tests {
         }
         assert!(vad.frame_buffer().is_empty());
     }
+
+    #[test]
+    fn default_speech_probability_follows_is_speech() {
+        let mut vad = EnergyVad::new(480, 0.01);
+        assert_eq!(vad.speech_probability(&[1.0f32; 480]).unwrap(), 1.0);
+        assert_eq!(vad.speech_probability(&[0.0f32; 480]).unwrap(), 0.0);
+    }
+
+    /// Inner VAD that reports the frame's first sample as its probability.
+    struct ProbabilityVad;
+
+    impl Vad for ProbabilityVad {
+        fn frame_size(&self) -> usize {
+            480
+        }
+
+        fn is_speech(&mut self, frame: &[f32]) -> Result<bool, TranscribeError> {
+            Ok(self.speech_probability(frame)? > 0.5)
+        }
+
+        fn speech_probability(&mut self, frame: &[f32]) -> Result<f32, TranscribeError> {
+            Ok(frame[0])
+        }
+
+        fn reset(&mut self) {}
+    }
+
+    #[test]
+    fn smoothed_hysteresis_uses_separate_thresholds() {
+        let mut vad = SmoothedVad::new(Box::new(ProbabilityVad), 0, 0, 1).with_hysteresis(0.6, 0.3);
+        let frame = |p: f32| vec![p; 480];
+
+        // Below onset threshold: no speech
+        assert!(!vad.is_speech(&frame(0.5)).unwrap());
+        // Onset
+        assert!(vad.is_speech(&frame(0.7)).unwrap());
+        // Between offset and onset thresholds: stays in speech
+        assert!(vad.is_speech(&frame(0.4)).unwrap());
+        // Below offset threshold: leaves speech
+        assert!(!vad.is_speech(&frame(0.2)).unwrap());
+        // Re-entry needs the onset threshold again
+        assert!(!vad.is_speech(&frame(0.4)).unwrap());
+    }
 }
@@ -0,0 +1,272 @@
+use std::ops::Range;
+
+use crate::transcriber::SAMPLE_RATE;
+use crate::TranscribeError;
+
+use super::Vad;
+
+/// Configuration for [`segment`], modelled on Silero's
+/// `get_speech_timestamps`.
+#[derive(Debug, Clone)]
+pub struct SegmentConfig {
+    /// Speech regions shorter than this are discarded.
+    pub min_speech_secs: f32,
+    /// A region ends only after this much continuous silence; shorter
+    /// pauses stay inside the region.
+    pub min_silence_secs: f32,
+    /// Padding added to both ends of every region. Where padded regions
+    /// would overlap, the gap between them is split in half.
+    pub speech_pad_secs: f32,
+    /// Regions longer than this are split: at the longest pause inside the
+    /// region if there is one, otherwise with a hard cut.
+    pub max_speech_secs: f32,
+}
+
+impl Default for SegmentConfig {
+    fn default() -> Self {
+        Self {
+            min_speech_secs: 0.25,
+            min_silence_secs: 0.1,
+            speech_pad_secs: 0.03,
+            max_speech_secs: f32::INFINITY,
+        }
+    }
+}
+
+/// A region of speech, in seconds from the start of the audio.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpeechRegion {
+    pub start: f32,
+    pub end: f32,
+}
+
+impl SpeechRegion {
+    /// Region duration in seconds.
+    pub fn duration(&self) -> f32 {
+        self.end - self.start
+    }
+
+    /// The region as a range of sample indices (16 kHz).
+    pub fn sample_range(&self) -> Range<usize> {
+        (self.start * SAMPLE_RATE).round() as usize..(self.end * SAMPLE_RATE).round() as usize
+    }
+}
+
+/// Find the speech regions in 16 kHz mono audio.
+///
+/// Runs `vad` over every full frame of `samples` (a trailing partial frame
+/// is ignored) and turns the per-frame decisions into regions according to
+/// `config`. The VAD is reset before and after.
+pub fn segment(
+    samples: &[f32],
+    vad: &mut dyn Vad,
+    config: &SegmentConfig,
+) -> Result<Vec<SpeechRegion>, TranscribeError> {
+    let frame_size = vad.frame_size();
+    let to_samples = |secs: f32| {
+        if secs.is_finite() {
+            (secs.max(0.0) * SAMPLE_RATE) as usize
+        } else {
+            usize::MAX
+        }
+    };
+    let min_speech = to_samples(config.min_speech_secs);
+    let min_silence = to_samples(config.min_silence_secs);
+    let max_speech = to_samples(config.max_speech_secs).max(frame_size);
+    let pad = to_samples(config.speech_pad_secs);
+
+    // Raw regions as sample ranges.
+    let mut regions: Vec<(usize, usize)> = Vec::new();
+    let mut start: Option<usize> = None;
+    // Start of the silence run currently in progress inside a region.
+    let mut silence_start: Option<usize> = None;
+    // Longest completed pause inside the current region: (start, end).
+    let mut best_pause: Option<(usize, usize)> = None;
+
+    vad.reset();
+    for (i, frame) in samples.chunks_exact(frame_size).enumerate() {
+        let offset = i * frame_size;
+        let frame_end = offset + frame_size;
+        let is_speech = vad.is_speech(frame)?;
+
+        let Some(region_start) = start else {
+            if is_speech {
+                start = Some(offset);
+            }
+            continue;
+        };
+
+        if is_speech {
+            if let Some(s) = silence_start.take() {
+                if best_pause.is_none_or(|(bs, be)| offset - s > be - bs) {
+                    best_pause = Some((s, offset));
+                }
+            }
+        } else {
+            let s = *silence_start.get_or_insert(offset);
+            if frame_end - s >= min_silence {
+                regions.push((region_start, s));
+                start = None;
+                silence_start = None;
+                best_pause = None;
+                continue;
+            }
+        }
+
+        if frame_end - region_start > max_speech {
+            match best_pause.take() {
+                Some((pause_start, pause_end)) => {
+                    regions.push((region_start, pause_start));
+                    start = Some(pause_end);
+                }
+                None => {
+                    regions.push((region_start, offset));
+                    start = Some(offset);
+                    silence_start = silence_start.map(|s| s.max(offset));
+                }
+            }
+        }
+    }
+    if let Some(region_start) = start {
+        let end = silence_start.unwrap_or(samples.len() / frame_size * frame_size);
+        regions.push((region_start, end));
+    }
+    vad.reset();
+
+    regions.retain(|&(s, e)| e > s && e - s >= min_speech);
+
+    // Pad, splitting gaps that are too small to pad both sides.
+    let total = samples.len();
+    let mut padded = regions.clone();
+    for i in 0..padded.len() {
+        let prev_end = i.checked_sub(1).map(|p| regions[p].1);
+        let next_start = regions.get(i + 1).map(|r| r.0);
+        let (s, e) = regions[i];
+
+        padded[i].0 = match prev_end {
+            Some(pe) if s - pe < 2 * pad => s - (s - pe) / 2,
+            _ => s.saturating_sub(pad),
+        };
+        padded[i].1 = match next_start {
+            Some(ns) if ns - e < 2 * pad => e + (ns - e) / 2,
+            _ => e.saturating_add(pad).min(total),
+        };
+    }
+
+    Ok(padded
+        .into_iter()
+        .map(|(s, e)| SpeechRegion {
+            start: s as f32 / SAMPLE_RATE,
+            end: e as f32 / SAMPLE_RATE,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vad::EnergyVad;
+
+    /// Build audio from (is_speech, frames) runs of 480-sample frames.
+    fn audio(runs: &[(bool, usize)]) -> Vec<f32> {
+        runs.iter()
+            .flat_map(|&(speech, frames)| {
+                std::iter::repeat_n(if speech { 0.5 } else { 0.0 }, 480 * frames)
+            })
+            .collect()
+    }
+
+    fn config() -> SegmentConfig {
+        SegmentConfig {
+            min_speech_secs: 0.25,
+            min_silence_secs: 0.1,
+            speech_pad_secs: 0.0,
+            max_speech_secs: f32::INFINITY,
+        }
+    }
+
+    fn run(samples: &[f32], config: &SegmentConfig) -> Vec<SpeechRegion> {
+        segment(samples, &mut EnergyVad::new(480, 0.1), config).unwrap()
+    }
+
+    fn approx(regions: &[SpeechRegion], expected: &[(f32, f32)]) {
+        assert_eq!(regions.len(), expected.len(), "{regions:?}");
+        for (r, &(s, e)) in regions.iter().zip(expected) {
+            assert!(
+                (r.start - s).abs() < 1e-3 && (r.end - e).abs() < 1e-3,
+                "{regions:?} vs {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn finds_regions_and_bridges_short_pauses() {
+        // 0.3s silence, 0.6s speech, 0.06s pause (bridged), 0.3s speech,
+        // 0.3s silence, 0.3s speech to the end
+        let samples = audio(&[
+            (false, 10),
+            (true, 20),
+            (false, 2),
+            (true, 10),
+            (false, 10),
+            (true, 10),
+        ]);
+        approx(&run(&samples, &config()), &[(0.3, 1.26), (1.56, 1.86)]);
+    }
+
+    #[test]
+    fn drops_short_speech() {
+        let samples = audio(&[(true, 5), (false, 10), (true, 10)]);
+        approx(&run(&samples, &config()), &[(0.45, 0.75)]);
+    }
+
+    #[test]
+    fn pads_and_splits_small_gaps() {
+        let samples = audio(&[(false, 10), (true, 10), (false, 4), (true, 10), (false, 10)]);
+        let regions = run(
+            &samples,
+            &SegmentConfig {
+                speech_pad_secs: 0.1,
+                ..config()
+            },
+        );
+        // Gap of 0.12s between regions is shared: 0.06s each side
+        approx(&regions, &[(0.2, 0.66), (0.66, 1.12)]);
+    }
+
+    #[test]
+    fn splits_long_regions_at_longest_pause() {
+        let samples = audio(&[(true, 10), (false, 1), (true, 10), (false, 3), (true, 20)]);
+        let regions = run(
+            &samples,
+            &SegmentConfig {
+                max_speech_secs: 1.0,
+                ..config()
+            },
+        );
+        approx(&regions, &[(0.0, 0.63), (0.72, 1.32)]);
+    }
+
+    #[test]
+    fn hard_cuts_without_pauses() {
+        let samples = audio(&[(true, 50)]);
+        let regions = run(
+            &samples,
+            &SegmentConfig {
+                max_speech_secs: 0.6,
+                ..config()
+            },
+        );
+        approx(&regions, &[(0.0, 0.6), (0.6, 1.2), (1.2, 1.5)]);
+    }
+
+    #[test]
+    fn sample_range_round_trips() {
+        let r = SpeechRegion {
+            start: 0.5,
+            end: 1.25,
+        };
+        assert_eq!(r.sample_range(), 8000..20000);
+        assert!((r.duration() - 0.75).abs() < 1e-6);
+    }
+}
@@ -1,6 +1,8 @@
 //! Silero VAD — neural voice activity detection using the Silero ONNX model.
 //!
-//! Requires the `vad-silero` feature flag and a `silero_vad_v4.onnx` model file.
+//! Requires the `vad-silero` feature flag and a Silero VAD model file
+//! (`silero_vad_v4.onnx` or the v5 `silero_vad.onnx`). The model version is
+//! detected from the model's inputs.
 //!
 //! The model file is NOT bundled — the consumer provides the path:
 //!
@@ -9,25 +11,82 @@
 //! let vad = SileroVad::new("/path/to/silero_vad_v4.onnx", 0.3)?;
 //! ```
 
+use std::io::Read;
 use std::path::Path;
 
 use ndarray::{Array1, Array3, ArrayView2};
 use ort::inputs;
-use ort::session::builder::GraphOptimizationLevel;
+use ort::session::builder::{GraphOptimizationLevel, SessionBuilder};
 use ort::session::Session;
 use ort::value::TensorRef;
 
 use super::Vad;
 use crate::TranscribeError;
 
-/// Number of samples per frame: 30ms at 16kHz.
-const FRAME_SAMPLES: usize = 480;
+/// Number of samples per v4 frame: 30ms at 16kHz.
+const V4_FRAME_SAMPLES: usize = 480;
 
-/// Silero VAD using an ONNX model with LSTM state.
+/// Number of samples per v5 window: 32ms at 16kHz.
+const V5_FRAME_SAMPLES: usize = 512;
+
+/// Samples of the previous v5 window prepended to each input.
+const V5_CONTEXT_SAMPLES: usize = 64;
+
+/// Silero VAD model generation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SileroVersion {
+    /// Separate `h`/`c` LSTM states of shape (2, 1, 64); 480-sample frames.
+    V4,
+    /// Combined `state` of shape (2, 1, 128); 512-sample windows with
+    /// 64 samples of context from the previous window.
+    V5,
+}
+
+/// Recurrent state carried across frames.
+enum ModelState {
+    V4 {
+        h: Array3<f32>, // LSTM hidden state (2, 1, 64)
+        c: Array3<f32>, // LSTM cell state (2, 1, 64)
+    },
+    V5 {
+        state: Array3<f32>, // combined state (2, 1, 128)
+        context: Vec<f32>,  // tail of the previous window
+    },
+}
+
+impl ModelState {
+    fn new(version: SileroVersion) -> Self {
+        match version {
+            SileroVersion::V4 => ModelState::V4 {
+                h: Array3::zeros((2, 1, 64)),
+                c: Array3::zeros((2, 1, 64)),
+            },
+            SileroVersion::V5 => ModelState::V5 {
+                state: Array3::zeros((2, 1, 128)),
+                context: vec![0.0; V5_CONTEXT_SAMPLES],
+            },
+        }
+    }
+}
+
+/// Single-threaded session settings; the model is tiny and runs per frame.
+fn session_builder() -> Result<SessionBuilder, TranscribeError> {
+    Session::builder()
+        .map_err(|e| TranscribeError::Config(format!("ort session builder: {e}")))?
+        .with_optimization_level(GraphOptimizationLevel::Level3)
+        .map_err(|e| TranscribeError::Config(format!("ort optimization level: {e}")))?
+        .with_intra_threads(1)
+        .map_err(|e| TranscribeError::Config(format!("ort intra threads: {e}")))?
+        .with_inter_threads(1)
+        .map_err(|e| TranscribeError::Config(format!("ort inter threads: {e}")))
+}
+
+/// Silero VAD using an ONNX model with recurrent state.
 ///
-/// Classifies 30ms audio frames (480 samples at 16kHz) as speech or
-/// non-speech using the Silero VAD v4 model. Maintains internal LSTM
-/// hidden and cell states across frames for temporal context.
+/// Classifies audio frames as speech or non-speech using the Silero VAD v4
+/// model (30ms / 480-sample frames) or v5 model (32ms / 512-sample
+/// windows). Check [`frame_size()`](Vad::frame_size) for the loaded model.
+/// Maintains internal recurrent state across frames for temporal context.
 ///
 /// # Example
 ///
@@ -35,13 +94,13 @@ const FRAME_SAMPLES: usize = 480;
 /// use transcribe_rs::vad::{SileroVad, Vad};
 ///
 /// let mut vad = SileroVad::new("silero_vad_v4.onnx", 0.3)?;
-/// let frame = vec![0.0f32; 480]; // 30ms of silence
+/// let frame = vec![0.0f32; vad.frame_size()]; // one frame of silence
 /// let is_speech = vad.is_speech(&frame)?;
 /// ```
 pub struct SileroVad {
     session: Session,
-    h: Array3<f32>,  // LSTM hidden state (2, 1, 64)
-    c: Array3<f32>,  // LSTM cell state (2, 1, 64)
+    version: SileroVersion,
+    state: ModelState,
     sr: Array1<i64>, // sample rate tensor
     threshold: f32,
 }
@@ -49,36 +108,90 @@ pub struct SileroVad {
 impl SileroVad {
     /// Create a new `SileroVad` from a Silero ONNX model file.
     ///
-    /// - `model_path`: path to `silero_vad_v4.onnx`
+    /// - `model_path`: path to a Silero v4 or v5 `.onnx` model
     /// - `threshold`: speech probability threshold (recommended: 0.3)
     pub fn new(model_path: impl AsRef<Path>, threshold: f32) -> Result<Self, TranscribeError> {
         let path = model_path.as_ref();
-        let session = Session::builder()
-            .map_err(|e| TranscribeError::Config(format!("ort session builder: {e}")))?
-            .with_optimization_level(GraphOptimizationLevel::Level3)
-            .map_err(|e| TranscribeError::Config(format!("ort optimization level: {e}")))?
-            .with_intra_threads(1)
-            .map_err(|e| TranscribeError::Config(format!("ort intra threads: {e}")))?
-            .with_inter_threads(1)
-            .map_err(|e| TranscribeError::Config(format!("ort inter threads: {e}")))?
-            .commit_from_file(path)
+        let session = session_builder()?.commit_from_file(path).map_err(|e| {
+            if !path.exists() {
+                TranscribeError::ModelNotFound(path.to_path_buf())
+            } else {
+                TranscribeError::Inference(format!("failed to load VAD model: {e}"))
+            }
+        })?;
+        Self::from_session(session, threshold, &path.display().to_string())
+    }
+
+    /// Create a `SileroVad` from the bytes of a Silero ONNX model, e.g.
+    /// `include_bytes!("silero_vad_v4.onnx")`.
+    pub fn from_bytes(model: &[u8], threshold: f32) -> Result<Self, TranscribeError> {
+        let session = session_builder()?
+            .commit_from_memory(model)
+            .map_err(|e| TranscribeError::Inference(format!("failed to load VAD model: {e}")))?;
+        Self::from_session(session, threshold, "memory")
+    }
+
+    /// Like [`new`](Self::new), with explicit ONNX session settings, e.g. to
+    /// keep the VAD on one CPU thread next to a GPU speech model.
+    #[cfg(feature = "onnx")]
+    pub fn new_with_config(
+        model_path: impl AsRef<Path>,
+        threshold: f32,
+        config: &crate::onnx::SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        let path = model_path.as_ref();
+        if !path.exists() {
+            return Err(TranscribeError::ModelNotFound(path.to_path_buf()));
+        }
+        let session = crate::onnx::session::create_session_with_config(path, config)
+            .map_err(|e| TranscribeError::Inference(format!("failed to load VAD model: {e}")))?;
+        Self::from_session(session, threshold, &path.display().to_string())
+    }
+
+    /// Like [`from_bytes`](Self::from_bytes), with explicit ONNX session
+    /// settings.
+    #[cfg(feature = "onnx")]
+    pub fn from_bytes_with_config(
+        model: &[u8],
+        threshold: f32,
+        config: &crate::onnx::SessionConfig,
+    ) -> Result<Self, TranscribeError> {
+        let session = crate::onnx::session::create_session_from_memory_with_config(model, config)
             .map_err(|e| {
-                if !path.exists() {
-                    TranscribeError::ModelNotFound(path.to_path_buf())
-                } else {
-                    TranscribeError::Inference(format!("failed to load VAD model: {e}"))
-                }
-            })?;
+            TranscribeError::Inference(format!("failed to load VAD model: {e}"))
+        })?;
+        Self::from_session(session, threshold, "memory")
+    }
+
+    /// Create a `SileroVad` by reading a Silero ONNX model from `reader`.
+    pub fn from_reader(mut reader: impl Read, threshold: f32) -> Result<Self, TranscribeError> {
+        let mut model = Vec::new();
+        reader.read_to_end(&mut model)?;
+        Self::from_bytes(&model, threshold)
+    }
+
+    fn from_session(
+        session: Session,
+        threshold: f32,
+        origin: &str,
+    ) -> Result<Self, TranscribeError> {
+        let version = detect_version(&session)?;
+        log::debug!("Silero VAD {:?} loaded from {}", version, origin);
 
         Ok(Self {
             session,
-            h: Array3::zeros((2, 1, 64)),
-            c: Array3::zeros((2, 1, 64)),
+            version,
+            state: ModelState::new(version),
             sr: Array1::from_vec(vec![16000i64]),
             threshold,
         })
     }
 
+    /// The detected model generation.
+    pub fn version(&self) -> SileroVersion {
+        self.version
+    }
+
     /// Current speech probability threshold.
     pub fn threshold(&self) -> f32 {
         self.threshold
@@ -89,83 +202,177 @@ impl SileroVad {
         self.threshold = threshold;
     }
 
-    /// Run inference and return the raw speech probability (0.0–1.0).
-    ///
-    /// Unlike [`is_speech()`](Vad::is_speech), this returns the model's
-    /// confidence rather than a thresholded boolean. Useful for custom
-    /// smoothing, visualization, or sensitivity tuning.
+    /// Speech probability (0.0–1.0) of one frame. Same as
+    /// [`Vad::speech_probability`], callable without importing the trait.
     pub fn speech_probability(&mut self, frame: &[f32]) -> Result<f32, TranscribeError> {
-        if frame.len() != FRAME_SAMPLES {
-            return Err(TranscribeError::Audio(format!(
-                "expected {FRAME_SAMPLES} samples, got {}",
-                frame.len()
-            )));
-        }
+        <Self as Vad>::speech_probability(self, frame)
+    }
+}
 
-        let input = ArrayView2::from_shape((1, FRAME_SAMPLES), frame)
-            .map_err(|e| TranscribeError::Inference(e.to_string()))?;
+/// The v5 model input: the tail of the previous window in front of the
+/// current frame.
+fn v5_window(context: &[f32], frame: &[f32]) -> Vec<f32> {
+    let mut window = Vec::with_capacity(V5_CONTEXT_SAMPLES + V5_FRAME_SAMPLES);
+    window.extend_from_slice(context);
+    window.extend_from_slice(frame);
+    window
+}
 
-        let t_input = TensorRef::from_array_view(input.into_dyn())
-            .map_err(|e| TranscribeError::Inference(format!("tensor input: {e}")))?;
-        let t_sr = TensorRef::from_array_view(self.sr.view().into_dyn())
-            .map_err(|e| TranscribeError::Inference(format!("tensor sr: {e}")))?;
-        let t_h = TensorRef::from_array_view(self.h.view().into_dyn())
-            .map_err(|e| TranscribeError::Inference(format!("tensor h: {e}")))?;
-        let t_c = TensorRef::from_array_view(self.c.view().into_dyn())
-            .map_err(|e| TranscribeError::Inference(format!("tensor c: {e}")))?;
-
-        let outputs = self
-            .session
-            .run(inputs![
-                "input" => t_input,
-                "sr" => t_sr,
-                "h" => t_h,
-                "c" => t_c,
-            ])
-            .map_err(|e| TranscribeError::Inference(e.to_string()))?;
-
-        // Extract updated LSTM states
-        let hn = outputs
-            .get("hn")
-            .ok_or_else(|| TranscribeError::Inference("missing output: hn".to_string()))?
-            .try_extract_array::<f32>()
-            .map_err(|e| TranscribeError::Inference(format!("extract hn: {e}")))?;
-        self.h = hn
-            .to_owned()
-            .into_shape_with_order((2, 1, 64))
-            .map_err(|e| TranscribeError::Inference(format!("reshape hn: {e}")))?;
-
-        let cn = outputs
-            .get("cn")
-            .ok_or_else(|| TranscribeError::Inference("missing output: cn".to_string()))?
-            .try_extract_array::<f32>()
-            .map_err(|e| TranscribeError::Inference(format!("extract cn: {e}")))?;
-        self.c = cn
-            .to_owned()
-            .into_shape_with_order((2, 1, 64))
-            .map_err(|e| TranscribeError::Inference(format!("reshape cn: {e}")))?;
-
-        let output = outputs
-            .get("output")
-            .ok_or_else(|| TranscribeError::Inference("missing output: output".to_string()))?
-            .try_extract_array::<f32>()
-            .map_err(|e| TranscribeError::Inference(format!("extract output: {e}")))?;
-
-        Ok(output[[0, 0]])
+/// Tell v4 from v5 by the model's state inputs.
+fn detect_version(session: &Session) -> Result<SileroVersion, TranscribeError> {
+    let input_names: Vec<&str> = session.inputs().iter().map(|i| i.name()).collect();
+    if input_names.contains(&"state") {
+        Ok(SileroVersion::V5)
+    } else if input_names.contains(&"h") && input_names.contains(&"c") {
+        Ok(SileroVersion::V4)
+    } else {
+        Err(TranscribeError::Config(format!(
+            "unrecognized Silero VAD model inputs: {input_names:?}"
+        )))
     }
 }
 
 impl Vad for SileroVad {
     fn frame_size(&self) -> usize {
-        FRAME_SAMPLES
+        match self.version {
+            SileroVersion::V4 => V4_FRAME_SAMPLES,
+            SileroVersion::V5 => V5_FRAME_SAMPLES,
+        }
     }
 
     fn is_speech(&mut self, frame: &[f32]) -> Result<bool, TranscribeError> {
         Ok(self.speech_probability(frame)? > self.threshold)
     }
 
+    fn speech_probability(&mut self, frame: &[f32]) -> Result<f32, TranscribeError> {
+        let frame_size = self.frame_size();
+        if frame.len() != frame_size {
+            return Err(TranscribeError::Audio(format!(
+                "expected {frame_size} samples, got {}",
+                frame.len()
+            )));
+        }
+
+        let t_sr = TensorRef::from_array_view(self.sr.view().into_dyn())
+            .map_err(|e| TranscribeError::Inference(format!("tensor sr: {e}")))?;
+
+        match &mut self.state {
+            ModelState::V4 { h, c } => {
+                let input = ArrayView2::from_shape((1, V4_FRAME_SAMPLES), frame)
+                    .map_err(|e| TranscribeError::Inference(e.to_string()))?;
+
+                let t_input = TensorRef::from_array_view(input.into_dyn())
+                    .map_err(|e| TranscribeError::Inference(format!("tensor input: {e}")))?;
+                let t_h = TensorRef::from_array_view(h.view().into_dyn())
+                    .map_err(|e| TranscribeError::Inference(format!("tensor h: {e}")))?;
+                let t_c = TensorRef::from_array_view(c.view().into_dyn())
+                    .map_err(|e| TranscribeError::Inference(format!("tensor c: {e}")))?;
+
+                let outputs = self
+                    .session
+                    .run(inputs![
+                        "input" => t_input,
+                        "sr" => t_sr,
+                        "h" => t_h,
+                        "c" => t_c,
+                    ])
+                    .map_err(|e| TranscribeError::Inference(e.to_string()))?;
+
+                // Extract updated LSTM states
+                let hn = outputs
+                    .get("hn")
+                    .ok_or_else(|| TranscribeError::Inference("missing output: hn".to_string()))?
+                    .try_extract_array::<f32>()
+                    .map_err(|e| TranscribeError::Inference(format!("extract hn: {e}")))?;
+                *h = hn
+                    .to_owned()
+                    .into_shape_with_order((2, 1, 64))
+                    .map_err(|e| TranscribeError::Inference(format!("reshape hn: {e}")))?;
+
+                let cn = outputs
+                    .get("cn")
+                    .ok_or_else(|| TranscribeError::Inference("missing output: cn".to_string()))?
+                    .try_extract_array::<f32>()
+                    .map_err(|e| TranscribeError::Inference(format!("extract cn: {e}")))?;
+                *c = cn
+                    .to_owned()
+                    .into_shape_with_order((2, 1, 64))
+                    .map_err(|e| TranscribeError::Inference(format!("reshape cn: {e}")))?;
+
+                let output = outputs
+                    .get("output")
+                    .ok_or_else(|| {
+                        TranscribeError::Inference("missing output: output".to_string())
+                    })?
+                    .try_extract_array::<f32>()
+                    .map_err(|e| TranscribeError::Inference(format!("extract output: {e}")))?;
+
+                Ok(output[[0, 0]])
+            }
+            ModelState::V5 { state, context } => {
+                let window = v5_window(context, frame);
+
+                let input = ArrayView2::from_shape((1, window.len()), &window)
+                    .map_err(|e| TranscribeError::Inference(e.to_string()))?;
+
+                let t_input = TensorRef::from_array_view(input.into_dyn())
+                    .map_err(|e| TranscribeError::Inference(format!("tensor input: {e}")))?;
+                let t_state = TensorRef::from_array_view(state.view().into_dyn())
+                    .map_err(|e| TranscribeError::Inference(format!("tensor state: {e}")))?;
+
+                let outputs = self
+                    .session
+                    .run(inputs![
+                        "input" => t_input,
+                        "state" => t_state,
+                        "sr" => t_sr,
+                    ])
+                    .map_err(|e| TranscribeError::Inference(e.to_string()))?;
+
+                let state_n = outputs
+                    .get("stateN")
+                    .ok_or_else(|| {
+                        TranscribeError::Inference("missing output: stateN".to_string())
+                    })?
+                    .try_extract_array::<f32>()
+                    .map_err(|e| TranscribeError::Inference(format!("extract stateN: {e}")))?;
+                *state = state_n
+                    .to_owned()
+                    .into_shape_with_order((2, 1, 128))
+                    .map_err(|e| TranscribeError::Inference(format!("reshape stateN: {e}")))?;
+                // Advance the context only with the state, so a failed run
+                // leaves both at the previous frame.
+                context.copy_from_slice(&frame[V5_FRAME_SAMPLES - V5_CONTEXT_SAMPLES..]);
+
+                let output = outputs
+                    .get("output")
+                    .ok_or_else(|| {
+                        TranscribeError::Inference("missing output: output".to_string())
+                    })?
+                    .try_extract_array::<f32>()
+                    .map_err(|e| TranscribeError::Inference(format!("extract output: {e}")))?;
+
+                Ok(output[[0, 0]])
+            }
+        }
+    }
+
     fn reset(&mut self) {
-        self.h.fill(0.0);
-        self.c.fill(0.0);
+        self.state = ModelState::new(self.version);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn v5_window_is_context_then_frame() {
+        let context: Vec<f32> = (0..V5_CONTEXT_SAMPLES).map(|i| -(i as f32)).collect();
+        let frame: Vec<f32> = (0..V5_FRAME_SAMPLES).map(|i| i as f32).collect();
+        let window = v5_window(&context, &frame);
+        assert_eq!(window.len(), 576);
+        assert_eq!(&window[..V5_CONTEXT_SAMPLES], &context[..]);
+        assert_eq!(&window[V5_CONTEXT_SAMPLES..], &frame[..]);
     }
 }
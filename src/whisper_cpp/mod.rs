@@ -30,11 +30,12 @@ pub mod gpu;
 
 use crate::accel::{get_whisper_accelerator, get_whisper_gpu_device, GPU_DEVICE_AUTO};
 use crate::{
-    ModelCapabilities, SpeechModel, TranscribeError, TranscribeOptions, TranscriptionResult,
-    TranscriptionSegment,
+    CancellationToken, ModelCapabilities, SpeechModel, TranscribeError, TranscribeOptions,
+    TranscriptionResult, TranscriptionSegment,
 };
 use gpu::auto_select_gpu_device;
 use log::info;
+use std::ffi::c_void;
 use std::path::Path;
 use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
 
@@ -114,6 +115,9 @@ pub struct WhisperInferenceParams {
     /// Start each decode with a clean prompt (whisper.cpp's `prompt_past`).
     /// Default `true` suits push-to-talk; set `false` for continuous speech.
     pub no_context: bool,
+
+    /// Token to abort decoding; passed to whisper.cpp as its abort callback.
+    pub cancel: Option<CancellationToken>,
 }
 
 impl Default for WhisperInferenceParams {
@@ -131,6 +135,7 @@ impl Default for WhisperInferenceParams {
             n_threads: 0,
             initial_prompt: None,
             no_context: true,
+            cancel: None,
         }
     }
 }
@@ -234,9 +239,29 @@ impl WhisperEngine {
             full_params.set_initial_prompt(prompt);
         }
 
-        self.state
-            .full(full_params, samples)
-            .map_err(|e| TranscribeError::Inference(e.to_string()))?;
+        if let Some(cancel) = &params.cancel {
+            cancel.check()?;
+            // SAFETY: `cancel` is borrowed from `params`, which outlives the
+            // `state.full` call below, and the callback only reads it.
+            unsafe {
+                full_params.set_abort_callback(Some(abort_if_cancelled));
+                full_params.set_abort_callback_user_data(
+                    cancel as *const CancellationToken as *mut c_void,
+                );
+            }
+        }
+
+        self.state.full(full_params, samples).map_err(|e| {
+            if params.cancel.as_ref().is_some_and(|c| c.is_cancelled()) {
+                TranscribeError::Cancelled
+            } else {
+                TranscribeError::Inference(e.to_string())
+            }
+        })?;
+        if let Some(cancel) = &params.cancel {
+            // whisper.cpp may stop early without reporting an error.
+            cancel.check()?;
+        }
 
         let num_segments = self.state.full_n_segments();
 
@@ -269,6 +294,14 @@ impl WhisperEngine {
     }
 }
 
+/// whisper.cpp abort callback; `user_data` points at the request's
+/// [`CancellationToken`].
+unsafe extern "C" fn abort_if_cancelled(user_data: *mut c_void) -> bool {
+    // SAFETY: `infer` sets `user_data` to a token that stays borrowed for the
+    // whole `state.full` call.
+    unsafe { &*(user_data as *const CancellationToken) }.is_cancelled()
+}
+
 impl SpeechModel for WhisperEngine {
     fn capabilities(&self) -> ModelCapabilities {
         ModelCapabilities {
@@ -281,6 +314,7 @@ impl SpeechModel for WhisperEngine {
                 ENGLISH_ONLY_LANGUAGES
             },
             supports_timestamps: true,
+            supports_word_timestamps: false,
             supports_translation: self.is_multilingual,
             supports_streaming: false,
         }
@@ -294,6 +328,7 @@ impl SpeechModel for WhisperEngine {
         let params = WhisperInferenceParams {
             language: options.language.clone(),
             translate: options.translate,
+            cancel: options.cancel.clone(),
             ..Default::default()
         };
         self.infer(samples, &params)
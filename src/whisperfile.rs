@@ -23,6 +23,7 @@ const CAPABILITIES: ModelCapabilities = ModelCapabilities {
         "mg", "as", "tt", "haw", "ln", "ha", "ba", "jw", "su", "yue",
     ],
     supports_timestamps: true,
+    supports_word_timestamps: false,
     supports_translation: true,
     supports_streaming: false,
 };
@@ -68,8 +68,7 @@ fn test_canary_v2_itn() {
         return;
     }
 
-    let mut model =
-        CanaryModel::load(&model_dir, &Quantization::Int8).expect("Failed to load model");
+    let model = CanaryModel::load(&model_dir, &Quantization::Int8).expect("Failed to load model");
     let samples = transcribe_rs::audio::read_wav_samples(&wav_path).expect("Failed to read WAV");
 
     // With ITN: spoken numbers should be converted to digits
@@ -120,8 +119,7 @@ fn test_canary_v2_pnc() {
         return;
     }
 
-    let mut model =
-        CanaryModel::load(&model_dir, &Quantization::Int8).expect("Failed to load model");
+    let model = CanaryModel::load(&model_dir, &Quantization::Int8).expect("Failed to load model");
     let samples = transcribe_rs::audio::read_wav_samples(&wav_path).expect("Failed to read WAV");
 
     // With PnC: should have commas or periods
@@ -173,8 +171,7 @@ fn test_canary_v2_translation() {
         return;
     }
 
-    let mut model =
-        CanaryModel::load(&model_dir, &Quantization::Int8).expect("Failed to load model");
+    let model = CanaryModel::load(&model_dir, &Quantization::Int8).expect("Failed to load model");
     let samples = transcribe_rs::audio::read_wav_samples(&wav_path).expect("Failed to read WAV");
 
     // Translate German to English
@@ -212,8 +209,7 @@ fn test_canary_v2_german_transcription() {
         return;
     }
 
-    let mut model =
-        CanaryModel::load(&model_dir, &Quantization::Int8).expect("Failed to load model");
+    let model = CanaryModel::load(&model_dir, &Quantization::Int8).expect("Failed to load model");
     let samples = transcribe_rs::audio::read_wav_samples(&wav_path).expect("Failed to read WAV");
 
     // Transcribe German as German (no translation)
@@ -247,8 +243,7 @@ fn test_canary_v2_translate_en_to_de() {
         return;
     }
 
-    let mut model =
-        CanaryModel::load(&model_dir, &Quantization::Int8).expect("Failed to load model");
+    let model = CanaryModel::load(&model_dir, &Quantization::Int8).expect("Failed to load model");
     let samples = transcribe_rs::audio::read_wav_samples(&wav_path).expect("Failed to read WAV");
 
     // Translate English to German
@@ -283,8 +278,7 @@ fn test_canary_v2_translate_en_to_es() {
         return;
     }
 
-    let mut model =
-        CanaryModel::load(&model_dir, &Quantization::Int8).expect("Failed to load model");
+    let model = CanaryModel::load(&model_dir, &Quantization::Int8).expect("Failed to load model");
     let samples = transcribe_rs::audio::read_wav_samples(&wav_path).expect("Failed to read WAV");
 
     let result = model
@@ -364,8 +358,7 @@ fn test_canary_flash_itn_ignored() {
         return;
     }
 
-    let mut model =
-        CanaryModel::load(&model_dir, &Quantization::Int8).expect("Failed to load model");
+    let model = CanaryModel::load(&model_dir, &Quantization::Int8).expect("Failed to load model");
     let samples = transcribe_rs::audio::read_wav_samples(&wav_path).expect("Failed to read WAV");
 
     // ITN enabled on Flash should be silently ignored and still produce output
@@ -39,3 +39,90 @@ fn test_moonshine_base_jfk() {
         result.text.trim()
     );
 }
+
+#[test]
+fn test_moonshine_streaming_events_jfk() {
+    use transcribe_rs::onnx::moonshine::StreamingModel;
+    use transcribe_rs::streaming::{StreamEvent, StreamOptions};
+    use transcribe_rs::StreamingSpeechModel;
+
+    let model_path = PathBuf::from("models/moonshine-streaming/moonshine-tiny-streaming-en");
+    let audio_path = PathBuf::from("samples/jfk.wav");
+
+    if !common::require_paths(&[&model_path, &audio_path]) {
+        return;
+    }
+
+    let mut model = StreamingModel::load(&model_path, 4, &Quantization::default())
+        .expect("Failed to load model");
+    let samples = transcribe_rs::audio::read_wav_samples(&audio_path).expect("Failed to read wav");
+
+    model
+        .create_stream(&StreamOptions::default())
+        .expect("Failed to create stream");
+    let mut partials = 0;
+    let mut endpoints = Vec::new();
+    for frame in samples.chunks(1600) {
+        for event in model.push_audio(frame).expect("Failed to push audio") {
+            match event {
+                StreamEvent::Partial { .. } => partials += 1,
+                StreamEvent::Endpoint { result } => endpoints.push(result),
+                StreamEvent::Stable { .. } => {}
+            }
+        }
+    }
+    let session = model.finalize().expect("Failed to finalize");
+
+    println!("Streaming transcription: {}", session.text);
+
+    assert!(partials > 0, "expected partial hypotheses while streaming");
+    assert!(
+        endpoints.iter().all(|r| !r.text.trim().is_empty()),
+        "endpoint results should not be empty"
+    );
+    let text = session.text.to_lowercase();
+    assert!(
+        text.contains("fellow americans") && text.contains("your country"),
+        "unexpected transcription: {}",
+        session.text
+    );
+}
+
+#[test]
+fn test_moonshine_streaming_cancelled_mid_utterance() {
+    use transcribe_rs::onnx::moonshine::StreamingModel;
+    use transcribe_rs::streaming::StreamOptions;
+    use transcribe_rs::{CancellationToken, StreamingSpeechModel, TranscribeError};
+
+    let model_path = PathBuf::from("models/moonshine-streaming/moonshine-tiny-streaming-en");
+    let audio_path = PathBuf::from("samples/jfk.wav");
+
+    if !common::require_paths(&[&model_path, &audio_path]) {
+        return;
+    }
+
+    let mut model = StreamingModel::load(&model_path, 4, &Quantization::default())
+        .expect("Failed to load model");
+    let samples = transcribe_rs::audio::read_wav_samples(&audio_path).expect("Failed to read wav");
+
+    let cancel = CancellationToken::new();
+    let mut options = StreamOptions::default();
+    options.transcribe.cancel = Some(cancel.clone());
+    model
+        .create_stream(&options)
+        .expect("Failed to create stream");
+
+    // Two seconds into the first sentence, then cancel while it is still
+    // being spoken: the next hypothesis decode must stop.
+    let (head, tail) = samples.split_at(32000);
+    for frame in head.chunks(1600) {
+        model.push_audio(frame).expect("Failed to push audio");
+    }
+    cancel.cancel();
+    let err = tail
+        .chunks(1600)
+        .find_map(|frame| model.push_audio(frame).err())
+        .or_else(|| model.finalize().err())
+        .expect("cancelled stream should fail");
+    assert!(matches!(err, TranscribeError::Cancelled), "{err}");
+}
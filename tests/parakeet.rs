@@ -110,3 +110,37 @@ fn test_timestamps() {
         first_segment.start
     );
 }
+
+#[test]
+fn test_shared_model_across_threads() {
+    let model_path = PathBuf::from("models/parakeet-tdt-0.6b-v3-int8");
+    let audio_path = PathBuf::from("samples/jfk.wav");
+
+    if !common::require_paths(&[&model_path, &audio_path]) {
+        return;
+    }
+
+    let model =
+        ParakeetModel::load(&model_path, &Quantization::Int8).expect("Failed to load model");
+    let samples =
+        transcribe_rs::audio::read_wav_samples(&audio_path).expect("Failed to read audio");
+
+    let texts: Vec<String> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let mut model = model.clone();
+                let samples = &samples;
+                scope.spawn(move || {
+                    model
+                        .transcribe(samples, &transcribe_rs::TranscribeOptions::default())
+                        .expect("Failed to transcribe")
+                        .text
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).collect()
+    });
+
+    assert!(texts.iter().all(|text| text == &texts[0]));
+    assert!(texts[0].contains("ask not what your country can do for you"));
+}
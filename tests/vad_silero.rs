@@ -149,3 +149,50 @@ fn test_smoothed_silero_vad_chunk_count() {
     println!("SmoothedVad(Silero) on dots.wav: {chunks} speech chunks");
     assert!(chunks >= 1, "Should detect at least one speech chunk");
 }
+
+/// `SileroVad::speech_probability` stays callable without importing `Vad`.
+mod without_vad_trait {
+    use std::path::PathBuf;
+
+    use transcribe_rs::vad::SileroVad;
+
+    #[test]
+    fn test_silero_speech_probability_inherent() {
+        let model_path = PathBuf::from("models/silero_vad_v4.onnx");
+
+        if !super::common::require_paths(&[&model_path]) {
+            return;
+        }
+
+        let mut vad = SileroVad::new(&model_path, 0.3).expect("Failed to load Silero VAD");
+        let probability = vad
+            .speech_probability(&[0.0; 480])
+            .expect("Failed to run Silero VAD");
+        assert!((0.0..0.3).contains(&probability), "silence: {probability}");
+    }
+}
+
+#[cfg(feature = "onnx")]
+#[test]
+fn test_silero_vad_with_session_config() {
+    use transcribe_rs::onnx::SessionConfig;
+    use transcribe_rs::OrtAccelerator;
+
+    let model_path = PathBuf::from("models/silero_vad_v4.onnx");
+
+    if !common::require_paths(&[&model_path]) {
+        return;
+    }
+
+    let config = SessionConfig::default()
+        .with_execution_providers(vec![OrtAccelerator::CpuOnly])
+        .with_intra_threads(1);
+    let mut vad =
+        SileroVad::new_with_config(&model_path, 0.3, &config).expect("Failed to load Silero VAD");
+    assert_eq!(vad.frame_size(), 480);
+    assert!(!vad.is_speech(&[0.0; 480]).unwrap());
+
+    let bytes = std::fs::read(&model_path).unwrap();
+    let vad = SileroVad::from_bytes_with_config(&bytes, 0.3, &config).unwrap();
+    assert_eq!(vad.frame_size(), 480);
+}
@@ -186,3 +186,39 @@ fn test_timestamps() {
         last_segment.end
     );
 }
+
+#[test]
+fn test_cancel_stops_running_transcription() {
+    let mut guard = match get_engine() {
+        Some(g) => g,
+        None => {
+            eprintln!("Skipping test: whisper engine not available");
+            return;
+        }
+    };
+    let engine = guard.as_mut().unwrap();
+
+    let samples = transcribe_rs::audio::read_wav_samples(&PathBuf::from("samples/jfk.wav"))
+        .expect("Failed to read WAV");
+    let samples = samples.repeat(4);
+
+    let cancel = transcribe_rs::CancellationToken::new();
+    let canceller = {
+        let cancel = cancel.clone();
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            cancel.cancel();
+        })
+    };
+    let options = transcribe_rs::TranscribeOptions {
+        cancel: Some(cancel),
+        ..Default::default()
+    };
+    let result = engine.transcribe(&samples, &options);
+    canceller.join().unwrap();
+
+    assert!(
+        matches!(result, Err(transcribe_rs::TranscribeError::Cancelled)),
+        "expected cancellation, got {result:?}"
+    );
+}